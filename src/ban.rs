@@ -0,0 +1,254 @@
+use std::fmt;
+
+use crate::error::{NetworkError, TransactionError, ValidationError};
+use crate::events::Event;
+use crate::message::Message;
+use crate::network::{BlockchainNetwork, SimEvent};
+use crate::sim::SimTime;
+
+// Bir komşunun ceza puanını artıran davranışlar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Offense {
+    // Doğrulamadan geçmeyen blok
+    InvalidBlock,
+    // Çıktıları girdilerini aşan işlem
+    InvalidTransaction,
+    // Bilinmeyen veya harcanamayan UTXO'ları harcayan işlem (komşu geride kalmış da olabilir, hafif ceza)
+    UnknownInputs,
+    // Hash'i, mührü veya sırası tutmayan başlıklar
+    InvalidHeaders,
+    // Doğrulanamayan çifte imza kanıtı
+    InvalidEvidence,
+}
+
+impl Offense {
+    // Davranışın ceza puanına eklenen değer
+    pub fn score(&self) -> u32 {
+        match self {
+            // Yarışı kaybeden dürüst bir blok sonraki UTXO durumunda geçersiz görünebilir; tek blok yasaklatmaz
+            Offense::InvalidBlock => 50,
+            Offense::InvalidTransaction => 10,
+            Offense::UnknownInputs => 1,
+            Offense::InvalidHeaders => 20,
+            Offense::InvalidEvidence => 20,
+        }
+    }
+
+    // Reddedilen bloğun cezası; kesinleşmiş bir bloğun yerine geçmeye çalışan blok komşunun kusuru sayılmaz
+    // (bölünmüş ağın diğer tarafından gelen dürüst bir blok olabilir)
+    pub fn for_block(error: &ValidationError) -> Option<Offense> {
+        match error {
+            ValidationError::ConflictsWithFinalized { .. } => None,
+            _ => Some(Offense::InvalidBlock),
+        }
+    }
+
+    // Reddedilen işlemin cezası; mempool'daki başka bir işlemle çakışan işlemler cezalandırılmaz
+    pub fn for_transaction(error: &TransactionError) -> Option<Offense> {
+        match error {
+            TransactionError::OutputsExceedInputs { .. } => Some(Offense::InvalidTransaction),
+            TransactionError::MissingUtxo { .. } | TransactionError::StakeLocked { .. } => Some(Offense::UnknownInputs),
+            TransactionError::DoubleSpend { .. } | TransactionError::Wallet(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Offense {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Offense::InvalidBlock => write!(f, "geçersiz blok"),
+            Offense::InvalidTransaction => write!(f, "geçersiz işlem"),
+            Offense::UnknownInputs => write!(f, "bilinmeyen girdi"),
+            Offense::InvalidHeaders => write!(f, "geçersiz başlık"),
+            Offense::InvalidEvidence => write!(f, "geçersiz kanıt"),
+        }
+    }
+}
+
+// Bir node'un bir komşusunu yasaklaması
+#[derive(Debug, Clone)]
+pub struct BanRecord {
+    pub node_id: usize,
+    pub peer: usize,
+    pub reason: Offense, // Eşiği aşmaya yol açan son davranış
+    pub score: u32,      // Yasaklandığı andaki ceza puanı
+    pub banned_at: SimTime,
+    pub expires_at: SimTime,
+}
+
+impl fmt::Display for BanRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Node {} -> Node {}: {} ({} puan), {} ms'de yasaklandı, bitiş {} ms",
+            self.node_id, self.peer, self.reason, self.score, self.banned_at, self.expires_at
+        )
+    }
+}
+
+impl BlockchainNetwork {
+    // Komşunun ceza puanını artır; eşiğe ulaşırsa bağlantıyı kes ve komşuyu süreli yasakla
+    pub(crate) fn penalize(&mut self, node_id: usize, peer: usize, offense: Option<Offense>) {
+        let offense = match offense {
+            Some(offense) => offense,
+            None => return,
+        };
+        let threshold = self.ban_threshold;
+        let node = match self.nodes.get_mut(node_id) {
+            Some(node) => node,
+            None => return,
+        };
+        let score = node.ban_scores.entry(peer).or_insert(0);
+        *score += offense.score();
+        let score = *score;
+
+        if score >= threshold {
+            self.ban_peer(node_id, peer, offense, score);
+        }
+    }
+
+    // Komşuyu yasakla: bağlantı kesilir ve yasak süresince yeniden kurulamaz
+    fn ban_peer(&mut self, node_id: usize, peer: usize, reason: Offense, score: u32) {
+        let now = self.now();
+        let expires_at = now.saturating_add(self.ban_duration);
+        let node = &mut self.nodes[node_id];
+        node.ban_scores.remove(&peer);
+        node.banned.insert(peer, expires_at);
+        // Yasaklanan komşudan senkronizasyon sırasında gövde istenmez
+        if let Some(sync) = node.sync.as_mut() {
+            sync.peers.retain(|&other| other != peer);
+            sync.pending.retain(|&other| other != peer);
+        }
+
+        self.disconnect_nodes(node_id, peer);
+        self.bans.push(BanRecord { node_id, peer, reason, score, banned_at: now, expires_at });
+        self.scheduler.schedule_at(expires_at, SimEvent::BanExpired { node: node_id, peer });
+        self.emit(Event::PeerBanned { node: node_id, peer, reason: reason.to_string(), score });
+    }
+
+    // Yasağın süresi doldu: komşu tekrar bağlanır ve zincir uçları eşitlenir
+    pub(crate) fn handle_ban_expired(&mut self, node_id: usize, peer: usize) {
+        let now = self.now();
+        // Yasak bu arada kaldırıldıysa veya yenilendiyse bir şey yapma
+        match self.nodes[node_id].banned.get(&peer) {
+            Some(&expires_at) if expires_at <= now => {}
+            _ => return,
+        }
+        self.nodes[node_id].banned.remove(&peer);
+        self.reconnect_peer(node_id, peer);
+    }
+
+    // Yasağı süresinden önce kaldır; komşu tekrar bağlanır
+    pub fn unban(&mut self, node_id: usize, peer: usize) -> Result<bool, NetworkError> {
+        let node = self.nodes.get_mut(node_id).ok_or(NetworkError::NodeNotFound(node_id))?;
+        if node.banned.remove(&peer).is_none() {
+            return Ok(false);
+        }
+        self.reconnect_peer(node_id, peer);
+        self.process_due_events();
+        Ok(true)
+    }
+
+    // Node'un yasaklı olmayan ama ceza puanı olan komşuları (komşu, puan)
+    pub fn ban_scores(&self, node_id: usize) -> Vec<(usize, u32)> {
+        let mut scores: Vec<(usize, u32)> = match self.nodes.get(node_id) {
+            Some(node) => node.ban_scores.iter().map(|(&peer, &score)| (peer, score)).collect(),
+            None => Vec::new(),
+        };
+        scores.sort_unstable();
+        scores
+    }
+
+    // Süresi dolmamış yasaklar (node verilirse sadece onun yasakladıkları)
+    pub fn active_bans(&self, node_id: Option<usize>) -> Vec<&BanRecord> {
+        self.bans
+            .iter()
+            .filter(|record| node_id.map(|id| record.node_id == id).unwrap_or(true))
+            .filter(|record| self.nodes[record.node_id].banned.get(&record.peer) == Some(&record.expires_at))
+            .collect()
+    }
+
+    // İki node'dan biri diğerini yasaklamış mı
+    pub fn is_banned(&self, node1_id: usize, node2_id: usize) -> bool {
+        let bans = |a: usize, b: usize| self.nodes.get(a).map(|node| node.banned.contains_key(&b)).unwrap_or(false);
+        bans(node1_id, node2_id) || bans(node2_id, node1_id)
+    }
+
+    // Yasak kalkınca bağlantıyı yeniden kur; karşı taraf da yasaklamıyorsa iki uç birbirinden başlık ister
+    fn reconnect_peer(&mut self, node_id: usize, peer: usize) {
+        if self.connect_nodes(node_id, peer).is_err() {
+            return;
+        }
+        for (from, to) in [(node_id, peer), (peer, node_id)] {
+            let locator = self.nodes[from].block_locator();
+            self.send_message(from, to, Message::GetHeaders { locator });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::events::EventRecord;
+    use crate::topology::Topology;
+
+    fn network() -> BlockchainNetwork {
+        let mut network = BlockchainNetwork::with_seed(7);
+        for _ in 0..3 {
+            network.add_node();
+        }
+        network.apply_topology(&Topology::FullMesh).unwrap();
+        network
+    }
+
+    #[test]
+    fn one_invalid_block_does_not_ban() {
+        let mut network = network();
+        let banned = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&banned);
+        network.events.subscribe(move |record: &EventRecord| {
+            if let Event::PeerBanned { node, peer, score, .. } = &record.event {
+                sink.lock().unwrap().push((*node, *peer, *score));
+            }
+        });
+
+        network.penalize(0, 1, Some(Offense::InvalidBlock));
+        assert_eq!(network.ban_scores(0), vec![(1, Offense::InvalidBlock.score())]);
+        assert!(!network.is_banned(0, 1));
+        assert!(network.nodes[0].connections.contains(&1));
+        assert!(banned.lock().unwrap().is_empty());
+
+        network.penalize(0, 1, Some(Offense::InvalidBlock));
+        assert!(network.is_banned(0, 1));
+        assert!(!network.nodes[0].connections.contains(&1));
+        assert!(!network.nodes[1].connections.contains(&0));
+        assert!(network.ban_scores(0).is_empty());
+        assert_eq!(network.active_bans(Some(0)).len(), 1);
+        assert_eq!(*banned.lock().unwrap(), vec![(0, 1, 2 * Offense::InvalidBlock.score())]);
+    }
+
+    #[test]
+    fn banned_pair_cannot_connect_until_unbanned() {
+        let mut network = network();
+        network.penalize(0, 1, Some(Offense::InvalidBlock));
+        network.penalize(0, 1, Some(Offense::InvalidBlock));
+
+        assert_eq!(network.connect_nodes(1, 0), Err(NetworkError::Banned { node: 1, peer: 0 }));
+        assert!(!network.nodes[0].connections.contains(&1));
+        // Yasakla ilgisi olmayan bağlantılar etkilenmez
+        assert!(network.nodes[0].connections.contains(&2));
+
+        assert_eq!(network.unban(0, 1), Ok(true));
+        assert!(network.nodes[0].connections.contains(&1));
+        assert!(network.nodes[1].connections.contains(&0));
+    }
+
+    #[test]
+    fn connect_nodes_reports_invalid_targets() {
+        let mut network = network();
+        assert_eq!(network.connect_nodes(1, 1), Err(NetworkError::SelfConnection(1)));
+        assert_eq!(network.connect_nodes(0, 5), Err(NetworkError::NodeNotFound(5)));
+    }
+}
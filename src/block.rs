@@ -2,7 +2,9 @@ use std::fmt;
 use sha2::{Sha256, Digest};
 
 // Transaction modülünü kullan
+use crate::slashing::EquivocationEvidence;
 use crate::transaction::Transaction;
+use crate::wallet::Wallet;
 
 // Block yapısı
 #[derive(Debug, Clone)]
@@ -14,10 +16,55 @@ pub struct Block {
     pub hash: String,
     pub nonce: u64,
     pub merkle_root: String,            // Merkle kök hash'i
+    pub proposer: String,               // PoS: bloğu öneren validator'un public key'i (hex), PoW'da boş
+    pub signature: Vec<u8>,             // PoS: önerenin blok hash'i üzerindeki imzası, PoW'da boş
+    pub evidence: Vec<EquivocationEvidence>, // Cezası bu blokta uygulanan çifte imza kanıtları
+}
+
+// Blok başlığı: işlemler olmadan bloğun hash'ini ve PoW'unu doğrulamaya yeten alanlar
+// İlk senkronizasyonda önce başlıklar indirilip zincir doğrulanır, gövdeler sonra istenir
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockHeader {
+    pub index: usize,
+    pub timestamp: u64,
+    pub previous_hash: String,
+    pub hash: String,
+    pub nonce: u64,
+    pub merkle_root: String,
+    pub proposer: String,
+    pub signature: Vec<u8>,
+}
+
+impl BlockHeader {
+    // Başlık alanlarından hash'i yeniden hesapla (Block::calculate_hash ile aynı)
+    pub fn calculate_hash(&self) -> String {
+        header_hash(self.index, self.timestamp, &self.merkle_root, &self.previous_hash, self.nonce, &self.proposer)
+    }
+
+    // Başlığın yaklaşık boyutu (byte)
+    pub fn size(&self) -> usize {
+        24 + self.previous_hash.len() + self.hash.len() + self.merkle_root.len() + self.proposer.len() + self.signature.len()
+    }
+}
+
+// Başlık alanlarının SHA-256 hash'i
+// PoW bloklarında önerici boş olduğundan hash eskisiyle aynı kalır
+fn header_hash(index: usize, timestamp: u64, merkle_root: &str, previous_hash: &str, nonce: u64, proposer: &str) -> String {
+    let mut hasher = Sha256::new();
+    let contents = format!("{}{}{}{}{}{}", index, timestamp, merkle_root, previous_hash, nonce, proposer);
+    hasher.update(contents.as_bytes());
+    let result = hasher.finalize();
+    format!("{:x}", result)
 }
 
 impl Block {
     pub fn new(index: usize, timestamp: u64, transactions: Vec<Transaction>, previous_hash: String) -> Self {
+        Self::with_evidence(index, timestamp, transactions, Vec::new(), previous_hash)
+    }
+    
+    // Çifte imza kanıtları içeren blok oluştur
+    // Kanıtlara karşılık gelen ceza işlemleri, işlem listesinin sonunda aynı sırayla yer almalı
+    pub fn with_evidence(index: usize, timestamp: u64, transactions: Vec<Transaction>, evidence: Vec<EquivocationEvidence>, previous_hash: String) -> Self {
         let mut block = Block {
             index,
             timestamp,
@@ -26,6 +73,9 @@ impl Block {
             hash: String::new(),
             nonce: 0,
             merkle_root: String::new(),
+            proposer: String::new(),
+            signature: Vec::new(),
+            evidence,
         };
         
         // Merkle kök hash'ini hesapla
@@ -38,22 +88,27 @@ impl Block {
 
     // Block'un hash'ini hesapla
     pub fn calculate_hash(&self) -> String {
-        let mut hasher = Sha256::new();
-        let contents = format!("{}{}{}{}{}", 
-            self.index, 
-            self.timestamp, 
-            self.merkle_root, 
-            self.previous_hash, 
-            self.nonce
-        );
-        hasher.update(contents.as_bytes());
-        let result = hasher.finalize();
-        format!("{:x}", result)
+        header_hash(self.index, self.timestamp, &self.merkle_root, &self.previous_hash, self.nonce, &self.proposer)
+    }
+    
+    // Bloğun başlığı
+    pub fn header(&self) -> BlockHeader {
+        BlockHeader {
+            index: self.index,
+            timestamp: self.timestamp,
+            previous_hash: self.previous_hash.clone(),
+            hash: self.hash.clone(),
+            nonce: self.nonce,
+            merkle_root: self.merkle_root.clone(),
+            proposer: self.proposer.clone(),
+            signature: self.signature.clone(),
+        }
     }
     
     // Merkle kök hash'ini hesapla
+    // Yaprakları işlem ID'leri ve ardından (varsa) kanıt hash'leridir
     pub fn calculate_merkle_root(&self) -> String {
-        if self.transactions.is_empty() {
+        if self.transactions.is_empty() && self.evidence.is_empty() {
             return "0".to_string();
         }
         
@@ -61,15 +116,16 @@ impl Block {
         let mut hashes: Vec<String> = self.transactions
             .iter()
             .map(|tx| tx.id.clone())
+            .chain(self.evidence.iter().map(|evidence| evidence.hash()))
             .collect();
         
-        // Tek sayıda hash varsa, son hash'i tekrarla
-        if hashes.len() % 2 == 1 {
-            hashes.push(hashes.last().unwrap().clone());
-        }
-        
         // Merkle ağacını oluştur
         while hashes.len() > 1 {
+            // Her seviyede tek sayıda hash varsa, son hash'i tekrarla
+            if hashes.len() % 2 == 1 {
+                hashes.push(hashes.last().unwrap().clone());
+            }
+            
             let mut new_hashes = Vec::new();
             
             // İkişerli grupla ve hash'le
@@ -87,6 +143,14 @@ impl Block {
         hashes[0].clone()
     }
 
+    // Bloğun yaklaşık boyutu (byte)
+    // Başlık: indeks, zaman damgası ve nonce (8'er byte) + önceki hash, hash ve merkle kökü
+    pub fn size(&self) -> usize {
+        self.header().size()
+            + self.transactions.iter().map(|tx| tx.size()).sum::<usize>()
+            + self.evidence.iter().map(|evidence| evidence.size()).sum::<usize>()
+    }
+    
     // Proof of Work (basit bir zorluk seviyesi)
     pub fn mine_block(&mut self, difficulty: usize) {
         let target = "0".repeat(difficulty); // Hedef: belirli sayıda 0 ile başlayan hash
@@ -98,6 +162,14 @@ impl Block {
         
         // Block mined
     }
+    
+    // Proof of Stake: bloğu PoW yerine önerenin anahtarıyla mühürle
+    // Önerenin public key'i hash'e dahil edilir, imza ise hash'in üzerine atılır
+    pub fn sign(&mut self, wallet: &Wallet) {
+        self.proposer = hex::encode(wallet.get_public_key().serialize());
+        self.hash = self.calculate_hash();
+        self.signature = wallet.sign(self.hash.as_bytes());
+    }
 }
 
 impl fmt::Display for Block {
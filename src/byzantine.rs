@@ -0,0 +1,427 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+use rand::Rng;
+
+use crate::block::{Block, BlockHeader};
+use crate::error::NetworkError;
+use crate::message::{InvItem, Message};
+use crate::network::{BlockchainNetwork, SimEvent};
+use crate::node::Node;
+use crate::sim::SimTime;
+use crate::transaction::{Transaction, TxInput, TxOutput};
+
+// Spam gönderen node'un gönderim aralığı (ms)
+const SPAM_INTERVAL: SimTime = 1000;
+
+// Bir node'un ağ katmanındaki kötü niyetli davranışı
+// (BFT kesinlik katmanındaki oylama davranışları için BftFault'a bakın)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Misbehavior {
+    // Aldığı işlemleri iletmez ve istendiğinde göndermez
+    DropTransactions,
+    // Ürettiği ve aldığı blokları iletmez ve istendiğinde göndermez
+    WithholdBlocks,
+    // Blokları coinbase ödülünü şişirerek bozar; bozuk kopyaları komşularına doğrudan gönderir
+    RelayInvalidBlocks,
+    // Her saniye her komşusuna var olmayan coinleri harcayan sahte işlemler gönderir
+    SpamMempool { per_second: usize },
+    // Gönderdiği her mesajı bekletir
+    DelayMessages { delay: SimTime },
+    // Var olmayan blokları duyurur ve başlık isteklerine sahte bir zincir ucu ekler
+    LieAboutTips,
+}
+
+impl FromStr for Misbehavior {
+    type Err = String;
+
+    // "droptx", "withhold", "invalid", "spam[:adet]", "delay[:ms]" veya "lie"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, value) = match s.split_once(':') {
+            Some((name, value)) => (name, Some(value)),
+            None => (s, None),
+        };
+        let number = |default: u64| match value {
+            Some(value) => value.parse::<u64>().map_err(|_| format!("geçersiz sayı: {}", value)),
+            None => Ok(default),
+        };
+
+        match name {
+            "droptx" => Ok(Misbehavior::DropTransactions),
+            "withhold" => Ok(Misbehavior::WithholdBlocks),
+            "invalid" => Ok(Misbehavior::RelayInvalidBlocks),
+            "spam" => Ok(Misbehavior::SpamMempool { per_second: number(5)? as usize }),
+            "delay" => Ok(Misbehavior::DelayMessages { delay: number(2000)? }),
+            "lie" => Ok(Misbehavior::LieAboutTips),
+            other => Err(format!("bilinmeyen davranış: {} (droptx, withhold, invalid, spam[:adet], delay[:ms], lie)", other)),
+        }
+    }
+}
+
+impl fmt::Display for Misbehavior {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Misbehavior::DropTransactions => write!(f, "işlemleri düşürür"),
+            Misbehavior::WithholdBlocks => write!(f, "blokları saklar"),
+            Misbehavior::RelayInvalidBlocks => write!(f, "geçersiz blok yayar"),
+            Misbehavior::SpamMempool { per_second } => write!(f, "saniyede {} sahte işlem gönderir", per_second),
+            Misbehavior::DelayMessages { delay } => write!(f, "mesajları {} ms geciktirir", delay),
+            Misbehavior::LieAboutTips => write!(f, "zincir ucu hakkında yalan söyler"),
+        }
+    }
+}
+
+// Kötü niyetli bir node'un davranışlarının ağ üzerindeki etkileri
+#[derive(Debug, Clone, Default)]
+pub struct ByzantineEffects {
+    pub dropped_transactions: usize,  // İletilmeyen veya istenip gönderilmeyen işlemler
+    pub withheld_blocks: usize,       // İletilmeyen veya istenip gönderilmeyen bloklar
+    pub invalid_blocks_sent: usize,   // Gönderilen bozuk bloklar
+    pub spam_sent: usize,             // Gönderilen sahte işlemler
+    pub delayed_messages: usize,      // Geciktirilen mesajlar
+    pub added_delay: SimTime,         // Mesajlara eklenen toplam gecikme (ms)
+    pub false_announcements: usize,   // Var olmayan blok duyuruları
+    pub fake_headers_sent: usize,     // Sahte başlık eklenmiş başlık cevapları
+    pub rejected_blocks: usize,       // Dürüst node'ların bu node'dan gelip reddettiği bloklar
+    pub rejected_transactions: usize, // Dürüst node'ların bu node'dan gelip reddettiği işlemler
+    pub rejected_headers: usize,      // Dürüst node'ların reddettiği başlık cevapları
+    pub unanswered_requests: usize,   // Dürüst node'ların bu node'dan isteyip alamadığı öğeler
+}
+
+// Kötü niyetli bir node'un davranış profili ve kaydedilen etkileri
+#[derive(Debug, Clone, Default)]
+pub struct ByzantineNode {
+    pub behaviors: Vec<Misbehavior>,
+    pub effects: ByzantineEffects,
+}
+
+// Davranış profillerinin etkileri ve dürüst node'ların durumu
+#[derive(Debug, Clone)]
+pub struct ByzantineReport {
+    pub nodes: Vec<(usize, ByzantineNode)>,
+    pub honest_nodes: usize,
+    pub honest_height: usize, // Dürüst node'ların en uzun zinciri
+    pub honest_tips: usize,   // Dürüst node'ların farklı zincir ucu sayısı (1: uzlaşmışlar)
+}
+
+impl fmt::Display for ByzantineReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Kötü niyetli node'lar:")?;
+        for (id, node) in &self.nodes {
+            let behaviors: Vec<String> = node.behaviors.iter().map(|behavior| behavior.to_string()).collect();
+            let effects = &node.effects;
+            writeln!(f, "  Node {} ({}):", id, behaviors.join(", "))?;
+            writeln!(f, "    İletilmeyen: {} işlem, {} blok", effects.dropped_transactions, effects.withheld_blocks)?;
+            writeln!(
+                f,
+                "    Gönderilen: {} bozuk blok, {} sahte işlem, {} sahte duyuru, {} sahte başlık cevabı",
+                effects.invalid_blocks_sent, effects.spam_sent, effects.false_announcements, effects.fake_headers_sent
+            )?;
+            writeln!(f, "    Geciktirilen mesaj: {} (toplam {} ms)", effects.delayed_messages, effects.added_delay)?;
+            writeln!(
+                f,
+                "    Dürüst node'ların reddettiği: {} blok, {} işlem, {} başlık cevabı; cevapsız istek: {}",
+                effects.rejected_blocks, effects.rejected_transactions, effects.rejected_headers, effects.unanswered_requests
+            )?;
+        }
+        write!(
+            f,
+            "Dürüst node'lar: {}, en uzun zincir {}, farklı zincir ucu {}",
+            self.honest_nodes, self.honest_height, self.honest_tips
+        )
+    }
+}
+
+impl BlockchainNetwork {
+    // Bir node'a ağ katmanı davranış profili ata (boş liste: dürüst)
+    pub fn set_misbehavior(&mut self, node_id: usize, behaviors: Vec<Misbehavior>) -> Result<(), NetworkError> {
+        if node_id >= self.nodes.len() {
+            return Err(NetworkError::NodeNotFound(node_id));
+        }
+
+        if behaviors.is_empty() {
+            self.byzantine.remove(&node_id);
+        } else {
+            self.byzantine.entry(node_id).or_default().behaviors = behaviors;
+        }
+        self.schedule_spam(node_id);
+        Ok(())
+    }
+
+    // Spam gönderen node'un gönderimini (yeniden) planla; önceki plan iptal edilir
+    // Çevrimdışı node gönderim yapmaz, tekrar çevrimiçi olunca bu fonksiyonla yeniden başlar
+    pub(crate) fn schedule_spam(&mut self, node_id: usize) {
+        self.scheduler.retain(|event| !matches!(event, SimEvent::Spam { node } if *node == node_id));
+        if self.misbehaves(node_id, |behavior| matches!(behavior, Misbehavior::SpamMempool { .. })) {
+            self.scheduler.schedule_in(SPAM_INTERVAL, SimEvent::Spam { node: node_id });
+        }
+    }
+
+    // Davranış profillerinin şimdiye kadarki etkileri
+    pub fn byzantine_report(&self) -> ByzantineReport {
+        let honest: Vec<_> = self.nodes.iter().filter(|node| !self.byzantine.contains_key(&node.id)).collect();
+        let tips: HashSet<&str> = honest
+            .iter()
+            .filter_map(|node| node.blockchain.last().map(|block| block.hash.as_str()))
+            .collect();
+
+        ByzantineReport {
+            nodes: self.byzantine.iter().map(|(&id, node)| (id, node.clone())).collect(),
+            honest_nodes: honest.len(),
+            honest_height: honest.iter().map(|node| node.blockchain.len()).max().unwrap_or(0),
+            honest_tips: tips.len(),
+        }
+    }
+
+    // Node'un davranış profilinde verilen davranış var mı
+    pub(crate) fn misbehaves(&self, node_id: usize, matches: impl Fn(&Misbehavior) -> bool) -> bool {
+        self.byzantine
+            .get(&node_id)
+            .map(|node| node.behaviors.iter().any(matches))
+            .unwrap_or(false)
+    }
+
+    // Node'un gönderdiği mesajlara eklediği gecikme
+    pub(crate) fn message_delay(&self, node_id: usize) -> Option<SimTime> {
+        self.byzantine.get(&node_id)?.behaviors.iter().find_map(|behavior| match behavior {
+            Misbehavior::DelayMessages { delay } => Some(*delay),
+            _ => None,
+        })
+    }
+
+    // Kötü niyetli node'un etki kaydını güncelle (dürüst node'lar için bir şey yapmaz)
+    pub(crate) fn record_byzantine(&mut self, node_id: usize, update: impl FnOnce(&mut ByzantineEffects)) {
+        if let Some(node) = self.byzantine.get_mut(&node_id) {
+            update(&mut node.effects);
+        }
+    }
+
+    // Kötü niyetli node'un yayacağı mesajı davranışlarına göre değiştir
+    // None dönerse mesaj hiç iletilmez
+    pub(crate) fn byzantine_outgoing(&mut self, origin: usize, message: Message) -> Option<Message> {
+        if !self.byzantine.contains_key(&origin) {
+            return Some(message);
+        }
+
+        match message {
+            Message::Transaction(_) if self.misbehaves(origin, |b| *b == Misbehavior::DropTransactions) => {
+                self.record_byzantine(origin, |effects| effects.dropped_transactions += 1);
+                None
+            }
+            Message::Block(_) if self.misbehaves(origin, |b| *b == Misbehavior::WithholdBlocks) => {
+                self.record_byzantine(origin, |effects| effects.withheld_blocks += 1);
+                None
+            }
+            Message::Block(block) if self.misbehaves(origin, |b| *b == Misbehavior::RelayInvalidBlocks) => {
+                let reward = self.nodes[origin].mining_reward;
+                Some(Message::Block(corrupt_block(&block, reward)))
+            }
+            message => Some(message),
+        }
+    }
+
+    // Yalancı node, yaydığı her bloğun ardından var olmayan bir bloğu duyurur
+    pub(crate) fn announce_fake_block(&mut self, origin: usize, peer: usize) {
+        if !self.misbehaves(origin, |b| *b == Misbehavior::LieAboutTips) {
+            return;
+        }
+        let fake_hash = random_hash(&mut self.nodes[origin].rng);
+        self.nodes[origin].mark_known(peer, &fake_hash);
+        self.record_byzantine(origin, |effects| effects.false_announcements += 1);
+        self.send_message(origin, peer, Message::Inv(vec![InvItem::Block(fake_hash)]));
+    }
+
+    // Yalancı node başlık cevabına kendi zincir ucunun üzerine sahte bir başlık ekler
+    pub(crate) fn append_fake_headers(&mut self, node_id: usize, headers: &mut Vec<BlockHeader>) {
+        if !self.misbehaves(node_id, |b| *b == Misbehavior::LieAboutTips) {
+            return;
+        }
+        let block_time = self.block_time;
+        let node = &mut self.nodes[node_id];
+        let tip = match node.blockchain.last() {
+            Some(tip) => tip.header(),
+            None => return,
+        };
+
+        let mut fake = BlockHeader {
+            index: tip.index + 1,
+            timestamp: tip.timestamp + block_time,
+            previous_hash: tip.hash.clone(),
+            hash: String::new(),
+            nonce: node.rng.random(),
+            merkle_root: random_hash(&mut node.rng),
+            proposer: tip.proposer.clone(),
+            signature: tip.signature.clone(),
+        };
+        fake.hash = fake.calculate_hash();
+        // Cevap öncekilerin devamı olmalı; uçtan önceki başlıklar eksikse sadece uç ve sahte başlık gönderilir
+        if headers.last().map(|last| last.hash != tip.hash).unwrap_or(true) {
+            headers.clear();
+            headers.push(tip);
+        }
+        headers.push(fake);
+        self.record_byzantine(node_id, |effects| effects.fake_headers_sent += 1);
+    }
+
+    // Spam gönderen node'un her komşusuna bir grup sahte işlem gönder ve bir sonrakini planla
+    pub(crate) fn handle_spam(&mut self, node_id: usize) {
+        // Çevrimdışı node gönderim yapmaz; yeniden başlatılınca gönderim tekrar planlanır
+        if !self.nodes[node_id].online {
+            return;
+        }
+        let per_second = match self.byzantine.get(&node_id).and_then(|node| {
+            node.behaviors.iter().find_map(|behavior| match behavior {
+                Misbehavior::SpamMempool { per_second } => Some(*per_second),
+                _ => None,
+            })
+        }) {
+            Some(per_second) => per_second,
+            None => return,
+        };
+
+        let timestamp = self.timestamp();
+        for peer in self.nodes[node_id].connections.clone() {
+            for _ in 0..per_second {
+                let tx = spam_transaction(&mut self.nodes[node_id], timestamp);
+                self.send_message(node_id, peer, Message::Transaction(tx));
+            }
+            self.record_byzantine(node_id, |effects| effects.spam_sent += per_second);
+        }
+        self.scheduler.schedule_in(SPAM_INTERVAL, SimEvent::Spam { node: node_id });
+    }
+}
+
+// Bloğun coinbase ödülünü şişirilmiş bir kopyası; başlık değişmediği için merkle kökü tutmaz
+fn corrupt_block(block: &Block, reward: u64) -> Block {
+    let mut corrupted = block.clone();
+    match corrupted.transactions.first_mut() {
+        Some(coinbase) if !coinbase.outputs.is_empty() => {
+            coinbase.outputs[0].amount += reward;
+            coinbase.id = coinbase.calculate_hash();
+        }
+        // İşlemsiz bloklarda nonce değiştirilir; hash artık içerikle eşleşmez
+        _ => corrupted.nonce = corrupted.nonce.wrapping_add(1),
+    }
+    corrupted
+}
+
+// Var olmayan bir UTXO'yu harcayan sahte işlem
+fn spam_transaction(node: &mut Node, timestamp: u64) -> Transaction {
+    let address = node.get_address().to_string();
+    let input = TxInput {
+        utxo_id: format!("{}:0", random_hash(&mut node.rng)),
+        utxo_output_index: 0,
+        signature: vec![0; 64],
+        sender_address: address.clone(),
+    };
+    let output = TxOutput {
+        amount: node.rng.random_range(1..100_000_000),
+        recipient_address: address,
+    };
+    Transaction::new_at(vec![input], vec![output], timestamp)
+}
+
+// Rastgele 32 byte'lık hash (hex)
+fn random_hash<R: Rng + ?Sized>(rng: &mut R) -> String {
+    let bytes: [u8; 32] = rng.random();
+    hex::encode(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::link::{LatencyModel, LinkConfig};
+    use crate::topology::Topology;
+
+    // Node 0'ın verilen davranışla çalıştığı, tam bağlı 5 node'lu PoW ağında blok üret
+    fn run_with(behavior: Misbehavior) -> ByzantineReport {
+        let mut network = BlockchainNetwork::with_seed(11);
+        network.set_difficulty(1);
+        network.set_block_time(2);
+        network.default_link = LinkConfig { latency: LatencyModel::Uniform { min: 50, max: 300 }, loss_rate: 0.0, bandwidth: 0 };
+        for _ in 0..5 {
+            network.add_node();
+        }
+        network.apply_topology(&Topology::FullMesh).unwrap();
+        network.set_misbehavior(0, vec![behavior]).unwrap();
+        network.select_random_validator().unwrap();
+        network.start_automatic_mining().unwrap();
+        network.run_blocks(15);
+        network.stop_automatic_mining().unwrap();
+        network.run_for(20_000);
+        let report = network.byzantine_report();
+        // Kötü niyetli node'un ürettiği bloklar dürüst zincire girmeyebilir
+        assert!(report.honest_height > 5);
+        report
+    }
+
+    fn effects(report: &ByzantineReport) -> &ByzantineEffects {
+        assert_eq!(report.nodes.len(), 1);
+        &report.nodes[0].1.effects
+    }
+
+    #[test]
+    fn invalid_blocks_are_rejected_by_honest_nodes() {
+        let report = run_with(Misbehavior::RelayInvalidBlocks);
+        assert_eq!(report.honest_tips, 1);
+        let effects = effects(&report);
+        assert!(effects.invalid_blocks_sent > 0);
+        assert!(effects.rejected_blocks > 0);
+    }
+
+    #[test]
+    fn lying_about_tips_does_not_split_honest_nodes() {
+        let report = run_with(Misbehavior::LieAboutTips);
+        assert_eq!(report.honest_tips, 1);
+        let effects = effects(&report);
+        assert!(effects.false_announcements > 0);
+        assert!(effects.unanswered_requests > 0);
+    }
+
+    #[test]
+    fn spam_is_rejected_by_honest_nodes() {
+        let report = run_with(Misbehavior::SpamMempool { per_second: 5 });
+        assert_eq!(report.honest_tips, 1);
+        let effects = effects(&report);
+        assert!(effects.spam_sent > 0);
+        assert!(effects.rejected_transactions > 0);
+    }
+
+    #[test]
+    fn withheld_blocks_do_not_split_honest_nodes() {
+        let report = run_with(Misbehavior::WithholdBlocks);
+        assert_eq!(report.honest_tips, 1);
+        assert!(effects(&report).withheld_blocks > 0);
+    }
+
+    #[test]
+    fn offline_spammer_stops_sending_until_restarted() {
+        let mut network = BlockchainNetwork::with_seed(3);
+        for _ in 0..3 {
+            network.add_node();
+        }
+        network.apply_topology(&Topology::FullMesh).unwrap();
+        network.set_misbehavior(0, vec![Misbehavior::SpamMempool { per_second: 2 }]).unwrap();
+        network.run_for(3_500);
+        let sent = network.byzantine_report().nodes[0].1.effects.spam_sent;
+        assert!(sent > 0);
+
+        network.crash_node(0).unwrap();
+        network.run_for(5_000);
+        assert_eq!(network.byzantine_report().nodes[0].1.effects.spam_sent, sent);
+        // Çevrimdışıyken bir sonraki gönderim planlanmaz
+        let mut pending = 0;
+        network.scheduler.retain(|event| {
+            if matches!(event, SimEvent::Spam { .. }) {
+                pending += 1;
+            }
+            true
+        });
+        assert_eq!(pending, 0);
+
+        network.restart_node(0).unwrap();
+        network.run_for(3_500);
+        assert!(network.byzantine_report().nodes[0].1.effects.spam_sent > sent);
+    }
+}
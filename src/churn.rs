@@ -0,0 +1,533 @@
+use rand::Rng;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::distribution;
+use crate::error::NetworkError;
+use crate::events::Event;
+use crate::mining::MiningModel;
+use crate::network::{BlockchainNetwork, SimEvent};
+use crate::sim::SimTime;
+use crate::stake::ConsensusMode;
+
+// Bir node'un çevrimiçi (oturum) veya çevrimdışı kalma süresinin dağılımı (ms)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SessionLength {
+    // Her seferinde aynı süre
+    Fixed(SimTime),
+    // Verilen ortalamaya sahip üstel dağılım (hafızasız)
+    Exponential { mean: SimTime },
+    // En az min kadar süren, ağır kuyruklu Pareto dağılımı
+    Pareto { min: SimTime, shape: f64 },
+    // Weibull dağılımı; şekil 1'den küçükse kısa oturumlar ağırlıktadır
+    Weibull { scale: SimTime, shape: f64 },
+}
+
+impl SessionLength {
+    // Bir süre örnekle (en az 1 ms)
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> SimTime {
+        let time = match *self {
+            SessionLength::Fixed(time) => time as f64,
+            SessionLength::Exponential { mean } => distribution::exponential(rng, mean as f64),
+            SessionLength::Pareto { min, shape } => distribution::pareto(rng, min as f64, shape),
+            SessionLength::Weibull { scale, shape } => distribution::weibull(rng, scale as f64, shape),
+        };
+        (time.round().min(SimTime::MAX as f64) as SimTime).max(1)
+    }
+
+    // Parametreler pozitif mi
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            SessionLength::Fixed(time) => time > 0,
+            SessionLength::Exponential { mean } => mean > 0,
+            SessionLength::Pareto { min, shape } => min > 0 && shape.is_finite() && shape > 0.0,
+            SessionLength::Weibull { scale, shape } => scale > 0 && shape.is_finite() && shape > 0.0,
+        }
+    }
+}
+
+impl FromStr for SessionLength {
+    type Err = String;
+
+    // "fixed:ms", "exp:ortalama_ms", "pareto:min_ms:şekil" veya "weibull:ölçek_ms:şekil"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(':').collect();
+        let time = |index: usize| -> Result<SimTime, String> {
+            let value = parts.get(index).ok_or_else(|| format!("eksik parametre: {}", s))?;
+            value.parse::<SimTime>().map_err(|_| format!("geçersiz süre: {}", value))
+        };
+        let shape = |index: usize| -> Result<f64, String> {
+            let value = parts.get(index).ok_or_else(|| format!("eksik şekil parametresi: {}", s))?;
+            value.parse::<f64>().map_err(|_| format!("geçersiz şekil parametresi: {}", value))
+        };
+
+        let session = match parts[0] {
+            "fixed" => SessionLength::Fixed(time(1)?),
+            "exp" => SessionLength::Exponential { mean: time(1)? },
+            "pareto" => SessionLength::Pareto { min: time(1)?, shape: shape(2)? },
+            "weibull" => SessionLength::Weibull { scale: time(1)?, shape: shape(2)? },
+            other => return Err(format!("bilinmeyen dağılım: {} (fixed:ms, exp:ms, pareto:ms:şekil, weibull:ms:şekil)", other)),
+        };
+        if !session.is_valid() {
+            return Err(format!("parametreler pozitif olmalı: {}", s));
+        }
+        Ok(session)
+    }
+}
+
+impl fmt::Display for SessionLength {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SessionLength::Fixed(time) => write!(f, "sabit {} ms", time),
+            SessionLength::Exponential { mean } => write!(f, "üstel, ortalama {} ms", mean),
+            SessionLength::Pareto { min, shape } => write!(f, "Pareto, en az {} ms, şekil {}", min, shape),
+            SessionLength::Weibull { scale, shape } => write!(f, "Weibull, ölçek {} ms, şekil {}", scale, shape),
+        }
+    }
+}
+
+// Bir node'un ağa girip çıkma düzeni
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChurnModel {
+    pub session: SessionLength,  // Çevrimiçi kalma süresi
+    pub downtime: SessionLength, // Çevrimdışı kalma süresi
+    pub crash_probability: f64,  // Oturumun düzgün ayrılma yerine çökmeyle bitme olasılığı
+}
+
+impl fmt::Display for ChurnModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "oturum {}; kapalı kalma {}; çökme olasılığı {:.0}%",
+            self.session,
+            self.downtime,
+            self.crash_probability * 100.0
+        )
+    }
+}
+
+// Bir node'un ağdaki durum değişikliği
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChurnEvent {
+    // Node düzgün şekilde ayrıldı; bellekteki durumu korunur
+    Left,
+    // Node çöktü; mempool, yetimler, istekler ve yan dallar kayboldu
+    Crashed,
+    // Node yeniden başlatıldı ve ağa tekrar katıldı
+    Restarted,
+}
+
+impl fmt::Display for ChurnEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ChurnEvent::Left => write!(f, "ayrıldı"),
+            ChurnEvent::Crashed => write!(f, "çöktü"),
+            ChurnEvent::Restarted => write!(f, "yeniden başladı"),
+        }
+    }
+}
+
+// Churn günlüğündeki bir kayıt
+#[derive(Debug, Clone)]
+pub struct ChurnRecord {
+    pub node_id: usize,
+    pub event: ChurnEvent,
+    pub time: SimTime,
+    pub blocks_behind: usize,         // Yeniden başladığında ağın en uzun zincirinin gerisinde kalan blok sayısı
+    pub synced_at: Option<SimTime>,   // Yeniden başladıktan sonra senkronizasyonun tamamlandığı an
+}
+
+impl fmt::Display for ChurnRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ms: Node {} {}", self.time, self.node_id, self.event)?;
+        if self.event == ChurnEvent::Restarted {
+            write!(f, " ({} blok geride", self.blocks_behind)?;
+            match self.synced_at {
+                Some(synced_at) => write!(f, ", {} ms'de yetişti)", synced_at - self.time)?,
+                None => write!(f, ", henüz yetişmedi)")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// Churn altında senkronizasyonun ve mempool yayılımının durumu
+#[derive(Debug, Clone)]
+pub struct ChurnReport {
+    pub online: usize,
+    pub offline: Vec<usize>,
+    pub departures: usize,                   // Düzgün ayrılmalar
+    pub crashes: usize,
+    pub restarts: usize,
+    pub resynced: usize,                     // Senkronizasyonu tamamlanan yeniden başlatmalar
+    pub average_resync_time: Option<f64>,    // Yeniden başlayan node'un zincire yetişme süresi ortalaması (ms)
+    pub average_blocks_behind: Option<f64>,  // Yeniden başlarken geride kalınan blok ortalaması
+    pub tips: usize,                         // Çevrimiçi node'ların farklı zincir ucu sayısı
+    pub pending_transactions: usize,         // Henüz zincire girmemiş işlemler
+    pub mempool_coverage: Option<f64>,       // Çevrimiçi node'ların mempool'unda bulunan bekleyen işlem oranı ortalaması
+}
+
+impl fmt::Display for ChurnReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Churn raporu:")?;
+        writeln!(f, "  Çevrimiçi: {}, çevrimdışı: {:?}", self.online, self.offline)?;
+        writeln!(f, "  Ayrılma: {}, çökme: {}, yeniden başlama: {} ({} tanesi zincire yetişti)", self.departures, self.crashes, self.restarts, self.resynced)?;
+        match self.average_resync_time {
+            Some(time) => writeln!(f, "  Ortalama yetişme süresi: {:.0} ms", time)?,
+            None => writeln!(f, "  Ortalama yetişme süresi: -")?,
+        }
+        match self.average_blocks_behind {
+            Some(blocks) => writeln!(f, "  Yeniden başlarken geride kalınan blok ortalaması: {:.1}", blocks)?,
+            None => writeln!(f, "  Yeniden başlarken geride kalınan blok ortalaması: -")?,
+        }
+        writeln!(f, "  Çevrimiçi node'lardaki zincir ucu sayısı: {}", self.tips)?;
+        write!(f, "  Bekleyen işlem: {}", self.pending_transactions)?;
+        if let Some(coverage) = self.mempool_coverage {
+            write!(f, ", mempool kapsaması %{:.1}", coverage * 100.0)?;
+        }
+        Ok(())
+    }
+}
+
+impl BlockchainNetwork {
+    // Node çevrimiçi mi
+    pub fn is_online(&self, node_id: usize) -> bool {
+        self.nodes.get(node_id).map(|node| node.online).unwrap_or(false)
+    }
+
+    // Node ağdan düzgün şekilde ayrılır: bağlantıları ve bellekteki durumu korunur, yolda olan mesajlar kaybolur
+    pub fn leave_node(&mut self, node_id: usize) -> Result<(), NetworkError> {
+        self.go_offline(node_id, ChurnEvent::Left)?;
+        self.process_due_events();
+        Ok(())
+    }
+
+    // Node çöker: diskte tutulan zincir, UTXO seti, cüzdan ve kesinlik durumu kalır;
+    // mempool, yetim bloklar, komşu envanterleri, bekleyen istekler, yan dallar ve ceza puanları kaybolur
+    pub fn crash_node(&mut self, node_id: usize) -> Result<(), NetworkError> {
+        self.crash(node_id)?;
+        self.process_due_events();
+        Ok(())
+    }
+
+    // Çevrimdışı node'u yeniden başlat: komşularına tekrar bağlanır ve kaçırdığı blokları senkronize eder
+    // Çevrimiçi komşusu kalmadıysa rastgele bir çevrimiçi node'a bağlanır (bootstrap).
+    pub fn restart_node(&mut self, node_id: usize) -> Result<(), NetworkError> {
+        self.restart(node_id)?;
+        self.process_due_events();
+        Ok(())
+    }
+
+    // Node'lara oturum modeli ata; her node'un ilk oturumunun bitişi modele göre planlanır
+    pub fn set_churn(&mut self, node_ids: &[usize], model: ChurnModel) -> Result<(), NetworkError> {
+        if !model.session.is_valid() || !model.downtime.is_valid() {
+            return Err(NetworkError::InvalidChurnModel(model.to_string()));
+        }
+        if !(0.0..=1.0).contains(&model.crash_probability) {
+            return Err(NetworkError::InvalidShare { name: "çökme olasılığı", value: model.crash_probability.to_string() });
+        }
+        if let Some(&missing) = node_ids.iter().find(|&&id| id >= self.nodes.len()) {
+            return Err(NetworkError::NodeNotFound(missing));
+        }
+
+        for &node_id in node_ids {
+            self.cancel_churn_events(node_id);
+            self.churn.insert(node_id, model);
+            let event = if self.nodes[node_id].online {
+                SimEvent::SessionEnd { node: node_id }
+            } else {
+                SimEvent::SessionStart { node: node_id }
+            };
+            let delay = if self.nodes[node_id].online { model.session } else { model.downtime }.sample(&mut self.rng);
+            self.scheduler.schedule_in(delay, event);
+        }
+        Ok(())
+    }
+
+    // Tüm oturum modellerini kaldır; çevrimdışı kalan node'lar yeniden başlatılır
+    pub fn stop_churn(&mut self) {
+        let node_ids: Vec<usize> = self.churn.keys().copied().collect();
+        for node_id in node_ids {
+            self.cancel_churn_events(node_id);
+            self.churn.remove(&node_id);
+            if !self.nodes[node_id].online {
+                let _ = self.restart_node(node_id);
+            }
+        }
+    }
+
+    // Churn günlüğünden ve node'ların şu anki durumundan rapor üret
+    pub fn churn_report(&self) -> ChurnReport {
+        let count = |event: ChurnEvent| self.churn_log.iter().filter(|record| record.event == event).count();
+        let restarts: Vec<&ChurnRecord> = self.churn_log.iter().filter(|record| record.event == ChurnEvent::Restarted).collect();
+        let resync_times: Vec<SimTime> = restarts.iter().filter_map(|record| record.synced_at.map(|at| at - record.time)).collect();
+        let average = |values: &[f64]| if values.is_empty() { None } else { Some(values.iter().sum::<f64>() / values.len() as f64) };
+
+        let online: Vec<_> = self.nodes.iter().filter(|node| node.online).collect();
+        let tips: HashSet<&str> = online.iter().filter_map(|node| node.blockchain.last().map(|block| block.hash.as_str())).collect();
+
+        // Ağ mempool'unda olup en uzun zincire girmemiş işlemler
+        let confirmed: HashSet<&str> = self
+            .canonical_chain()
+            .iter()
+            .flat_map(|block| block.transactions.iter().map(|tx| tx.id.as_str()))
+            .collect();
+        let pending: Vec<&str> = self.mempool.iter().map(|tx| tx.id.as_str()).filter(|id| !confirmed.contains(id)).collect();
+        let coverage: Vec<f64> = online
+            .iter()
+            .map(|node| {
+                let known = pending.iter().filter(|id| node.mempool.iter().any(|tx| tx.id == **id)).count();
+                known as f64 / pending.len() as f64
+            })
+            .collect();
+
+        ChurnReport {
+            online: online.len(),
+            offline: self.nodes.iter().filter(|node| !node.online).map(|node| node.id).collect(),
+            departures: count(ChurnEvent::Left),
+            crashes: count(ChurnEvent::Crashed),
+            restarts: restarts.len(),
+            resynced: resync_times.len(),
+            average_resync_time: average(&resync_times.iter().map(|&time| time as f64).collect::<Vec<_>>()),
+            average_blocks_behind: average(&restarts.iter().map(|record| record.blocks_behind as f64).collect::<Vec<_>>()),
+            tips: tips.len(),
+            pending_transactions: pending.len(),
+            mempool_coverage: if pending.is_empty() { None } else { average(&coverage) },
+        }
+    }
+
+    // Oturum modeline göre oturum bitti: node çöker veya düzgün ayrılır, dönüşü planlanır
+    pub(crate) fn handle_session_end(&mut self, node_id: usize) {
+        let model = match self.churn.get(&node_id) {
+            Some(&model) => model,
+            None => return,
+        };
+        if self.nodes[node_id].online {
+            let crashed = self.rng.random::<f64>() < model.crash_probability;
+            let _ = if crashed { self.crash(node_id) } else { self.go_offline(node_id, ChurnEvent::Left) };
+        }
+        let delay = model.downtime.sample(&mut self.rng);
+        self.scheduler.schedule_in(delay, SimEvent::SessionStart { node: node_id });
+    }
+
+    // Oturum modeline göre kapalı kalma süresi bitti: node yeniden başlar, bir sonraki ayrılışı planlanır
+    pub(crate) fn handle_session_start(&mut self, node_id: usize) {
+        let model = match self.churn.get(&node_id) {
+            Some(&model) => model,
+            None => return,
+        };
+        if !self.nodes[node_id].online {
+            let _ = self.restart(node_id);
+        }
+        let delay = model.session.sample(&mut self.rng);
+        self.scheduler.schedule_in(delay, SimEvent::SessionEnd { node: node_id });
+    }
+
+    // Yeniden başlayan node'un senkronizasyonu tamamlandı
+    pub(crate) fn record_resync(&mut self, node_id: usize, now: SimTime) {
+        let record = self
+            .churn_log
+            .iter_mut()
+            .rev()
+            .find(|record| record.node_id == node_id && record.event == ChurnEvent::Restarted);
+        if let Some(record) = record.filter(|record| record.synced_at.is_none()) {
+            record.synced_at = Some(now);
+        }
+    }
+
+    fn crash(&mut self, node_id: usize) -> Result<(), NetworkError> {
+        self.go_offline(node_id, ChurnEvent::Crashed)?;
+
+        let node = &mut self.nodes[node_id];
+        node.mempool.clear();
+        node.orphan_blocks.clear();
+        node.known_inventory.clear();
+        node.sync = None;
+        node.evidence_pool.clear();
+        node.ban_scores.clear();
+        node.block_store = node.blockchain.iter().map(|block| (block.hash.clone(), block.clone())).collect();
+
+        Ok(())
+    }
+
+    fn restart(&mut self, node_id: usize) -> Result<(), NetworkError> {
+        let node = self.nodes.get(node_id).ok_or(NetworkError::NodeNotFound(node_id))?;
+        if node.online {
+            return Err(NetworkError::NodeOnline(node_id));
+        }
+        let has_online_peer = node.connections.iter().any(|&peer| self.nodes[peer].online);
+
+        self.nodes[node_id].online = true;
+        if !has_online_peer {
+            let candidates: Vec<usize> = self
+                .nodes
+                .iter()
+                .filter(|node| node.online && node.id != node_id && !self.is_banned(node_id, node.id))
+                .map(|node| node.id)
+                .collect();
+            if !candidates.is_empty() {
+                let peer = candidates[self.rng.random_range(0..candidates.len())];
+                self.connect_nodes(node_id, peer)?;
+            }
+        }
+
+        let best = self.nodes.iter().filter(|node| node.online).map(|node| node.blockchain.len()).max().unwrap_or(0);
+        self.churn_log.push(ChurnRecord {
+            node_id,
+            event: ChurnEvent::Restarted,
+            time: self.now(),
+            blocks_behind: best.saturating_sub(self.nodes[node_id].blockchain.len()),
+            synced_at: None,
+        });
+        self.emit(Event::Churn { node: node_id, transition: ChurnEvent::Restarted });
+
+        self.start_sync(node_id)?;
+        self.reschedule_producers(node_id);
+        self.schedule_spam(node_id);
+        Ok(())
+    }
+
+    // Node'u çevrimdışı yap: yolda olan mesajları ve cevapsız istekleri düşür, blok üretimini yeniden planla
+    fn go_offline(&mut self, node_id: usize, event: ChurnEvent) -> Result<(), NetworkError> {
+        let node = self.nodes.get_mut(node_id).ok_or(NetworkError::NodeNotFound(node_id))?;
+        if !node.online {
+            return Err(NetworkError::NodeOffline(node_id));
+        }
+        node.online = false;
+        node.requested.clear();
+
+        self.scheduler.retain(|scheduled| match scheduled {
+            SimEvent::Deliver { from, to, .. } => *from != node_id && *to != node_id,
+            _ => true,
+        });
+        self.churn_log.push(ChurnRecord { node_id, event, time: self.now(), blocks_behind: 0, synced_at: None });
+        self.emit(Event::Churn { node: node_id, transition: event });
+
+        self.reschedule_producers(node_id);
+        Ok(())
+    }
+
+    // Çevrimiçi node'lar değişti: hash yarışı yeni güçlerle yeniden örneklenir,
+    // rastgele validator modelinde çevrimdışı validator'un yerine yenisi seçilir
+    fn reschedule_producers(&mut self, node_id: usize) {
+        if self.consensus != ConsensusMode::ProofOfWork {
+            return;
+        }
+        match self.mining_model {
+            MiningModel::HashRace if self.nodes[node_id].hashrate > 0.0 => self.restart_race(),
+            MiningModel::RandomValidator => {
+                let validator_online = self.current_validator_id.map(|id| self.nodes[id].online).unwrap_or(false);
+                if self.current_validator_id.is_some() && !validator_online {
+                    let _ = self.select_random_validator();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Node için planlanmış oturum olaylarını iptal et
+    fn cancel_churn_events(&mut self, node_id: usize) {
+        self.scheduler.retain(|event| match event {
+            SimEvent::SessionEnd { node } | SimEvent::SessionStart { node } => *node != node_id,
+            _ => true,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::link::{LatencyModel, LinkConfig};
+    use crate::topology::Topology;
+
+    // Verilen sayıda blok üretmiş, tam bağlı 4 node'lu PoW ağı (madencilik sürüyor)
+    fn network(blocks: usize) -> BlockchainNetwork {
+        let mut network = BlockchainNetwork::with_seed(5);
+        network.set_difficulty(1);
+        network.set_block_time(2);
+        network.default_link = LinkConfig { latency: LatencyModel::Uniform { min: 50, max: 300 }, loss_rate: 0.0, bandwidth: 0 };
+        for _ in 0..4 {
+            network.add_node();
+        }
+        network.apply_topology(&Topology::FullMesh).unwrap();
+        network.select_random_validator().unwrap();
+        network.start_automatic_mining().unwrap();
+        network.run_blocks(blocks);
+        network
+    }
+
+    fn model(session: SessionLength, crash_probability: f64) -> ChurnModel {
+        ChurnModel { session, downtime: SessionLength::Fixed(1000), crash_probability }
+    }
+
+    #[test]
+    fn crash_keeps_the_chain_and_loses_volatile_state() {
+        let mut network = network(5);
+        let node = &mut network.nodes[3];
+        let orphan = node.blockchain.last().unwrap().clone();
+        node.orphan_blocks.push(orphan);
+        node.ban_scores.insert(1, 10);
+        let tx = network.create_transaction(3, "alıcı", 1).unwrap();
+        network.nodes[3].mempool.push(tx);
+        let chain: Vec<String> = network.nodes[3].blockchain.iter().map(|block| block.hash.clone()).collect();
+
+        network.crash_node(3).unwrap();
+        let node = &network.nodes[3];
+        assert!(!node.online);
+        assert!(node.mempool.is_empty());
+        assert!(node.orphan_blocks.is_empty());
+        assert!(node.ban_scores.is_empty());
+        assert_eq!(node.blockchain.iter().map(|block| block.hash.clone()).collect::<Vec<_>>(), chain);
+        assert_eq!(network.churn_report().crashes, 1);
+    }
+
+    #[test]
+    fn restarted_node_records_when_it_catches_up() {
+        let mut network = network(3);
+        network.crash_node(3).unwrap();
+        network.run_blocks(5);
+        network.restart_node(3).unwrap();
+
+        let record = network.churn_log.last().unwrap().clone();
+        assert_eq!(record.event, ChurnEvent::Restarted);
+        assert!(record.blocks_behind > 0);
+        assert_eq!(record.synced_at, None);
+
+        network.stop_automatic_mining().unwrap();
+        network.run_for(20_000);
+        let record = network.churn_log.iter().rev().find(|record| record.event == ChurnEvent::Restarted).unwrap();
+        assert!(record.synced_at.unwrap() > record.time);
+        assert_eq!(network.churn_report().resynced, 1);
+        let tip = |node_id: usize| network.nodes[node_id].blockchain.last().unwrap().hash.clone();
+        assert_eq!(tip(3), tip(0));
+    }
+
+    #[test]
+    fn online_node_cannot_restart_and_offline_node_cannot_leave() {
+        let mut network = network(1);
+        assert_eq!(network.restart_node(0), Err(NetworkError::NodeOnline(0)));
+        network.leave_node(0).unwrap();
+        assert_eq!(network.crash_node(0), Err(NetworkError::NodeOffline(0)));
+        assert_eq!(network.restart_node(9), Err(NetworkError::NodeNotFound(9)));
+    }
+
+    #[test]
+    fn invalid_churn_models_are_rejected() {
+        let mut network = network(1);
+        let zero = model(SessionLength::Fixed(0), 0.5);
+        assert_eq!(network.set_churn(&[0], zero), Err(NetworkError::InvalidChurnModel(zero.to_string())));
+        let flat = model(SessionLength::Pareto { min: 1000, shape: 0.0 }, 0.5);
+        assert_eq!(network.set_churn(&[0], flat), Err(NetworkError::InvalidChurnModel(flat.to_string())));
+        assert!(matches!(
+            network.set_churn(&[0], model(SessionLength::Fixed(1000), 1.5)),
+            Err(NetworkError::InvalidShare { .. })
+        ));
+        assert_eq!(network.set_churn(&[7], model(SessionLength::Fixed(1000), 0.5)), Err(NetworkError::NodeNotFound(7)));
+        assert!(network.churn.is_empty());
+
+        assert!("exp:0".parse::<SessionLength>().is_err());
+        assert!("weibull:1000".parse::<SessionLength>().is_err());
+        assert!("normal:1000".parse::<SessionLength>().is_err());
+    }
+}
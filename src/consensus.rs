@@ -0,0 +1,229 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::block::{Block, BlockHeader};
+use crate::error::{NetworkError, ValidationError};
+use crate::mining::MiningModel;
+use crate::network::BlockchainNetwork;
+use crate::stake::{self, ConsensusMode};
+use crate::transaction::UTXO;
+use crate::wallet::Wallet;
+
+// Takılabilir uzlaşma kuralları: kimin blok üreteceği (lider seçimi), bloğun nasıl mühürlendiği,
+// mührün nasıl doğrulandığı ve çatallar arasında hangi zincirin seçildiği.
+// Ağ seviyesindeki metotlar blok üretim olaylarını yönetir; node seviyesindeki metotlar her node'un
+// kendi doğrulamasında kullanılır. Yeni bir kural denemek için bu trait'i uygulayıp
+// BlockchainNetwork::set_consensus_engine ile ağa takmak yeterlidir.
+pub trait ConsensusEngine: fmt::Debug + Send + Sync {
+    // Motorun kısa adı (durum çıktıları için)
+    fn name(&self) -> &str;
+
+    // Otomatik blok üretimi başlamadan önce gerekli koşulları kontrol et
+    fn check_ready(&self, _network: &BlockchainNetwork) -> Result<(), NetworkError> {
+        Ok(())
+    }
+
+    // Bir sonraki blok üretim olayını planla (varsayılan: her block_time saniyede bir)
+    fn schedule_next(&self, network: &mut BlockchainNetwork) {
+        let delay = network.block_time * 1000;
+        network.schedule_block_event(delay, None);
+    }
+
+    // Blok üretim anında bloğu üretecek node'ları seç
+    // found_by: olay planlanırken belirlenmiş üretici (ör. hash yarışının kazananı)
+    fn select_leaders(&self, network: &mut BlockchainNetwork, found_by: Option<usize>) -> Result<Vec<usize>, NetworkError>;
+
+    // Bir blok üretilip yayıldıktan sonra çağrılır (ör. bir sonraki validator'un seçimi)
+    fn after_block(&self, _network: &mut BlockchainNetwork, _producer: usize) -> Result<(), NetworkError> {
+        Ok(())
+    }
+
+    // Hiçbir node'un zinciri yokken motora özgü genesis bloğunu oluştur
+    // None dönerse genesis de seçilen lider tarafından sıradan bir blok gibi üretilir
+    fn create_genesis(&self, _network: &mut BlockchainNetwork) -> Option<Result<Block, NetworkError>> {
+        None
+    }
+
+    // Üretilen bloğu mühürle (hash mühürle birlikte güncellenmeli)
+    fn seal(&self, block: &mut Block, wallet: &Wallet, difficulty: usize);
+
+    // Başlığın mührünü zincir durumundan bağımsız olarak doğrula
+    fn verify_seal(&self, header: &BlockHeader, difficulty: usize) -> Result<(), ValidationError>;
+
+    // Bloğun üreticisinin, ebeveyninden sonraki UTXO durumuna göre blok üretmeye yetkili olduğunu doğrula
+    fn verify_leader(&self, _header: &BlockHeader, _parent: &Block, _utxo_set: &[UTXO]) -> Result<(), ValidationError> {
+        Ok(())
+    }
+
+    // Blok zaman damgasının alıcının saatinin en fazla ne kadar ilerisinde olabileceği (saniye, None: sınır yok)
+    fn max_clock_drift(&self) -> Option<u64> {
+        None
+    }
+
+    // Bloklar üreticinin imzasını taşıyor mu; çifte imza koruması, tespiti ve cezası buna bağlıdır
+    fn signed_blocks(&self) -> bool {
+        false
+    }
+
+    // Bir bloğun çatal seçimindeki ağırlığı (iş)
+    // Zorluk ağ genelinde sabit olduğundan varsayılan olarak her blok eşit iş taşır
+    fn block_work(&self, _hash: &str) -> u128 {
+        1
+    }
+
+    // Çatal seçimi: aday dal aktif zincirin yerini almalı mı
+    // Toplam işi daha fazla olan dal kazanır (varsayılan ağırlıklarla en uzun zincir kuralı)
+    fn prefer(&self, current: &[Block], candidate: &[Block]) -> bool {
+        let work = |chain: &[Block]| chain.iter().map(|block| self.block_work(&block.hash)).sum::<u128>();
+        work(candidate) > work(current)
+    }
+}
+
+// Proof of Work: bloklar nonce aranarak mühürlenir
+// Üretici ağın madencilik modeline göre seçilir: rastgele validator veya hash gücüne göre yarış
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProofOfWorkEngine;
+
+impl ConsensusEngine for ProofOfWorkEngine {
+    fn name(&self) -> &str {
+        "pow"
+    }
+
+    fn check_ready(&self, network: &BlockchainNetwork) -> Result<(), NetworkError> {
+        match network.mining_model {
+            // Önce bir validator seçilmiş olmalı
+            MiningModel::RandomValidator if network.current_validator_id.is_none() => Err(NetworkError::NoValidatorSelected),
+            // Yarışa katılacak hash gücü olmalı
+            MiningModel::HashRace if network.total_hashrate() <= 0.0 => Err(NetworkError::NoHashPower),
+            _ => Ok(()),
+        }
+    }
+
+    fn schedule_next(&self, network: &mut BlockchainNetwork) {
+        match network.mining_model {
+            MiningModel::RandomValidator => {
+                let delay = network.block_time * 1000;
+                network.schedule_block_event(delay, None);
+            }
+            // Yarışın kazananı ve bloğu bulma anı örneklenir
+            MiningModel::HashRace => {
+                if let Some((miner, delay)) = network.sample_race() {
+                    network.schedule_block_event(delay, Some(miner));
+                }
+            }
+        }
+    }
+
+    fn select_leaders(&self, network: &mut BlockchainNetwork, found_by: Option<usize>) -> Result<Vec<usize>, NetworkError> {
+        match network.mining_model {
+            MiningModel::RandomValidator => network.current_validator_id.map(|id| vec![id]).ok_or(NetworkError::NoValidatorSelected),
+            // Planlanmış bir yarış yoksa (elle madencilik) yarış hemen örneklenir
+            MiningModel::HashRace => match found_by {
+                Some(miner) => Ok(vec![miner]),
+                None => network.sample_race().map(|(miner, _)| vec![miner]).ok_or(NetworkError::NoHashPower),
+            },
+        }
+    }
+
+    // Rastgele validator modelinde her bloktan sonra yeni validator seçilir
+    // (hash yarışında bir sonraki üretici yarışla belirlenir)
+    fn after_block(&self, network: &mut BlockchainNetwork, _producer: usize) -> Result<(), NetworkError> {
+        if network.mining_model == MiningModel::RandomValidator {
+            network.select_random_validator()?;
+        }
+        Ok(())
+    }
+
+    fn seal(&self, block: &mut Block, _wallet: &Wallet, difficulty: usize) {
+        block.mine_block(difficulty);
+    }
+
+    fn verify_seal(&self, header: &BlockHeader, difficulty: usize) -> Result<(), ValidationError> {
+        let target = "0".repeat(difficulty);
+        if !header.hash.starts_with(&target) {
+            return Err(ValidationError::InsufficientProofOfWork { index: header.index, difficulty });
+        }
+        Ok(())
+    }
+}
+
+// Proof of Stake: her slotta stake ağırlıklı seçilen önerici bloğu kendi anahtarıyla imzalar
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProofOfStakeEngine;
+
+impl ConsensusEngine for ProofOfStakeEngine {
+    fn name(&self) -> &str {
+        "pos"
+    }
+
+    // Şu anki slotta kendi zincir ucuna göre önerici olan her node blok üretir
+    // Node'lar aynı uca sahipse tek bir önerici çıkar; farklı uçlarda olanlar farklı dallarda üretebilir
+    fn select_leaders(&self, network: &mut BlockchainNetwork, _found_by: Option<usize>) -> Result<Vec<usize>, NetworkError> {
+        let slot = network.timestamp();
+        let proposers: Vec<usize> = network
+            .nodes
+            .iter()
+            .filter(|node| node.online && node.sync.as_ref().map(|sync| sync.is_complete()).unwrap_or(true))
+            .filter(|node| node.slot_proposer(slot).as_deref() == Some(node.get_address()))
+            .map(|node| node.id)
+            .collect();
+
+        if proposers.is_empty() {
+            return Err(NetworkError::NoProposer);
+        }
+        Ok(proposers)
+    }
+
+    // Her node'a genesis_stake kadar kilitli stake veren genesis
+    fn create_genesis(&self, network: &mut BlockchainNetwork) -> Option<Result<Block, NetworkError>> {
+        Some(network.create_stake_genesis())
+    }
+
+    fn seal(&self, block: &mut Block, wallet: &Wallet, _difficulty: usize) {
+        block.sign(wallet);
+    }
+
+    // Önericinin doğru olup olmadığı zincir durumuna göre verify_leader ile ayrıca kontrol edilir
+    fn verify_seal(&self, header: &BlockHeader, _difficulty: usize) -> Result<(), ValidationError> {
+        stake::verify_seal(header)
+    }
+
+    fn verify_leader(&self, header: &BlockHeader, parent: &Block, utxo_set: &[UTXO]) -> Result<(), ValidationError> {
+        stake::validate_proposer(header, parent.timestamp, utxo_set)
+    }
+
+    // Zaman damgası slotu belirlediğinden önerici gelecekteki slotları deneyemez
+    fn max_clock_drift(&self) -> Option<u64> {
+        Some(stake::MAX_CLOCK_DRIFT)
+    }
+
+    fn signed_blocks(&self) -> bool {
+        true
+    }
+}
+
+impl ConsensusMode {
+    // Bu uzlaşma modunun yerleşik motoru
+    pub fn engine(self) -> Arc<dyn ConsensusEngine> {
+        match self {
+            ConsensusMode::ProofOfWork => Arc::new(ProofOfWorkEngine),
+            ConsensusMode::ProofOfStake => Arc::new(ProofOfStakeEngine),
+        }
+    }
+}
+
+impl BlockchainNetwork {
+    // Özel bir uzlaşma motorunu ağa ve tüm node'lara tak
+    // consensus alanı değişmez; yerleşik motorlara set_consensus ile geri dönülebilir
+    pub fn set_consensus_engine(&mut self, engine: Arc<dyn ConsensusEngine>) {
+        for node in self.nodes.iter_mut() {
+            node.consensus = engine.clone();
+        }
+        self.engine = engine;
+    }
+
+    // Ağın kullandığı uzlaşma motoru
+    pub fn consensus_engine(&self) -> &Arc<dyn ConsensusEngine> {
+        &self.engine
+    }
+}
@@ -0,0 +1,42 @@
+use rand::Rng;
+
+// Simülasyonda kullanılan olasılık dağılımları
+// Tüm örnekleyiciler dışarıdan verilen üreteci kullanır, böylece seed'li çalıştırmalar tekrarlanabilir kalır.
+
+// (0, 1] aralığında düzgün dağılımlı sayı (logaritma alınırken 0'dan kaçınmak için)
+fn open_unit<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    1.0 - rng.random::<f64>()
+}
+
+// Verilen ortalamaya sahip üstel dağılım
+pub fn exponential<R: Rng + ?Sized>(rng: &mut R, mean: f64) -> f64 {
+    -mean * open_unit(rng).ln()
+}
+
+// Normal dağılım (Box-Muller dönüşümü)
+pub fn normal<R: Rng + ?Sized>(rng: &mut R, mean: f64, std_dev: f64) -> f64 {
+    let u1 = open_unit(rng);
+    let u2 = rng.random::<f64>();
+    let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+    mean + std_dev * z
+}
+
+// Ölçeği (en küçük değeri) ve şekil parametresi verilen Pareto dağılımı (ağır kuyruklu)
+// Şekil parametresi 1'den küçükse ortalama sonsuzdur; P2P ağlarındaki oturum süreleri genelde bu dağılıma uyar.
+pub fn pareto<R: Rng + ?Sized>(rng: &mut R, scale: f64, shape: f64) -> f64 {
+    scale / open_unit(rng).powf(1.0 / shape)
+}
+
+// Ölçeği ve şekil parametresi verilen Weibull dağılımı
+// Şekil 1 iken üstel dağılıma eşittir; 1'den küçük şekil çok sayıda kısa ve az sayıda uzun oturum üretir.
+pub fn weibull<R: Rng + ?Sized>(rng: &mut R, scale: f64, shape: f64) -> f64 {
+    scale * (-open_unit(rng).ln()).powf(1.0 / shape)
+}
+
+// [min, max] aralığında düzgün dağılım
+pub fn uniform<R: Rng + ?Sized>(rng: &mut R, min: f64, max: f64) -> f64 {
+    if max <= min {
+        return min;
+    }
+    rng.random_range(min..=max)
+}
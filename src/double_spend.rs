@@ -0,0 +1,274 @@
+use std::fmt;
+
+use crate::block::Block;
+#[cfg(feature = "attacks")]
+use crate::error::NetworkError;
+#[cfg(feature = "attacks")]
+use crate::message::Message;
+use crate::network::BlockchainNetwork;
+use crate::sim::SimTime;
+use crate::transaction::Transaction;
+#[cfg(feature = "attacks")]
+use crate::transaction::TxOutput;
+
+// Dürüst zincir saldırganın gizli dalından bu kadar blok öne geçerse saldırgan vazgeçer
+pub const DEFAULT_MAX_DEFICIT: usize = 12;
+
+// Çifte harcama saldırısının aşaması
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DoubleSpendStatus {
+    // Saldırgan gizli dalında madencilik yapıyor
+    Mining,
+    // Satıcı ödemeyi yeterince onayladıktan sonra daha uzun gizli dal yayınlandı
+    Released { at: SimTime, confirmations: usize },
+    // Dürüst zincir çok öne geçti, saldırgan dürüst zincire döndü
+    GaveUp { at: SimTime, deficit: usize },
+}
+
+// %51 çifte harcama saldırısı: saldırgan satıcıya öder, aynı coinleri kendine geri gönderen işlemi
+// gizli bir dalda madencilikle onaylar ve satıcı ödemeyi k kez onayladıktan sonra dal daha uzunsa yayınlar.
+// Saldırganın dürüst zinciri anında gözlediği, ama saldırı sürerken dürüst blokları kabul etmediği kabul edilir.
+#[derive(Debug, Clone)]
+pub struct DoubleSpendAttack {
+    pub attacker: usize,
+    pub merchant: usize,
+    pub payment: Transaction,     // Satıcıya yapılan, herkese yayılan ödeme
+    pub conflict: Transaction,    // Aynı girdileri saldırgana geri harcayan, sadece gizli dalda onaylanan işlem
+    pub confirmations: usize,     // Satıcının ödemeyi kabul etmek için beklediği onay sayısı (k)
+    pub fork_height: usize,       // Gizli dalın ayrıldığı zincir uzunluğu
+    pub max_deficit: usize,       // Vazgeçmeden önce dürüst zincirin öne geçebileceği blok sayısı
+    pub started_at: SimTime,
+    pub private_blocks: usize,    // Gizli dalın uzunluğu (saldırı bitince sabitlenir)
+    pub honest_blocks: usize,     // Dürüst zincirin ayrılma noktasından sonraki uzunluğu (saldırı bitince sabitlenir)
+    pub status: DoubleSpendStatus,
+}
+
+// Saldırının sonucu
+#[derive(Debug, Clone)]
+pub struct DoubleSpendReport {
+    pub attacker: usize,
+    pub merchant: usize,
+    pub amount: u64,
+    pub hash_share: f64,
+    pub confirmations: usize,     // Beklenen onay sayısı (k)
+    pub private_blocks: usize,    // Gizli dalın uzunluğu
+    pub honest_blocks: usize,     // Aynı sürede dürüst zincirin ayrılma noktasından sonraki uzunluğu
+    pub status: DoubleSpendStatus,
+    pub reversed: bool,           // Ödeme satıcının zincirinden çıktı ve yerine çelişen işlem girdi
+    pub success_probability: f64, // Analitik modelde bu hash gücü payı ve k için başarı olasılığı
+}
+
+impl fmt::Display for DoubleSpendStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DoubleSpendStatus::Mining => write!(f, "gizli dalda madencilik sürüyor"),
+            DoubleSpendStatus::Released { at, confirmations } => {
+                write!(f, "{} ms'de {} onaydan sonra gizli dal yayınlandı", at, confirmations)
+            }
+            DoubleSpendStatus::GaveUp { at, deficit } => {
+                write!(f, "{} ms'de dürüst zincir {} blok öndeyken vazgeçildi", at, deficit)
+            }
+        }
+    }
+}
+
+impl fmt::Display for DoubleSpendReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Çifte harcama: Node {} -> Node {}, {} coin, hash gücü payı %{:.1}, k = {}",
+            self.attacker,
+            self.merchant,
+            self.amount as f64 / 100_000_000.0,
+            self.hash_share * 100.0,
+            self.confirmations
+        )?;
+        writeln!(f, "  Durum: {}", self.status)?;
+        writeln!(f, "  Gizli dal: {} blok, dürüst zincir: {} blok", self.private_blocks, self.honest_blocks)?;
+        writeln!(f, "  Ödeme geri alındı: {}", if self.reversed { "evet" } else { "hayır" })?;
+        write!(f, "  Analitik modelde başarı olasılığı: %{:.2}", self.success_probability * 100.0)
+    }
+}
+
+// Hash gücü payı q olan saldırganın, satıcı k onay bekledikten sonra dürüst zincirin önüne geçme olasılığı
+// (Rosenfeld'in analizi, vazgeçmeyen saldırgan). Satıcı k. onayı gördüğünde saldırganın bulduğu blok sayısı
+// m negatif binom dağılımındadır; z blok geriden öne geçme olasılığı (q/p)^(z+1)'dir.
+pub fn success_probability(q: f64, k: usize) -> f64 {
+    let p = 1.0 - q;
+    if q >= p {
+        return 1.0;
+    }
+
+    let mut failure = 0.0;
+    let mut combinations = 1.0; // C(m + k - 1, m)
+    for m in 0..=k {
+        if m > 0 {
+            combinations *= (m + k - 1) as f64 / m as f64;
+        }
+        let probability = combinations * p.powi(k as i32) * q.powi(m as i32);
+        failure += probability * (1.0 - (q / p).powi((k - m + 1) as i32));
+    }
+    (1.0 - failure).clamp(0.0, 1.0)
+}
+
+impl BlockchainNetwork {
+    // Saldırı senaryosu: attacker satıcıya amount kadar öder ve aynı coinleri kendine geri harcayan
+    // işlemi gizli bir dalda onaylatmaya başlar. Saldırganın hash gücü toplamın hash_share kadarı olur.
+    // Dönüş: satıcıya yapılan ödeme
+    #[cfg(feature = "attacks")]
+    pub fn start_double_spend(
+        &mut self,
+        attacker: usize,
+        merchant: usize,
+        amount: u64,
+        confirmations: usize,
+        hash_share: f64,
+    ) -> Result<Transaction, NetworkError> {
+        if self.consensus != crate::stake::ConsensusMode::ProofOfWork || self.mining_model != crate::mining::MiningModel::HashRace {
+            return Err(NetworkError::HashRaceRequired);
+        }
+        if self.attack_in_progress() {
+            return Err(NetworkError::AttackInProgress);
+        }
+        if !(hash_share > 0.0 && hash_share < 1.0) {
+            return Err(NetworkError::InvalidShare { name: "hash gücü payı", value: hash_share.to_string() });
+        }
+        let merchant_address = self.nodes.get(merchant).ok_or(NetworkError::NodeNotFound(merchant))?.get_address().to_string();
+        let node = self.nodes.get(attacker).ok_or(NetworkError::NodeNotFound(attacker))?;
+        if attacker == merchant {
+            return Err(NetworkError::SelfTarget(attacker));
+        }
+
+        let honest = self.total_hashrate() - node.hashrate;
+        if honest <= 0.0 {
+            return Err(NetworkError::NoHashPower);
+        }
+        let fork_height = node.blockchain.len();
+
+        // Ödeme herkese yayılır
+        let payment = self.submit_transaction(attacker, &merchant_address, amount)?;
+        self.set_hashrate(attacker, honest * hash_share / (1.0 - hash_share))?;
+
+        // Aynı girdileri saldırgana geri harcayan işlem sadece saldırganın mempool'una girer
+        let node = &mut self.nodes[attacker];
+        let conflict = Transaction::new_at(
+            payment.inputs.clone(),
+            vec![TxOutput {
+                amount: payment.get_total_output_amount(),
+                recipient_address: node.get_address().to_string(),
+            }],
+            payment.timestamp,
+        );
+        node.mempool.retain(|tx| tx.id != payment.id);
+        node.receive_transaction(conflict.clone())?;
+
+        // Saldırgan ödemeyi artık mempool'unda tutmadığı için getdata isteklerine cevap veremez; ödeme komşulara doğrudan gönderilir
+        for peer in node.connections.clone() {
+            self.send_message(attacker, peer, Message::Transaction(payment.clone()));
+        }
+
+        self.double_spend = Some(DoubleSpendAttack {
+            attacker,
+            merchant,
+            payment: payment.clone(),
+            conflict,
+            confirmations: confirmations.max(1),
+            fork_height,
+            max_deficit: DEFAULT_MAX_DEFICIT,
+            started_at: self.now(),
+            private_blocks: 0,
+            honest_blocks: 0,
+            status: DoubleSpendStatus::Mining,
+        });
+        self.process_due_events();
+        Ok(payment)
+    }
+
+    // Çifte harcama saldırısının şu anki sonucu
+    pub fn double_spend_report(&self) -> Option<DoubleSpendReport> {
+        let attack = self.double_spend.as_ref()?;
+        let attacker = &self.nodes[attack.attacker];
+        let merchant = &self.nodes[attack.merchant];
+
+        let total = self.total_hashrate();
+        let hash_share = if total > 0.0 { attacker.hashrate / total } else { 0.0 };
+        let contains = |chain: &[Block], id: &str| chain.iter().any(|block| block.transactions.iter().any(|tx| tx.id == id));
+        let reversed = !contains(&merchant.blockchain, &attack.payment.id) && contains(&merchant.blockchain, &attack.conflict.id);
+
+        Some(DoubleSpendReport {
+            attacker: attack.attacker,
+            merchant: attack.merchant,
+            amount: attack.payment.outputs[0].amount,
+            hash_share,
+            confirmations: attack.confirmations,
+            private_blocks: attack.private_blocks,
+            honest_blocks: attack.honest_blocks,
+            status: attack.status,
+            reversed,
+            success_probability: success_probability(hash_share, attack.confirmations),
+        })
+    }
+
+    // Satıcının zincirinde ödemenin onay sayısı (ödemeyi içeren blok dahil)
+    pub fn payment_confirmations(&self) -> usize {
+        let attack = match self.double_spend.as_ref() {
+            Some(attack) => attack,
+            None => return 0,
+        };
+        let chain = &self.nodes[attack.merchant].blockchain;
+        chain
+            .iter()
+            .position(|block| block.transactions.iter().any(|tx| tx.id == attack.payment.id))
+            .map(|index| chain.len() - index)
+            .unwrap_or(0)
+    }
+
+    // Saldırgan gizli dalında madencilik yapıyor mu (bloklarını yaymaz, dürüst blokları kabul etmez)
+    pub(crate) fn is_double_spender(&self, node_id: usize) -> bool {
+        self.double_spend
+            .as_ref()
+            .map(|attack| attack.attacker == node_id && attack.status == DoubleSpendStatus::Mining)
+            .unwrap_or(false)
+    }
+
+    // Her yeni blokta saldırganın durumunu değerlendir:
+    // satıcı ödemeyi k kez onayladıysa ve gizli dal dürüst zincirden uzunsa dal yayınlanır,
+    // dürüst zincir çok öne geçtiyse saldırgan vazgeçip dürüst zincire döner
+    pub(crate) fn advance_double_spend(&mut self) {
+        let (attacker, required, max_deficit, fork_height) = match self.double_spend.as_ref() {
+            Some(attack) if attack.status == DoubleSpendStatus::Mining => {
+                (attack.attacker, attack.confirmations, attack.max_deficit, attack.fork_height)
+            }
+            _ => return,
+        };
+        let private_length = self.nodes[attacker].blockchain.len();
+        let honest_length = self.honest_chain(attacker).len();
+        if let Some(attack) = self.double_spend.as_mut() {
+            attack.private_blocks = private_length.saturating_sub(fork_height);
+            attack.honest_blocks = honest_length.saturating_sub(fork_height);
+        }
+        let honest_tip = self.nodes.iter().position(|node| node.id != attacker && node.blockchain.len() == honest_length);
+        let confirmations = self.payment_confirmations();
+        let now = self.now();
+
+        if confirmations >= required && private_length > honest_length {
+            if let Some(attack) = self.double_spend.as_mut() {
+                attack.status = DoubleSpendStatus::Released { at: now, confirmations };
+            }
+            for index in fork_height..private_length {
+                let block = self.nodes[attacker].blockchain[index].clone();
+                self.propagation.record_mined(&block, attacker, now);
+                self.broadcast_block(attacker, &block);
+            }
+        } else if honest_length >= private_length + max_deficit {
+            if let Some(attack) = self.double_spend.as_mut() {
+                attack.status = DoubleSpendStatus::GaveUp { at: now, deficit: honest_length - private_length };
+            }
+            if let Some(source) = honest_tip {
+                self.catch_up(attacker, source);
+            }
+            let conflict = self.double_spend.as_ref().map(|attack| attack.conflict.id.clone()).unwrap_or_default();
+            self.nodes[attacker].mempool.retain(|tx| tx.id != conflict);
+        }
+    }
+}
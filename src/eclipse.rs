@@ -0,0 +1,234 @@
+#[cfg(feature = "attacks")]
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::block::Block;
+#[cfg(feature = "attacks")]
+use crate::error::NetworkError;
+#[cfg(feature = "attacks")]
+use crate::message::Message;
+use crate::network::BlockchainNetwork;
+use crate::sim::SimTime;
+
+// Sybil node'ların hepsinin bulunduğu, saldırganın kontrol ettiği adres bloğu
+pub const SYBIL_NETGROUP: u16 = u16::MAX;
+
+// Kurbanın bağlantılarını yeniden kurarken uyguladığı isteğe bağlı savunmalar
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EclipseDefense {
+    pub diverse_outbound: bool, // Her adres bloğundan (netgroup) en fazla bir komşu seç
+    pub anchors: usize,         // Saldırıdan önceki komşulardan korunan (çapa) bağlantı sayısı
+}
+
+// Tutulma (eclipse) saldırısının durumu
+// Saldırgan kurbanın adres tablosunu Sybil adresleriyle doldurup bağlantılarını yeniden kurmasını sağlar.
+// Kurbanın komşu yuvalarını ele geçiren Sybil'ler yalnızca kurbana ve birbirlerine bağlıdır;
+// kurban dürüst blokları göremez ve Sybil'lerin kendi hash güçleriyle ürettiği sahte zinciri izler.
+#[derive(Debug, Clone)]
+pub struct EclipseAttack {
+    pub victim: usize,
+    pub sybils: Vec<usize>,         // Saldırganın eklediği Sybil node'lar
+    pub original_peers: Vec<usize>, // Kurbanın saldırıdan önceki komşuları
+    pub honest_peers: Vec<usize>,   // Savunmalar sayesinde kurbanda kalan dürüst komşular
+    pub defense: EclipseDefense,
+    pub started_at: SimTime,
+    pub ended_at: Option<SimTime>,
+}
+
+impl EclipseAttack {
+    // Sybil'ler hâlâ kurbanın yuvalarında mı
+    pub fn is_active(&self) -> bool {
+        self.ended_at.is_none()
+    }
+}
+
+// Kurbanın gördüğü zincirin dürüst zincirden ne kadar ayrıldığı
+#[derive(Debug, Clone)]
+pub struct EclipseReport {
+    pub victim: usize,
+    pub sybil_peers: usize,       // Kurbanın komşuları arasındaki Sybil sayısı
+    pub honest_peers: Vec<usize>, // Kurbanın dürüst komşuları
+    pub eclipsed: bool,           // Kurbanın hiç dürüst komşusu yok
+    pub victim_height: usize,
+    pub honest_height: usize,     // Dürüst node'ların en uzun zinciri
+    pub common_prefix: usize,     // İki zincirin ortak başlangıç uzunluğu
+    pub missed_blocks: usize,     // Kurbanın göremediği dürüst bloklar
+    pub fake_blocks: usize,       // Kurbanın zincirinde olup dürüst zincirde olmayan bloklar
+    pub duration: SimTime,        // Saldırının sürdüğü sanal zaman (ms)
+}
+
+impl fmt::Display for EclipseReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Tutulma saldırısı, kurban Node {} ({} ms):", self.victim, self.duration)?;
+        writeln!(f, "  Komşular: {} Sybil, dürüst {:?}", self.sybil_peers, self.honest_peers)?;
+        writeln!(f, "  Durum: {}", if self.eclipsed { "kurban ağdan tamamen yalıtıldı" } else { "kurbanın dürüst bağlantısı var" })?;
+        writeln!(f, "  Zincir uzunluğu: kurban {}, dürüst uç {} (ortak kısım {})", self.victim_height, self.honest_height, self.common_prefix)?;
+        write!(f, "  Görülmeyen dürüst bloklar: {}, sahte bloklar: {}", self.missed_blocks, self.fake_blocks)
+    }
+}
+
+impl BlockchainNetwork {
+    // Saldırı senaryosu: kurbanın komşu yuvalarını Sybil node'larla ele geçir
+    // sybil_count kadar Sybil eklenir ve kurban, adres tablosu Sybil'lerle dolmuş halde bağlantılarını yeniden kurar.
+    // Sybil'lerin toplam hash gücü, toplam hash gücünün hash_share kadarı olacak şekilde ayarlanır;
+    // 0 verilirse Sybil'ler blok üretmez ve kurbanın zinciri olduğu yerde kalır.
+    // Yeni Sybil node'ların ID'leri döner.
+    #[cfg(feature = "attacks")]
+    pub fn start_eclipse(&mut self, victim: usize, sybil_count: usize, hash_share: f64, defense: EclipseDefense) -> Result<Vec<usize>, NetworkError> {
+        if self.attack_in_progress() {
+            return Err(NetworkError::AttackInProgress);
+        }
+        if sybil_count == 0 {
+            return Err(NetworkError::NoSybils);
+        }
+        if !(0.0..1.0).contains(&hash_share) {
+            return Err(NetworkError::InvalidShare { name: "hash gücü payı", value: hash_share.to_string() });
+        }
+        if hash_share > 0.0 && (self.consensus != crate::stake::ConsensusMode::ProofOfWork || self.mining_model != crate::mining::MiningModel::HashRace) {
+            return Err(NetworkError::HashRaceRequired);
+        }
+        let node = self.nodes.get(victim).ok_or(NetworkError::NodeNotFound(victim))?;
+        let original_peers = node.connections.clone();
+
+        // Sybil'lerin hash gücü mevcut (dürüst) hash gücüne göre ayarlanır
+        let honest = self.total_hashrate();
+        if hash_share > 0.0 && honest <= 0.0 {
+            return Err(NetworkError::NoHashPower);
+        }
+        let sybil_hashrate = honest * hash_share / (1.0 - hash_share) / sybil_count as f64;
+
+        // Sybil'ler kurbanın zincirini bilir, aynı adres bloğundadır ve kendi aralarında tam bağlıdır
+        let mut sybils = Vec::with_capacity(sybil_count);
+        for _ in 0..sybil_count {
+            let id = self.add_node();
+            self.nodes[id].netgroup = SYBIL_NETGROUP;
+            self.nodes[id].hashrate = 0.0;
+            self.catch_up(id, victim);
+            for &other in &sybils {
+                self.connect_nodes(id, other)?;
+            }
+            sybils.push(id);
+        }
+
+        // Kurban aynı sayıda yuvayı yeniden doldurur: önce çapalar, sonra adres tablosunu dolduran Sybil'ler,
+        // yer kalırsa eski komşular. Çeşitlilik savunmasında her adres bloğundan tek komşu seçilir.
+        let slots = original_peers.len().max(1);
+        let mut chosen: Vec<usize> = Vec::new();
+        let mut netgroups = HashSet::new();
+        let anchors = original_peers.iter().take(defense.anchors);
+        for &candidate in anchors.chain(sybils.iter()).chain(original_peers.iter()) {
+            if chosen.len() >= slots {
+                break;
+            }
+            if chosen.contains(&candidate) {
+                continue;
+            }
+            if defense.diverse_outbound && !netgroups.insert(self.nodes[candidate].netgroup) {
+                continue;
+            }
+            chosen.push(candidate);
+        }
+
+        for &peer in &original_peers {
+            if !chosen.contains(&peer) {
+                self.disconnect_nodes(victim, peer);
+            }
+        }
+        for &peer in chosen.iter().filter(|peer| sybils.contains(peer)) {
+            self.connect_nodes(victim, peer)?;
+        }
+        let honest_peers = chosen.iter().copied().filter(|peer| !sybils.contains(peer)).collect();
+
+        for &id in &sybils {
+            self.set_hashrate(id, sybil_hashrate)?;
+        }
+
+        self.eclipse = Some(EclipseAttack {
+            victim,
+            sybils: sybils.clone(),
+            original_peers,
+            honest_peers,
+            defense,
+            started_at: self.now(),
+            ended_at: None,
+        });
+        Ok(sybils)
+    }
+
+    // Tutulma saldırısını bitir: Sybil'ler kurbandan ve madencilikten çekilir, kurban eski komşularına bağlanır
+    // Son rapor bağlantılar yeniden kurulmadan önce alınır; kurban ardından dürüst zincire geçer.
+    #[cfg(feature = "attacks")]
+    pub fn stop_eclipse(&mut self) -> Option<EclipseReport> {
+        let attack = self.eclipse.clone().filter(|attack| attack.is_active())?;
+        let report = self.eclipse_report()?;
+
+        for &sybil in &attack.sybils {
+            self.disconnect_nodes(attack.victim, sybil);
+            let _ = self.set_hashrate(sybil, 0.0);
+        }
+        for &peer in &attack.original_peers {
+            if !self.nodes[attack.victim].connections.contains(&peer) && self.connect_nodes(attack.victim, peer).is_ok() {
+                // Yeniden bağlanan taraflar birbirinden bilmediği blokların başlıklarını ister
+                for (from, to) in [(attack.victim, peer), (peer, attack.victim)] {
+                    let locator = self.nodes[from].block_locator();
+                    self.send_message(from, to, Message::GetHeaders { locator });
+                }
+            }
+        }
+
+        let now = self.now();
+        if let Some(attack) = self.eclipse.as_mut() {
+            attack.ended_at = Some(now);
+        }
+        self.process_due_events();
+        Some(report)
+    }
+
+    // Kurbanın zincirinin dürüst zincir ucundan sapması
+    // Saldırı bittikten sonra çağrılırsa kurbanın dürüst zincire ne kadar yetiştiği görülür
+    pub fn eclipse_report(&self) -> Option<EclipseReport> {
+        let attack = self.eclipse.as_ref()?;
+        let victim = &self.nodes[attack.victim];
+        let honest = self.eclipse_honest_chain(attack);
+
+        let common_prefix = victim
+            .blockchain
+            .iter()
+            .zip(honest.iter())
+            .take_while(|(a, b)| a.hash == b.hash)
+            .count();
+        let honest_peers: Vec<usize> = victim.connections.iter().copied().filter(|peer| !attack.sybils.contains(peer)).collect();
+
+        Some(EclipseReport {
+            victim: attack.victim,
+            sybil_peers: victim.connections.len() - honest_peers.len(),
+            eclipsed: honest_peers.is_empty(),
+            honest_peers,
+            victim_height: victim.blockchain.len(),
+            honest_height: honest.len(),
+            common_prefix,
+            missed_blocks: honest.len() - common_prefix,
+            fake_blocks: victim.blockchain.len() - common_prefix,
+            duration: attack.ended_at.unwrap_or(self.now()) - attack.started_at,
+        })
+    }
+
+    // Başka bir saldırı senaryosu sürüyor mu
+    #[cfg(feature = "attacks")]
+    pub(crate) fn attack_in_progress(&self) -> bool {
+        self.selfish.is_some()
+            || self.double_spend.as_ref().map(|attack| attack.status == crate::double_spend::DoubleSpendStatus::Mining).unwrap_or(false)
+            || self.eclipse.as_ref().map(|attack| attack.is_active()).unwrap_or(false)
+    }
+
+    // Kurban ve Sybil'ler dışındaki node'ların en uzun zinciri (eşitlikte en küçük ID)
+    fn eclipse_honest_chain(&self, attack: &EclipseAttack) -> &[Block] {
+        let mut best: &[Block] = &[];
+        for node in self.nodes.iter().filter(|node| node.id != attack.victim && !attack.sybils.contains(&node.id)) {
+            if node.blockchain.len() > best.len() {
+                best = &node.blockchain;
+            }
+        }
+        best
+    }
+}
@@ -0,0 +1,456 @@
+use std::error::Error;
+use std::fmt;
+
+// Cüzdan işlemlerinde oluşabilecek hatalar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WalletError {
+    // Gönderilmek istenen miktar bakiyeden büyük
+    InsufficientFunds { available: u64, requested: u64 },
+    // Sıfır coin transfer edilemez
+    ZeroAmount,
+    // İmza DER formatında çözümlenemedi
+    MalformedSignature,
+    // İmza geçerli formatta ama veriyle/anahtarla eşleşmiyor
+    SignatureMismatch,
+}
+
+impl fmt::Display for WalletError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WalletError::InsufficientFunds { available, requested } => write!(
+                f,
+                "Yetersiz bakiye: {} coin mevcut, {} coin isteniyor",
+                *available as f64 / 100_000_000.0,
+                *requested as f64 / 100_000_000.0
+            ),
+            WalletError::ZeroAmount => write!(f, "Transfer miktarı sıfır olamaz"),
+            WalletError::MalformedSignature => write!(f, "İmza DER formatında çözümlenemedi"),
+            WalletError::SignatureMismatch => write!(f, "İmza doğrulanamadı"),
+        }
+    }
+}
+
+impl Error for WalletError {}
+
+// İşlem oluşturma ve doğrulama hataları
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionError {
+    // Girdinin harcamak istediği UTXO, UTXO setinde yok (harcanmış ya da hiç oluşmamış)
+    MissingUtxo { utxo_id: String },
+    // Çıktıların toplamı girdilerin toplamını aşıyor
+    OutputsExceedInputs { input: u64, output: u64 },
+    // UTXO mempool'daki başka bir işlem tarafından zaten harcanıyor
+    DoubleSpend { utxo_id: String },
+    // UTXO stake olarak kilitli, harcanamaz
+    StakeLocked { utxo_id: String },
+    // Cüzdan işlemi oluşturamadı
+    Wallet(WalletError),
+}
+
+impl fmt::Display for TransactionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TransactionError::MissingUtxo { utxo_id } => write!(f, "UTXO bulunamadı: {}", utxo_id),
+            TransactionError::OutputsExceedInputs { input, output } => write!(
+                f,
+                "Çıktı miktarı ({}) girdi miktarından ({}) büyük",
+                output, input
+            ),
+            TransactionError::DoubleSpend { utxo_id } => {
+                write!(f, "UTXO bekleyen başka bir işlem tarafından harcanıyor: {}", utxo_id)
+            }
+            TransactionError::StakeLocked { utxo_id } => write!(f, "UTXO stake olarak kilitli: {}", utxo_id),
+            TransactionError::Wallet(e) => write!(f, "Cüzdan hatası: {}", e),
+        }
+    }
+}
+
+impl Error for TransactionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TransactionError::Wallet(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<WalletError> for TransactionError {
+    fn from(e: WalletError) -> Self {
+        TransactionError::Wallet(e)
+    }
+}
+
+// Çifte imza kanıtı hataları
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EvidenceError {
+    // İki başlık aynı blok
+    SameBlock,
+    // Başlıklar farklı yüksekliklerde
+    DifferentHeights { first: usize, second: usize },
+    // Başlıkları farklı önericiler imzalamış
+    DifferentProposers,
+    // Başlıklardan biri geçerli bir önerici imzası taşımıyor
+    InvalidSignature { hash: String },
+    // Suçlunun kesilecek kilitli stake'i yok
+    NothingToSlash { offender: String },
+    // Bu suç için zincirde zaten ceza uygulanmış
+    AlreadySlashed,
+}
+
+impl fmt::Display for EvidenceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EvidenceError::SameBlock => write!(f, "Kanıttaki iki başlık aynı blok"),
+            EvidenceError::DifferentHeights { first, second } => {
+                write!(f, "Kanıttaki başlıklar farklı yüksekliklerde: #{} ve #{}", first, second)
+            }
+            EvidenceError::DifferentProposers => write!(f, "Kanıttaki başlıkları farklı önericiler imzalamış"),
+            EvidenceError::InvalidSignature { hash } => write!(f, "Başlık {} geçerli bir önerici imzası taşımıyor", hash),
+            EvidenceError::NothingToSlash { offender } => write!(f, "{} adresinin kesilecek stake'i yok", offender),
+            EvidenceError::AlreadySlashed => write!(f, "Bu çifte imza için zaten ceza uygulanmış"),
+        }
+    }
+}
+
+impl Error for EvidenceError {}
+
+// Blok ve zincir doğrulama hataları
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    // Blok indeksi zincirin devamı değil
+    InvalidIndex { expected: usize, found: usize },
+    // Önceki hash zincirin son bloğuyla eşleşmiyor
+    InvalidPreviousHash { expected: String, found: String },
+    // Blok hash'i içerikten hesaplanan hash ile aynı değil
+    InvalidHash { index: usize },
+    // Hash, zorluk seviyesinin istediği sayıda 0 ile başlamıyor
+    InsufficientProofOfWork { index: usize, difficulty: usize },
+    // Merkle kökü işlemlerle eşleşmiyor
+    InvalidMerkleRoot { index: usize },
+    // İlk işlem coinbase değil
+    InvalidCoinbase { index: usize },
+    // Bloktaki bir işlem geçersiz
+    InvalidTransaction { tx_id: String, error: TransactionError },
+    // Zincir boşken gelen blok genesis bloğu değil
+    ExpectedGenesis { found: usize },
+    // PoS bloğunda önerici public key'i yok veya çözümlenemedi
+    MissingProposer { index: usize },
+    // Önerenin imzası blok hash'iyle eşleşmiyor
+    InvalidProposerSignature { index: usize },
+    // Blok, o slot için stake ağırlıklı seçilen önerici tarafından üretilmemiş
+    WrongProposer { index: usize, expected: String, found: String },
+    // Slot için önerici seçilemiyor: hiç stake kilitlenmemiş
+    NoStake { index: usize },
+    // Bloğun zaman damgası ebeveyninden sonra değil
+    TimestampTooEarly { index: usize },
+    // Bloğun zaman damgası node'un saatinin izin verilen sapmadan daha ilerisinde
+    TimestampInFuture { index: usize, timestamp: u64, now: u64 },
+    // Bloktaki çifte imza kanıtı geçersiz
+    Evidence { index: usize, error: EvidenceError },
+    // Kanıtlara karşılık gelen ceza işlemleri eksik veya beklenenden farklı
+    InvalidSlashing { index: usize },
+    // Blok veya zincir, BFT ile kesinleşmiş bir bloğun yerine geçmeye çalışıyor
+    ConflictsWithFinalized { index: usize, finalized: usize },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationError::InvalidIndex { expected, found } => {
+                write!(f, "Geçersiz blok indeksi: {} bekleniyordu, {} geldi", expected, found)
+            }
+            ValidationError::InvalidPreviousHash { expected, found } => {
+                write!(f, "Geçersiz önceki hash: {} bekleniyordu, {} geldi", expected, found)
+            }
+            ValidationError::InvalidHash { index } => write!(f, "Blok #{} hash'i geçersiz", index),
+            ValidationError::InsufficientProofOfWork { index, difficulty } => write!(
+                f,
+                "Blok #{} Proof of Work şartını sağlamıyor (zorluk: {})",
+                index, difficulty
+            ),
+            ValidationError::InvalidMerkleRoot { index } => {
+                write!(f, "Blok #{} merkle kökü geçersiz", index)
+            }
+            ValidationError::InvalidCoinbase { index } => {
+                write!(f, "Blok #{} geçerli bir coinbase işlemi ile başlamıyor", index)
+            }
+            ValidationError::InvalidTransaction { tx_id, error } => {
+                write!(f, "Geçersiz işlem {}: {}", tx_id, error)
+            }
+            ValidationError::ExpectedGenesis { found } => {
+                write!(f, "Zincir boş, genesis bloğu bekleniyordu ama #{} geldi", found)
+            }
+            ValidationError::MissingProposer { index } => {
+                write!(f, "Blok #{} geçerli bir önerici public key'i taşımıyor", index)
+            }
+            ValidationError::InvalidProposerSignature { index } => {
+                write!(f, "Blok #{} önerici imzası geçersiz", index)
+            }
+            ValidationError::WrongProposer { index, expected, found } => write!(
+                f,
+                "Blok #{} yanlış önerici tarafından üretilmiş: {} bekleniyordu, {} geldi",
+                index, expected, found
+            ),
+            ValidationError::NoStake { index } => {
+                write!(f, "Blok #{} için önerici seçilemiyor: kilitli stake yok", index)
+            }
+            ValidationError::TimestampTooEarly { index } => {
+                write!(f, "Blok #{} zaman damgası önceki bloktan sonra değil", index)
+            }
+            ValidationError::TimestampInFuture { index, timestamp, now } => {
+                write!(f, "Blok #{} zaman damgası ({}) şu anki zamanın ({}) çok ilerisinde", index, timestamp, now)
+            }
+            ValidationError::Evidence { index, error } => {
+                write!(f, "Blok #{} geçersiz çifte imza kanıtı içeriyor: {}", index, error)
+            }
+            ValidationError::InvalidSlashing { index } => {
+                write!(f, "Blok #{} ceza işlemleri kanıtlarla eşleşmiyor", index)
+            }
+            ValidationError::ConflictsWithFinalized { index, finalized } => write!(
+                f,
+                "Blok #{} kesinleşmiş zincirle çelişiyor (#{} yüksekliğine kadar bloklar kesinleşti)",
+                index, finalized
+            ),
+        }
+    }
+}
+
+impl Error for ValidationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ValidationError::InvalidTransaction { error, .. } => Some(error),
+            ValidationError::Evidence { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+// Ağ seviyesindeki hatalar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkError {
+    // Verilen ID ile bir node yok
+    NodeNotFound(usize),
+    // Ağda hiç node yok
+    NoNodes,
+    // Node validator değil, blok üretemez
+    NotValidator(usize),
+    // Madencilik için seçili validator yok
+    NoValidatorSelected,
+    // Madencilik yarışına katılan hiçbir node'un hash gücü yok
+    NoHashPower,
+    // PoS: bu slot için blok önermeye yetkili node yok
+    NoProposer,
+    // Node bu yükseklikte zaten bir blok imzaladı (çifte imza koruması)
+    DoubleSign { node: usize, height: usize },
+    // Hash gücü negatif veya sayı değil
+    InvalidHashrate(String),
+    // Pay 0 ile 1 arasında olmalı (hash gücü payı, gamma vb.)
+    InvalidShare { name: &'static str, value: String },
+    // Senaryo PoW hash yarışı madencilik modelini gerektiriyor
+    HashRaceRequired,
+    // Başka bir saldırı senaryosu zaten sürüyor
+    AttackInProgress,
+    // Saldırgan ve hedef aynı node
+    SelfTarget(usize),
+    // Tutulma saldırısı en az bir Sybil node gerektirir
+    NoSybils,
+    // Node çevrimdışı (ayrılmış veya çökmüş)
+    NodeOffline(usize),
+    // Node zaten çevrimiçi
+    NodeOnline(usize),
+    // Node kendisine bağlanamaz
+    SelfConnection(usize),
+    // Node'lardan biri diğerini yasakladığı için bağlantı kurulamaz
+    Banned { node: usize, peer: usize },
+    // Oturum süresi dağılımı veya çökme olasılığı geçersiz
+    InvalidChurnModel(String),
+    // İşlem yükü parametreleri geçersiz (oran, miktar dağılımı veya toplu ödeme boyutu)
+    InvalidWorkload(String),
+    // BFT kesinlik katmanı için validator kümesi boş
+    EmptyValidatorSet,
+    // BFT kesinlik katmanı açılmamış
+    FinalityNotEnabled,
+    MiningAlreadyActive,
+    MiningNotActive,
+    Transaction(TransactionError),
+    Validation(ValidationError),
+    Topology(TopologyError),
+    Evidence(EvidenceError),
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NetworkError::NodeNotFound(id) => write!(f, "Node {} bulunamadı", id),
+            NetworkError::NoNodes => write!(f, "Ağda hiç node yok"),
+            NetworkError::NotValidator(id) => write!(f, "Node {} validator değil", id),
+            NetworkError::NoValidatorSelected => write!(f, "Madencilik için seçili validator yok"),
+            NetworkError::NoHashPower => write!(f, "Madencilik için hash gücü olan node yok"),
+            NetworkError::NoProposer => write!(f, "Bu slot için blok önermeye yetkili node yok"),
+            NetworkError::DoubleSign { node, height } => {
+                write!(f, "Node {} #{} yüksekliğinde zaten bir blok imzaladı", node, height)
+            }
+            NetworkError::InvalidHashrate(value) => write!(f, "Geçersiz hash gücü: {}", value),
+            NetworkError::InvalidShare { name, value } => write!(f, "Geçersiz {}: {} (0 ile 1 arasında olmalı)", name, value),
+            NetworkError::HashRaceRequired => write!(f, "Bu senaryo PoW hash yarışı madencilik modelini gerektirir (--mining race)"),
+            NetworkError::AttackInProgress => write!(f, "Başka bir saldırı senaryosu zaten sürüyor"),
+            NetworkError::SelfTarget(id) => write!(f, "Node {} kendisini hedef alamaz", id),
+            NetworkError::NoSybils => write!(f, "Tutulma saldırısı için en az bir Sybil node gerekli"),
+            NetworkError::NodeOffline(id) => write!(f, "Node {} çevrimdışı", id),
+            NetworkError::NodeOnline(id) => write!(f, "Node {} zaten çevrimiçi", id),
+            NetworkError::SelfConnection(id) => write!(f, "Node {} kendisine bağlanamaz", id),
+            NetworkError::Banned { node, peer } => write!(f, "Node {} ile Node {} arasında yasak var, bağlantı kurulamaz", node, peer),
+            NetworkError::InvalidChurnModel(value) => write!(f, "Geçersiz oturum modeli: {}", value),
+            NetworkError::InvalidWorkload(value) => write!(f, "Geçersiz işlem yükü: {}", value),
+            NetworkError::EmptyValidatorSet => write!(f, "Kesinlik katmanı için en az bir validator gerekli"),
+            NetworkError::FinalityNotEnabled => write!(f, "BFT kesinlik katmanı açık değil"),
+            NetworkError::MiningAlreadyActive => write!(f, "Madencilik zaten aktif"),
+            NetworkError::MiningNotActive => write!(f, "Madencilik zaten durdurulmuş"),
+            NetworkError::Transaction(e) => write!(f, "İşlem hatası: {}", e),
+            NetworkError::Validation(e) => write!(f, "Doğrulama hatası: {}", e),
+            NetworkError::Topology(e) => write!(f, "Topoloji hatası: {}", e),
+            NetworkError::Evidence(e) => write!(f, "Kanıt hatası: {}", e),
+        }
+    }
+}
+
+impl Error for NetworkError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            NetworkError::Transaction(e) => Some(e),
+            NetworkError::Validation(e) => Some(e),
+            NetworkError::Topology(e) => Some(e),
+            NetworkError::Evidence(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<TransactionError> for NetworkError {
+    fn from(e: TransactionError) -> Self {
+        NetworkError::Transaction(e)
+    }
+}
+
+impl From<ValidationError> for NetworkError {
+    fn from(e: ValidationError) -> Self {
+        NetworkError::Validation(e)
+    }
+}
+
+impl From<TopologyError> for NetworkError {
+    fn from(e: TopologyError) -> Self {
+        NetworkError::Topology(e)
+    }
+}
+
+impl From<EvidenceError> for NetworkError {
+    fn from(e: EvidenceError) -> Self {
+        NetworkError::Evidence(e)
+    }
+}
+
+// Ağ topolojisi oluşturma hataları
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TopologyError {
+    // Topoloji adı tanınmadı
+    UnknownTopology(String),
+    // Topoloji parametresi geçersiz veya bu node sayısıyla kurulamaz
+    InvalidParameter(String),
+    // Kenar listesinde var olmayan bir node geçiyor
+    NodeOutOfRange { node: usize, node_count: usize },
+    // Kenar listesi dosyası okunamadı
+    Io(String),
+    // Kenar listesi satırı çözümlenemedi
+    Parse { line: usize, content: String },
+}
+
+impl fmt::Display for TopologyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TopologyError::UnknownTopology(spec) => write!(
+                f,
+                "Bilinmeyen topoloji: {} (mesh, ring, star[:merkez], regular:<k>, er:<p>, ws:<k>:<p>, ba:<m>, file:<yol>)",
+                spec
+            ),
+            TopologyError::InvalidParameter(message) => write!(f, "Geçersiz topoloji parametresi: {}", message),
+            TopologyError::NodeOutOfRange { node, node_count } => {
+                write!(f, "Node {} yok (ağda {} node var)", node, node_count)
+            }
+            TopologyError::Io(message) => write!(f, "Kenar listesi okunamadı: {}", message),
+            TopologyError::Parse { line, content } => {
+                write!(f, "Kenar listesi satır {} çözümlenemedi: {}", line, content)
+            }
+        }
+    }
+}
+
+impl Error for TopologyError {}
+
+// Senaryo dosyası okunurken veya ağ kurulurken oluşan hatalar
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScenarioError {
+    // Senaryo dosyası okunamadı veya sonuçlar yazılamadı
+    Io(String),
+    // TOML sözdizimi hatası
+    Parse(String),
+    // Alan eksik, tanınmıyor veya değeri geçersiz
+    InvalidField { field: String, message: String },
+    // Senaryodaki ağ kurulamadı
+    Setup(NetworkError),
+}
+
+impl fmt::Display for ScenarioError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScenarioError::Io(message) => write!(f, "Senaryo dosyası hatası: {}", message),
+            ScenarioError::Parse(message) => write!(f, "Senaryo çözümlenemedi: {}", message),
+            ScenarioError::InvalidField { field, message } => write!(f, "Geçersiz senaryo alanı {}: {}", field, message),
+            ScenarioError::Setup(e) => write!(f, "Senaryo ağı kurulamadı: {}", e),
+        }
+    }
+}
+
+impl Error for ScenarioError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ScenarioError::Setup(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<NetworkError> for ScenarioError {
+    fn from(e: NetworkError) -> Self {
+        ScenarioError::Setup(e)
+    }
+}
+
+// TCP çerçevelerinin çözülmesi sırasında oluşan hatalar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WireError {
+    // Veri beklenenden önce bitti
+    UnexpectedEof,
+    // Bilinmeyen paket, mesaj veya öğe türü
+    UnknownTag(u8),
+    // Metin alanı geçerli UTF-8 değil
+    InvalidUtf8,
+    // Paket çözüldükten sonra artan byte'lar var
+    TrailingBytes(usize),
+    // Çerçeve izin verilen boyuttan büyük
+    FrameTooLarge(usize),
+}
+
+impl fmt::Display for WireError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WireError::UnexpectedEof => write!(f, "Veri beklenenden önce bitti"),
+            WireError::UnknownTag(tag) => write!(f, "Bilinmeyen tür etiketi: {}", tag),
+            WireError::InvalidUtf8 => write!(f, "Metin alanı geçerli UTF-8 değil"),
+            WireError::TrailingBytes(count) => write!(f, "Paketin sonunda {} fazla byte var", count),
+            WireError::FrameTooLarge(size) => write!(f, "Çerçeve çok büyük: {} byte", size),
+        }
+    }
+}
+
+impl Error for WireError {}
@@ -0,0 +1,312 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use crate::churn::ChurnEvent;
+use crate::metrics::{MetricRow, MetricValue};
+use crate::network::BlockchainNetwork;
+use crate::sim::SimTime;
+use crate::sync::SyncProgress;
+
+// Simülasyon sırasında ağda olan ve dışarıya bildirilen olaylar
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    // Node yeni bir blok üretti
+    BlockMined { node: usize, height: usize, hash: String, transactions: usize },
+    // Node komşusundan gelen bloğu zincirine ekledi
+    BlockAccepted { node: usize, from: usize, height: usize, hash: String },
+    // Node komşusundan gelen bloğu geçersiz buldu
+    BlockRejected { node: usize, from: usize, hash: String, reason: String },
+    // İşlem node'un mempool'una girdi (göndericide oluşturulduğunda veya komşudan geldiğinde)
+    TxAdmitted { node: usize, tx_id: String },
+    // Node daha uzun bir dala geçti ve aktif zincirinden blok geri aldı
+    Reorg { node: usize, depth: usize, new_tip: String },
+    // Sıradaki bloğu üretecek node belirlendi
+    ValidatorSelected { node: usize },
+    // Node ceza puanı eşiği aşan komşusunun bağlantısını kesip onu yasakladı
+    PeerBanned { node: usize, peer: usize, reason: String, score: u32 },
+    // Node ağdan ayrıldı, çöktü veya yeniden başladı
+    Churn { node: usize, transition: ChurnEvent },
+    // Senkronize olan node'un ilerlemesi (her %25'te bir ve tamamlandığında)
+    SyncProgress(SyncProgress),
+}
+
+impl Event {
+    // Olay türünün JSON satırlarında ve filtrelerde kullanılan adı
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Event::BlockMined { .. } => "block_mined",
+            Event::BlockAccepted { .. } => "block_accepted",
+            Event::BlockRejected { .. } => "block_rejected",
+            Event::TxAdmitted { .. } => "tx_admitted",
+            Event::Reorg { .. } => "reorg",
+            Event::ValidatorSelected { .. } => "validator_selected",
+            Event::PeerBanned { .. } => "peer_banned",
+            Event::Churn { .. } => "churn",
+            Event::SyncProgress(_) => "sync_progress",
+        }
+    }
+
+    // Tanınan tüm olay türleri
+    pub fn kinds() -> &'static [&'static str] {
+        &["block_mined", "block_accepted", "block_rejected", "tx_admitted", "reorg", "validator_selected", "peer_banned", "churn", "sync_progress"]
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Event::BlockMined { node, height, hash, transactions } => {
+                write!(f, "Yeni blok oluşturuldu: #{} {} (Node {}, {} işlem)", height, hash, node, transactions)
+            }
+            Event::BlockAccepted { node, from, height, hash } => {
+                write!(f, "Node {} bloğu kabul etti: #{} {} (Node {}'den)", node, height, hash, from)
+            }
+            Event::BlockRejected { node, from, hash, reason } => {
+                write!(f, "Node {} bloğu reddetti: {} (Node {}'den): {}", node, hash, from, reason)
+            }
+            Event::TxAdmitted { node, tx_id } => write!(f, "Node {} işlemi mempool'a aldı: {}", node, tx_id),
+            Event::Reorg { node, depth, new_tip } => {
+                write!(f, "Node {} zincir değiştirdi: {} blok geri alındı, yeni uç {}", node, depth, new_tip)
+            }
+            Event::ValidatorSelected { node } => write!(f, "Node {} blok üreticisi olarak seçildi", node),
+            Event::PeerBanned { node, peer, reason, score } => {
+                write!(f, "Node {}, Node {}'i yasakladı: {} ({} puan)", node, peer, reason, score)
+            }
+            Event::Churn { node, transition } => write!(f, "Node {} {}", node, transition),
+            Event::SyncProgress(progress) => write!(f, "Senkronizasyon: {}", progress),
+        }
+    }
+}
+
+// Olayın gerçekleştiği sanal zamanla birlikte kaydı
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventRecord {
+    pub time: SimTime,
+    pub event: Event,
+}
+
+impl EventRecord {
+    // Tek satırlık JSON nesnesi
+    pub fn to_json(&self) -> String {
+        let pairs: Vec<String> = self.fields().iter().map(|(name, value)| format!("\"{}\":{}", name, value.json())).collect();
+        format!("{{{}}}", pairs.join(","))
+    }
+}
+
+impl MetricRow for EventRecord {
+    fn fields(&self) -> Vec<(&'static str, MetricValue)> {
+        let mut fields = vec![
+            ("time", MetricValue::Integer(self.time)),
+            ("event", MetricValue::Text(self.event.kind().to_string())),
+        ];
+        let count = |value: usize| MetricValue::Integer(value as u64);
+        let text = |value: &str| MetricValue::Text(value.to_string());
+        match &self.event {
+            Event::BlockMined { node, height, hash, transactions } => {
+                fields.extend([("node", count(*node)), ("height", count(*height)), ("hash", text(hash)), ("transactions", count(*transactions))]);
+            }
+            Event::BlockAccepted { node, from, height, hash } => {
+                fields.extend([("node", count(*node)), ("from", count(*from)), ("height", count(*height)), ("hash", text(hash))]);
+            }
+            Event::BlockRejected { node, from, hash, reason } => {
+                fields.extend([("node", count(*node)), ("from", count(*from)), ("hash", text(hash)), ("reason", text(reason))]);
+            }
+            Event::TxAdmitted { node, tx_id } => fields.extend([("node", count(*node)), ("tx_id", text(tx_id))]),
+            Event::Reorg { node, depth, new_tip } => {
+                fields.extend([("node", count(*node)), ("depth", count(*depth)), ("new_tip", text(new_tip))]);
+            }
+            Event::ValidatorSelected { node } => fields.push(("node", count(*node))),
+            Event::PeerBanned { node, peer, reason, score } => {
+                fields.extend([("node", count(*node)), ("peer", count(*peer)), ("reason", text(reason)), ("score", MetricValue::Integer(*score as u64))]);
+            }
+            Event::Churn { node, transition } => {
+                let transition = match transition {
+                    ChurnEvent::Left => "left",
+                    ChurnEvent::Crashed => "crashed",
+                    ChurnEvent::Restarted => "restarted",
+                };
+                fields.extend([("node", count(*node)), ("transition", text(transition))]);
+            }
+            Event::SyncProgress(progress) => fields.extend([
+                ("node", count(progress.node_id)),
+                ("headers", count(progress.headers)),
+                ("height", count(progress.height)),
+                ("target", count(progress.target)),
+                ("elapsed", MetricValue::Integer(progress.elapsed)),
+                ("complete", MetricValue::Bool(progress.complete)),
+            ]),
+        }
+        fields
+    }
+}
+
+impl fmt::Display for EventRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{} ms] {}", self.time, self.event)
+    }
+}
+
+// Olay veri yoluna bağlanan abone
+// Ağ thread'ler arasında paylaşılabildiği için aboneler de Send olmalıdır.
+pub trait EventSink: Send {
+    fn handle(&mut self, record: &EventRecord);
+}
+
+// Kapanışlar doğrudan abone olarak kullanılabilir
+impl<F: FnMut(&EventRecord) + Send> EventSink for F {
+    fn handle(&mut self, record: &EventRecord) {
+        self(record)
+    }
+}
+
+// Olayları her satırda bir JSON nesnesi olarak dosyaya yazar
+// Her satırdan sonra dosya boşaltılır, böylece `tail -f` ile canlı izlenebilir.
+pub struct JsonlSink {
+    writer: BufWriter<File>,
+}
+
+impl JsonlSink {
+    pub fn create(path: &str) -> io::Result<JsonlSink> {
+        Ok(JsonlSink { writer: BufWriter::new(File::create(path)?) })
+    }
+}
+
+impl EventSink for JsonlSink {
+    fn handle(&mut self, record: &EventRecord) {
+        // Yazma hataları simülasyonu durdurmaz
+        let _ = writeln!(self.writer, "{}", record.to_json()).and_then(|_| self.writer.flush());
+    }
+}
+
+// Olayları okunabilir biçimde standart çıktıya yazar
+#[derive(Debug, Clone, Default)]
+pub struct StdoutSink {
+    pub kinds: Option<Vec<String>>, // Yazılacak olay türleri (boşsa hepsi)
+}
+
+impl StdoutSink {
+    pub fn new() -> StdoutSink {
+        StdoutSink::default()
+    }
+
+    // Sadece verilen türdeki olayları yaz
+    pub fn only(kinds: &[&str]) -> StdoutSink {
+        StdoutSink { kinds: Some(kinds.iter().map(|kind| kind.to_string()).collect()) }
+    }
+}
+
+impl EventSink for StdoutSink {
+    fn handle(&mut self, record: &EventRecord) {
+        let wanted = self.kinds.as_ref().map(|kinds| kinds.iter().any(|kind| kind == record.event.kind())).unwrap_or(true);
+        if wanted {
+            println!("{}", record.event);
+        }
+    }
+}
+
+// Olayları tüm abonelere dağıtan veri yolu
+#[derive(Default)]
+pub struct EventBus {
+    sinks: Vec<Box<dyn EventSink>>,
+}
+
+impl fmt::Debug for EventBus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "EventBus({} abone)", self.sinks.len())
+    }
+}
+
+impl EventBus {
+    pub fn new() -> EventBus {
+        EventBus::default()
+    }
+
+    // Yeni bir abone ekle
+    pub fn subscribe<S: EventSink + 'static>(&mut self, sink: S) {
+        self.sinks.push(Box::new(sink));
+    }
+
+    // Tüm aboneleri kaldır
+    pub fn clear(&mut self) {
+        self.sinks.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sinks.is_empty()
+    }
+
+    // Olayı abone sırasıyla tüm abonelere ilet
+    pub fn emit(&mut self, record: &EventRecord) {
+        for sink in self.sinks.iter_mut() {
+            sink.handle(record);
+        }
+    }
+}
+
+impl BlockchainNetwork {
+    // Olayı şu anki sanal zamanla abonelere ilet
+    pub(crate) fn emit(&mut self, event: Event) {
+        if self.events.is_empty() {
+            return;
+        }
+        let record = EventRecord { time: self.now(), event };
+        self.events.emit(&record);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::topology::Topology;
+
+    #[test]
+    fn jsonl_sink_writes_one_object_per_event() {
+        let path = std::env::temp_dir().join(format!("blockchain-sim-events-{}.jsonl", std::process::id()));
+        let path = path.to_str().unwrap().to_string();
+
+        let mut network = BlockchainNetwork::with_seed(9);
+        network.events.subscribe(JsonlSink::create(&path).unwrap());
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&records);
+        network.events.subscribe(move |record: &EventRecord| sink.lock().unwrap().push(record.clone()));
+
+        for _ in 0..3 {
+            network.add_node();
+        }
+        network.apply_topology(&Topology::FullMesh).unwrap();
+        network.select_random_validator().unwrap();
+        network.mine_block().unwrap();
+        network.run_for(5_000);
+        network.crash_node(2).unwrap();
+
+        let text = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        let records = records.lock().unwrap();
+        assert!(records.iter().any(|record| record.event.kind() == "block_mined"));
+        assert!(records.iter().any(|record| record.event.kind() == "churn"));
+
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), records.len());
+        for (line, record) in lines.iter().zip(records.iter()) {
+            assert!(line.starts_with('{') && line.ends_with('}'));
+            assert!(line.starts_with(&format!("{{\"time\":{},\"event\":\"{}\"", record.time, record.event.kind())));
+            assert_eq!(*line, record.to_json());
+        }
+    }
+
+    #[test]
+    fn event_json_escapes_text_fields() {
+        let record = EventRecord {
+            time: 7,
+            event: Event::PeerBanned { node: 1, peer: 2, reason: "\"geçersiz\" blok".to_string(), score: 100 },
+        };
+        assert_eq!(
+            record.to_json(),
+            "{\"time\":7,\"event\":\"peer_banned\",\"node\":1,\"peer\":2,\"reason\":\"\\\"geçersiz\\\" blok\",\"score\":100}"
+        );
+    }
+}
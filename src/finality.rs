@@ -0,0 +1,995 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::str::FromStr;
+
+use secp256k1::PublicKey;
+use sha2::{Digest, Sha256};
+
+use crate::block::Block;
+use crate::error::{NetworkError, ValidationError};
+use crate::message::{InvItem, Message};
+use crate::network::{BlockchainNetwork, ReorgRecord, SimEvent};
+use crate::node::Node;
+use crate::sim::SimTime;
+use crate::wallet::Wallet;
+
+// Bir BFT turunun adımları (Tendermint): önce önerici bir blok önerir,
+// sonra validator'lar ön oy (prevote) ve kesin oy (precommit) verir
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BftStep {
+    Propose,
+    Prevote,
+    Precommit,
+}
+
+impl fmt::Display for BftStep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BftStep::Propose => write!(f, "öneri"),
+            BftStep::Prevote => write!(f, "ön oy"),
+            BftStep::Precommit => write!(f, "kesin oy"),
+        }
+    }
+}
+
+// Oy türü
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum VoteKind {
+    Prevote,
+    Precommit,
+}
+
+// Simülasyonda bir validator'un Bizans (kötü niyetli) davranışı
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BftFault {
+    // Hiç öneri veya oy göndermez
+    Silent,
+    // Her oylamada komşularının yarısına bir değer, diğer yarısına başka bir değer için oy verir
+    Equivocate,
+}
+
+impl FromStr for BftFault {
+    type Err = String;
+
+    // "silent" veya "equivocate"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "silent" => Ok(BftFault::Silent),
+            "equivocate" => Ok(BftFault::Equivocate),
+            other => Err(format!("bilinmeyen Bizans davranışı: {} (silent veya equivocate)", other)),
+        }
+    }
+}
+
+impl fmt::Display for BftFault {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BftFault::Silent => write!(f, "sessiz"),
+            BftFault::Equivocate => write!(f, "çelişkili oy"),
+        }
+    }
+}
+
+// İmzanın sahibini doğrula ve imzalayanın adresini döndür
+fn signer_address(public_key: &str, digest: &str, signature: &[u8]) -> Option<String> {
+    let public_key = PublicKey::from_slice(&hex::decode(public_key).ok()?).ok()?;
+    Wallet::verify_with_key(&public_key, digest.as_bytes(), signature).ok()?;
+    Some(Wallet::generate_address(&public_key))
+}
+
+fn sha256_hex(contents: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(contents.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+// Bir yükseklik ve turda önericinin kesinleştirilmesini önerdiği blok
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Proposal {
+    pub height: usize,
+    pub round: u32,
+    pub block_hash: String,
+    pub valid_round: Option<u32>, // Blok daha önceki bir turda 2/3+ ön oy aldıysa o tur
+    pub proposer: String,         // Önericinin public key'i (hex)
+    pub signature: Vec<u8>,
+}
+
+impl Proposal {
+    pub fn new(height: usize, round: u32, block_hash: String, valid_round: Option<u32>, wallet: &Wallet) -> Self {
+        let mut proposal = Proposal {
+            height,
+            round,
+            block_hash,
+            valid_round,
+            proposer: hex::encode(wallet.get_public_key().serialize()),
+            signature: Vec::new(),
+        };
+        proposal.signature = wallet.sign(proposal.digest().as_bytes());
+        proposal
+    }
+
+    // İmzalanan içerik
+    fn digest(&self) -> String {
+        sha256_hex(&format!("proposal{}{}{}{:?}", self.height, self.round, self.block_hash, self.valid_round))
+    }
+
+    pub fn id(&self) -> String {
+        sha256_hex(&format!("{}{}", self.digest(), self.proposer))
+    }
+
+    // İmza geçerliyse önericinin adresi
+    pub fn verify(&self) -> Option<String> {
+        signer_address(&self.proposer, &self.digest(), &self.signature)
+    }
+
+    // Önerinin yaklaşık boyutu (byte)
+    pub fn size(&self) -> usize {
+        20 + self.block_hash.len() + self.proposer.len() + self.signature.len()
+    }
+}
+
+// Bir validator'un ön oyu veya kesin oyu; blok hash'i yoksa "nil" oyudur
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vote {
+    pub height: usize,
+    pub round: u32,
+    pub kind: VoteKind,
+    pub block_hash: Option<String>,
+    pub validator: String, // Validator'un public key'i (hex)
+    pub signature: Vec<u8>,
+}
+
+impl Vote {
+    pub fn new(height: usize, round: u32, kind: VoteKind, block_hash: Option<String>, wallet: &Wallet) -> Self {
+        let mut vote = Vote {
+            height,
+            round,
+            kind,
+            block_hash,
+            validator: hex::encode(wallet.get_public_key().serialize()),
+            signature: Vec::new(),
+        };
+        vote.signature = wallet.sign(vote.digest().as_bytes());
+        vote
+    }
+
+    // İmzalanan içerik
+    fn digest(&self) -> String {
+        sha256_hex(&format!("{:?}{}{}{:?}", self.kind, self.height, self.round, self.block_hash))
+    }
+
+    pub fn id(&self) -> String {
+        sha256_hex(&format!("{}{}", self.digest(), self.validator))
+    }
+
+    // İmza geçerliyse validator'un adresi
+    pub fn verify(&self) -> Option<String> {
+        signer_address(&self.validator, &self.digest(), &self.signature)
+    }
+
+    // Oyun yaklaşık boyutu (byte)
+    pub fn size(&self) -> usize {
+        18 + self.block_hash.as_ref().map(|hash| hash.len()).unwrap_or(0) + self.validator.len() + self.signature.len()
+    }
+}
+
+// Bir node'un kesinlik katmanındaki durumu (Tendermint algoritmasının değişkenleri)
+// Yükseklik, kesinleştirilmeye çalışılan bloğun zincirdeki indeksidir
+#[derive(Debug, Clone)]
+pub struct FinalityState {
+    pub validators: Vec<String>,          // Validator adresleri; önerici sırası bu sıradır
+    pub finalized_height: usize,          // Kesinleşmiş son bloğun indeksi (genesis her zaman kesindir)
+    pub height: usize,
+    pub round: u32,
+    pub step: BftStep,
+    pub started: bool,                    // Bu yükseklikte blok görülüp tur başladı mı
+    pub locked: Option<(String, u32)>,    // Kesin oy verilen ve kilitlenilen blok ve turu
+    pub valid: Option<(String, u32)>,     // 2/3+ ön oy almış en son blok ve turu
+    pub fault: Option<BftFault>,          // Simülasyonda atanmış Bizans davranışı
+    pub commits: BTreeMap<usize, Vec<Vote>>, // Kesinleşen yükseklikler ve 2/3+ kesin oy sertifikaları
+    proposals: BTreeMap<(usize, u32), Proposal>,
+    votes: BTreeMap<(usize, u32, VoteKind), Vec<(String, Vote)>>, // İmzalayan adresiyle birlikte oylar
+    timeouts: BTreeSet<(u32, BftStep)>,   // Bu yükseklikte planlanmış zaman aşımları
+    polka: BTreeSet<u32>,                 // Bu yükseklikte 2/3+ ön oy görülen turlar
+    checked: BTreeMap<String, bool>,      // Bu yükseklikte doğrulanmış aday bloklar
+}
+
+// Node'un bir sonraki adımı; kurallar sırayla değerlendirilir ve ilk uyan kural uygulanır
+#[derive(Debug, Clone)]
+pub(crate) enum FinalityAction {
+    StartRound(u32),
+    Prevote(Option<String>),
+    Precommit(Option<String>),
+    Polka(String),
+    ScheduleTimeout(BftStep),
+    Decide(usize, String),
+}
+
+impl FinalityState {
+    pub fn new(validators: Vec<String>) -> Self {
+        FinalityState {
+            validators,
+            finalized_height: 0,
+            height: 1,
+            round: 0,
+            step: BftStep::Propose,
+            started: false,
+            locked: None,
+            valid: None,
+            fault: None,
+            commits: BTreeMap::new(),
+            proposals: BTreeMap::new(),
+            votes: BTreeMap::new(),
+            timeouts: BTreeSet::new(),
+            polka: BTreeSet::new(),
+            checked: BTreeMap::new(),
+        }
+    }
+
+    // 2/3'ten fazla: n = 3f + 1 validator için 2f + 1
+    pub fn quorum(&self) -> usize {
+        self.validators.len() * 2 / 3 + 1
+    }
+
+    // Tolere edilen Bizans validator sayısı f
+    pub fn max_faulty(&self) -> usize {
+        self.validators.len().saturating_sub(1) / 3
+    }
+
+    // Yükseklik ve tur için önerici (validator listesinde sırayla)
+    pub fn proposer(&self, height: usize, round: u32) -> &str {
+        &self.validators[(height + round as usize) % self.validators.len()]
+    }
+
+    pub fn is_validator(&self, address: &str) -> bool {
+        self.validators.iter().any(|validator| validator == address)
+    }
+
+    // Oyu kaydet; aynı oy daha önce kaydedildiyse false
+    fn record_vote(&mut self, signer: String, vote: Vote) -> bool {
+        let votes = self.votes.entry((vote.height, vote.round, vote.kind)).or_default();
+        if votes.iter().any(|(_, known)| known.id() == vote.id()) {
+            return false;
+        }
+        votes.push((signer, vote));
+        true
+    }
+
+    // Öneriyi kaydet; tur için zaten bir öneri varsa false
+    fn record_proposal(&mut self, proposal: Proposal) -> bool {
+        let key = (proposal.height, proposal.round);
+        if self.proposals.contains_key(&key) {
+            return false;
+        }
+        self.proposals.insert(key, proposal);
+        true
+    }
+
+    // Verilen değere (None: herhangi bir değer) oy veren farklı validator sayısı
+    fn count(&self, height: usize, round: u32, kind: VoteKind, value: Option<&Option<String>>) -> usize {
+        let votes = match self.votes.get(&(height, round, kind)) {
+            Some(votes) => votes,
+            None => return 0,
+        };
+        let signers: BTreeSet<&str> = votes
+            .iter()
+            .filter(|(_, vote)| value.map(|value| &vote.block_hash == value).unwrap_or(true))
+            .map(|(signer, _)| signer.as_str())
+            .collect();
+        signers.len()
+    }
+
+    // Yeterli oy almış blok hash'leri (bu yükseklik ve tur için)
+    fn quorum_values(&self, height: usize, round: u32, kind: VoteKind) -> Vec<String> {
+        let values: BTreeSet<&String> = self
+            .votes
+            .get(&(height, round, kind))
+            .map(|votes| votes.iter().filter_map(|(_, vote)| vote.block_hash.as_ref()).collect())
+            .unwrap_or_default();
+        values
+            .into_iter()
+            .filter(|value| self.count(height, round, kind, Some(&Some((*value).clone()))) >= self.quorum())
+            .cloned()
+            .collect()
+    }
+
+    // Bir turda öneri veya oy göndermiş farklı validator sayısı
+    fn round_participants(&self, height: usize, round: u32) -> usize {
+        let mut senders: BTreeSet<&str> = BTreeSet::new();
+        for kind in [VoteKind::Prevote, VoteKind::Precommit] {
+            if let Some(votes) = self.votes.get(&(height, round, kind)) {
+                senders.extend(votes.iter().map(|(signer, _)| signer.as_str()));
+            }
+        }
+        if let Some(proposal) = self.proposals.get(&(height, round)) {
+            senders.insert(self.proposer(proposal.height, proposal.round));
+        }
+        senders.len()
+    }
+
+    // Kesinleştirilemeyen adayı bu yükseklik için geçersiz say (aynı karar tekrar denenmez)
+    fn mark_invalid(&mut self, hash: &str) {
+        self.checked.insert(hash.to_string(), false);
+    }
+
+    // Kesinleşen yükseklik için 2/3+ kesin oyu sakla ve bir sonraki yüksekliğe geç
+    fn finalize(&mut self, height: usize, hash: &str) {
+        let certificate = self
+            .votes
+            .iter()
+            .filter(|((vote_height, _, kind), _)| *vote_height == height && *kind == VoteKind::Precommit)
+            .flat_map(|(_, votes)| votes.iter())
+            .filter(|(_, vote)| vote.block_hash.as_deref() == Some(hash))
+            .map(|(_, vote)| vote.clone())
+            .collect();
+        self.commits.insert(height, certificate);
+
+        self.finalized_height = height;
+        self.height = height + 1;
+        self.round = 0;
+        self.step = BftStep::Propose;
+        self.started = false;
+        self.locked = None;
+        self.valid = None;
+        self.timeouts.clear();
+        self.polka.clear();
+        self.checked.clear();
+
+        // Eski yüksekliklerin öneri ve oyları artık gerekmez
+        let next = self.height;
+        self.proposals.retain(|(proposal_height, _), _| *proposal_height >= next);
+        self.votes.retain(|(vote_height, _, _), _| *vote_height >= next);
+    }
+}
+
+impl Node {
+    // BFT ile kesinleşmiş son yükseklik (kesinlik katmanı kapalıysa veya zincir boşsa None)
+    pub fn finalized_height(&self) -> Option<usize> {
+        if self.blockchain.is_empty() {
+            return None;
+        }
+        self.finality.as_ref().map(|finality| finality.finalized_height)
+    }
+
+    // Zincir, node'un kesinleşmiş bloğunu aynı yükseklikte içeriyor mu
+    pub(crate) fn contains_finalized(&self, chain: &[Block]) -> bool {
+        match self.finalized_height() {
+            Some(height) => chain.get(height).map(|block| block.hash == self.blockchain[height].hash).unwrap_or(false),
+            None => true,
+        }
+    }
+
+    // Aday bloğun kesinleştirilebilir olup olmadığı: blok bilinmiyorsa None,
+    // verilen yükseklikte değilse, kesinleşmiş zinciri uzatmıyorsa veya geçersizse Some(false)
+    fn finality_candidate(&self, state: &mut FinalityState, hash: &str, height: usize, difficulty: usize) -> Option<bool> {
+        if let Some(&valid) = state.checked.get(hash) {
+            return Some(valid);
+        }
+
+        let block = self.find_block(hash)?;
+        let valid = if block.index != height {
+            false
+        } else if self.blockchain.get(height).map(|active| active.hash == hash).unwrap_or(false) {
+            true
+        } else {
+            let branch = self.branch_to(hash);
+            self.contains_finalized(&branch) && self.validate_branch(&branch, difficulty).is_ok()
+        };
+
+        state.checked.insert(hash.to_string(), valid);
+        Some(valid)
+    }
+
+    // Tendermint kurallarından şu an uygulanabilir olan ilkini bul
+    pub(crate) fn next_finality_action(&mut self, difficulty: usize) -> Option<FinalityAction> {
+        let mut state = self.finality.take()?;
+        let action = self.evaluate_finality(&mut state, difficulty);
+        self.finality = Some(state);
+        action
+    }
+
+    fn evaluate_finality(&self, state: &mut FinalityState, difficulty: usize) -> Option<FinalityAction> {
+        let height = state.height;
+        let quorum = state.quorum();
+
+        // Karar: herhangi bir turda bir blok 2/3+ kesin oy aldıysa kesinleşir.
+        // Geride kalan node'lar daha yüksek bir yükseklikteki sertifikayla doğrudan ilerler.
+        let precommit_keys: Vec<(usize, u32)> = state
+            .votes
+            .keys()
+            .filter(|(vote_height, _, kind)| *vote_height >= height && *kind == VoteKind::Precommit)
+            .map(|(vote_height, round, _)| (*vote_height, *round))
+            .collect();
+        for (vote_height, round) in precommit_keys {
+            for value in state.quorum_values(vote_height, round, VoteKind::Precommit) {
+                if self.finality_candidate(state, &value, vote_height, difficulty) == Some(true) {
+                    return Some(FinalityAction::Decide(vote_height, value));
+                }
+            }
+        }
+
+        // Daha ileri bir turda f+1 validator görüldüyse o tura atla (en az biri dürüsttür)
+        let skip_to = state
+            .proposals
+            .keys()
+            .filter(|(proposal_height, _)| *proposal_height == height)
+            .map(|(_, round)| *round)
+            .chain(state.votes.keys().filter(|(vote_height, _, _)| *vote_height == height).map(|(_, round, _)| *round))
+            .filter(|&round| !state.started || round > state.round)
+            .filter(|&round| state.round_participants(height, round) > state.max_faulty())
+            .max();
+        if let Some(round) = skip_to {
+            return Some(FinalityAction::StartRound(round));
+        }
+
+        // Tur, node bu yükseklikte bir bloğa sahip olunca başlar
+        if !state.started {
+            return (self.blockchain.len() > height).then_some(FinalityAction::StartRound(0));
+        }
+
+        let round = state.round;
+        if state.step == BftStep::Propose {
+            if let Some(proposal) = state.proposals.get(&(height, round)).cloned() {
+                let value = proposal.block_hash.clone();
+                let unlocked = |locked_round: Option<u32>| match &state.locked {
+                    None => true,
+                    Some((locked_value, locked_at)) => *locked_value == value || locked_round.map(|vr| *locked_at <= vr).unwrap_or(false),
+                };
+
+                match proposal.valid_round {
+                    None => {
+                        let acceptable = unlocked(None);
+                        if let Some(valid) = self.finality_candidate(state, &value, height, difficulty) {
+                            return Some(FinalityAction::Prevote((valid && acceptable).then_some(value)));
+                        }
+                    }
+                    Some(valid_round) if valid_round < round && state.count(height, valid_round, VoteKind::Prevote, Some(&Some(value.clone()))) >= quorum => {
+                        let acceptable = unlocked(Some(valid_round));
+                        if let Some(valid) = self.finality_candidate(state, &value, height, difficulty) {
+                            return Some(FinalityAction::Prevote((valid && acceptable).then_some(value)));
+                        }
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        if state.step >= BftStep::Prevote && !state.polka.contains(&round) {
+            for value in state.quorum_values(height, round, VoteKind::Prevote) {
+                if self.finality_candidate(state, &value, height, difficulty) == Some(true) {
+                    return Some(FinalityAction::Polka(value));
+                }
+            }
+        }
+
+        if state.step == BftStep::Prevote {
+            if state.count(height, round, VoteKind::Prevote, Some(&None)) >= quorum {
+                return Some(FinalityAction::Precommit(None));
+            }
+            if state.count(height, round, VoteKind::Prevote, None) >= quorum && !state.timeouts.contains(&(round, BftStep::Prevote)) {
+                return Some(FinalityAction::ScheduleTimeout(BftStep::Prevote));
+            }
+        }
+
+        if state.count(height, round, VoteKind::Precommit, None) >= quorum && !state.timeouts.contains(&(round, BftStep::Precommit)) {
+            return Some(FinalityAction::ScheduleTimeout(BftStep::Precommit));
+        }
+
+        None
+    }
+
+    // Kesinleşen bloğa geç: aktif zincirde değilse bloğu içeren en uzun bilinen dala geçilir
+    pub(crate) fn finalize_block(&mut self, height: usize, hash: &str, difficulty: usize) -> Result<Vec<Block>, ValidationError> {
+        let mut reverted = Vec::new();
+        if !self.blockchain.get(height).map(|block| block.hash == hash).unwrap_or(false) {
+            let tip = self
+                .block_store
+                .values()
+                .filter(|block| block.index > height && self.branch_to(&block.hash).get(height).map(|ancestor| ancestor.hash == hash).unwrap_or(false))
+                .max_by(|a, b| a.index.cmp(&b.index).then(b.hash.cmp(&a.hash)))
+                .map(|block| block.hash.clone());
+
+            // Uzun dal geçersiz çıkarsa sadece kesinleşen bloğa kadar olan dala geçilir
+            reverted = match tip.map(|tip| self.branch_to(&tip)) {
+                Some(branch) => match self.switch_chain(branch, difficulty) {
+                    Ok(reverted) => reverted,
+                    Err(_) => self.switch_chain(self.branch_to(hash), difficulty)?,
+                },
+                None => self.switch_chain(self.branch_to(hash), difficulty)?,
+            };
+        }
+
+        if let Some(finality) = self.finality.as_mut() {
+            finality.finalize(height, hash);
+        }
+        Ok(reverted)
+    }
+}
+
+// Bir node'un kesinlik katmanındaki özeti
+#[derive(Debug, Clone)]
+pub struct FinalityStatus {
+    pub node_id: usize,
+    pub validator: bool,
+    pub finalized_height: usize,
+    pub height: usize,
+    pub round: u32,
+    pub step: BftStep,
+    pub fault: Option<BftFault>,
+}
+
+impl fmt::Display for FinalityStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Node {}: kesinleşmiş #{}, yükseklik {} tur {} ({})",
+            self.node_id, self.finalized_height, self.height, self.round, self.step
+        )?;
+        if !self.validator {
+            write!(f, ", gözlemci")?;
+        }
+        if let Some(fault) = self.fault {
+            write!(f, ", Bizans: {}", fault)?;
+        }
+        Ok(())
+    }
+}
+
+impl BlockchainNetwork {
+    // BFT kesinlik katmanını aç: verilen node'lar validator kümesini oluşturur, diğerleri sadece izler.
+    // Bir blok, validator'ların 2/3'ten fazlası kesin oy verince kesinleşir ve bir daha geri alınamaz.
+    // n = 3f + 1 validator ile en fazla f Bizans validator tolere edilir.
+    pub fn enable_finality(&mut self, validators: &[usize]) -> Result<(), NetworkError> {
+        if validators.is_empty() {
+            return Err(NetworkError::EmptyValidatorSet);
+        }
+        let mut addresses = Vec::new();
+        for &id in validators {
+            let node = self.nodes.get(id).ok_or(NetworkError::NodeNotFound(id))?;
+            if !addresses.iter().any(|address| address == node.get_address()) {
+                addresses.push(node.get_address().to_string());
+            }
+        }
+
+        self.finality_validators = validators.to_vec();
+        for node_id in 0..self.nodes.len() {
+            self.nodes[node_id].finality = Some(FinalityState::new(addresses.clone()));
+            self.drive_finality(node_id);
+        }
+        self.process_due_events();
+        Ok(())
+    }
+
+    // Validator kümesinin adresleri (kesinlik katmanı kapalıysa boş)
+    pub(crate) fn finality_addresses(&self) -> Vec<String> {
+        self.finality_validators.iter().map(|&id| self.nodes[id].get_address().to_string()).collect()
+    }
+
+    // Bir validator'a Bizans davranışı ata (None: dürüst)
+    pub fn set_bft_fault(&mut self, node_id: usize, fault: Option<BftFault>) -> Result<(), NetworkError> {
+        let node = self.nodes.get_mut(node_id).ok_or(NetworkError::NodeNotFound(node_id))?;
+        let finality = node.finality.as_mut().ok_or(NetworkError::FinalityNotEnabled)?;
+        finality.fault = fault;
+        Ok(())
+    }
+
+    // Node'ların kesinlik durumu
+    pub fn finality_status(&self) -> Vec<FinalityStatus> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(node_id, node)| {
+                let finality = node.finality.as_ref()?;
+                Some(FinalityStatus {
+                    node_id,
+                    validator: finality.is_validator(node.get_address()),
+                    finalized_height: finality.finalized_height,
+                    height: finality.height,
+                    round: finality.round,
+                    step: finality.step,
+                    fault: finality.fault,
+                })
+            })
+            .collect()
+    }
+
+    // Node'ların farklı bloklar kesinleştirdiği yükseklikler (güvenlik ihlali; boş olmalı)
+    pub fn finality_conflicts(&self) -> Vec<usize> {
+        let mut finalized: BTreeMap<usize, BTreeSet<&str>> = BTreeMap::new();
+        for node in &self.nodes {
+            if let Some(height) = node.finalized_height() {
+                // Genesis BFT ile kesinleşmez, sadece sonraki bloklar karşılaştırılır
+                for block in node.blockchain[..=height].iter().skip(1) {
+                    finalized.entry(block.index).or_default().insert(&block.hash);
+                }
+            }
+        }
+        finalized.into_iter().filter(|(_, hashes)| hashes.len() > 1).map(|(height, _)| height).collect()
+    }
+
+    // Tur için zaman aşımı süresi; her turda artar, böylece ağ gecikmesi sonunda aşılır
+    fn finality_timeout_for(&self, round: u32) -> SimTime {
+        self.finality_timeout + self.finality_timeout * round as SimTime / 2
+    }
+
+    // Node'un uygulanabilir kuralları bitene kadar adım at
+    pub(crate) fn drive_finality(&mut self, node_id: usize) {
+        let difficulty = self.difficulty;
+        loop {
+            let action = match self.nodes.get_mut(node_id).and_then(|node| node.next_finality_action(difficulty)) {
+                Some(action) => action,
+                None => return,
+            };
+            self.apply_finality_action(node_id, action);
+        }
+    }
+
+    fn apply_finality_action(&mut self, node_id: usize, action: FinalityAction) {
+        let now = self.now();
+        let difficulty = self.difficulty;
+        let node = &mut self.nodes[node_id];
+        let state = match node.finality.as_mut() {
+            Some(state) => state,
+            None => return,
+        };
+        let height = state.height;
+        let round = state.round;
+
+        match action {
+            FinalityAction::StartRound(round) => self.start_round(node_id, round),
+            FinalityAction::Prevote(value) => {
+                state.step = BftStep::Prevote;
+                self.cast_vote(node_id, VoteKind::Prevote, value);
+            }
+            FinalityAction::Precommit(value) => {
+                state.step = BftStep::Precommit;
+                self.cast_vote(node_id, VoteKind::Precommit, value);
+            }
+            FinalityAction::Polka(value) => {
+                state.polka.insert(round);
+                state.valid = Some((value.clone(), round));
+                if state.step == BftStep::Prevote {
+                    state.locked = Some((value.clone(), round));
+                    state.step = BftStep::Precommit;
+                    self.cast_vote(node_id, VoteKind::Precommit, Some(value));
+                }
+            }
+            FinalityAction::ScheduleTimeout(step) => {
+                state.timeouts.insert((round, step));
+                let delay = self.finality_timeout_for(round);
+                self.scheduler.schedule_in(delay, SimEvent::FinalityTimeout { node: node_id, height, round, step });
+            }
+            FinalityAction::Decide(height, hash) => {
+                match node.finalize_block(height, &hash, difficulty) {
+                    Ok(reverted) => {
+                        if !reverted.is_empty() {
+                            let new_tip = node.blockchain.last().map(|block| block.hash.clone()).unwrap_or_default();
+                            self.reorgs.push(ReorgRecord { node_id, time: now, reverted, new_tip });
+                        }
+                    }
+                    // Kesinleşen dal doğrulanamadıysa karar bu node için ertelenir
+                    Err(_) => {
+                        if let Some(state) = node.finality.as_mut() {
+                            state.mark_invalid(&hash);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Yeni bir tur başlat: önerici bloğunu önerir, herkes öneri için zaman aşımı kurar
+    fn start_round(&mut self, node_id: usize, round: u32) {
+        let delay = self.finality_timeout_for(round);
+        let node = &mut self.nodes[node_id];
+        let address = node.get_address().to_string();
+        let own_block = node.finality.as_ref().and_then(|state| node.blockchain.get(state.height)).map(|block| block.hash.clone());
+        let state = match node.finality.as_mut() {
+            Some(state) => state,
+            None => return,
+        };
+
+        state.round = round;
+        state.step = BftStep::Propose;
+        state.started = true;
+        state.timeouts.insert((round, BftStep::Propose));
+        let height = state.height;
+
+        // Önerici daha önce 2/3+ ön oy almış bir blok varsa onu, yoksa kendi zincirindeki bloğu önerir
+        let proposal = if state.proposer(height, round) == address && state.fault != Some(BftFault::Silent) {
+            match state.valid.clone() {
+                Some((value, valid_round)) => Some((value, Some(valid_round))),
+                None => own_block.map(|value| (value, None)),
+            }
+        } else {
+            None
+        };
+
+        self.scheduler.schedule_in(delay, SimEvent::FinalityTimeout { node: node_id, height, round, step: BftStep::Propose });
+
+        if let Some((value, valid_round)) = proposal {
+            let node = &mut self.nodes[node_id];
+            let proposal = Proposal::new(height, round, value, valid_round, &node.wallet);
+            if let Some(state) = node.finality.as_mut() {
+                state.record_proposal(proposal.clone());
+            }
+            self.relay_finality(node_id, proposal.id(), Message::Proposal(Box::new(proposal)));
+        }
+    }
+
+    // Oyu imzala, kendi durumuna kaydet ve komşulara gönder
+    // Bizans validator'lar sessiz kalabilir veya komşularının yarısına çelişkili oy gönderebilir
+    fn cast_vote(&mut self, node_id: usize, kind: VoteKind, value: Option<String>) {
+        let node = &mut self.nodes[node_id];
+        let address = node.get_address().to_string();
+        let state = match node.finality.as_mut() {
+            Some(state) => state,
+            None => return,
+        };
+        if !state.is_validator(&address) || state.fault == Some(BftFault::Silent) {
+            return;
+        }
+        let fault = state.fault;
+        let (height, round) = (state.height, state.round);
+
+        let vote = Vote::new(height, round, kind, value.clone(), &node.wallet);
+        if let Some(state) = node.finality.as_mut() {
+            state.record_vote(address, vote.clone());
+        }
+
+        // Çelişkili oy: blok için oy verildiyse nil, nil verildiyse bu yükseklikteki kendi bloğu için
+        let conflicting = match (fault, &value) {
+            (Some(BftFault::Equivocate), Some(_)) => Some(None),
+            (Some(BftFault::Equivocate), None) => node.blockchain.get(height).map(|block| Some(block.hash.clone())),
+            _ => None,
+        };
+
+        match conflicting {
+            Some(other) => {
+                let other = Vote::new(height, round, kind, other, &node.wallet);
+                let peers = node.connections.clone();
+                let half = peers.len().div_ceil(2);
+                for (position, &peer) in peers.iter().enumerate() {
+                    let vote = if position < half { &vote } else { &other };
+                    self.nodes[node_id].mark_known(peer, &vote.id());
+                    self.send_message(node_id, peer, Message::Vote(Box::new(vote.clone())));
+                }
+            }
+            None => self.relay_finality(node_id, vote.id(), Message::Vote(Box::new(vote))),
+        }
+    }
+
+    // Öneri veya oyu, henüz bilmeyen komşulara ilet
+    fn relay_finality(&mut self, origin: usize, id: String, message: Message) {
+        let peers = match self.nodes.get(origin) {
+            Some(node) => node.connections.clone(),
+            None => return,
+        };
+
+        for peer in peers {
+            if self.nodes[origin].peer_knows(peer, &id) {
+                continue;
+            }
+            if self.send_message(origin, peer, message.clone()) {
+                self.nodes[origin].mark_known(peer, &id);
+            }
+        }
+    }
+
+    // Geride kalan komşuya o yüksekliğin kesin oy sertifikasını gönder
+    fn send_commit(&mut self, node_id: usize, peer: usize, height: usize) {
+        let node = &mut self.nodes[node_id];
+        let certificate = match node.finality.as_ref().and_then(|state| state.commits.get(&height)) {
+            Some(certificate) => certificate.clone(),
+            None => return,
+        };
+
+        for vote in certificate {
+            let id = vote.id();
+            if self.nodes[node_id].peer_knows(peer, &id) {
+                continue;
+            }
+            if self.send_message(node_id, peer, Message::Vote(Box::new(vote))) {
+                self.nodes[node_id].mark_known(peer, &id);
+            }
+        }
+    }
+
+    // Komşudan gelen öneriyi işle
+    pub(crate) fn handle_proposal(&mut self, from: usize, to: usize, proposal: Proposal) {
+        let id = proposal.id();
+        let node = match self.nodes.get_mut(to) {
+            Some(node) => node,
+            None => return,
+        };
+        node.mark_known(from, &id);
+        let state = match node.finality.as_mut() {
+            Some(state) => state,
+            None => return,
+        };
+
+        // Sadece o turun önericisinin imzaladığı öneriler kabul edilir
+        if proposal.verify().as_deref() != Some(state.proposer(proposal.height, proposal.round)) {
+            return;
+        }
+        if proposal.height < state.height {
+            self.send_commit(to, from, proposal.height);
+            return;
+        }
+        if !state.record_proposal(proposal.clone()) {
+            return;
+        }
+
+        // Önerilen blok henüz gelmediyse öneriyi gönderen komşudan istenir
+        let hash = proposal.block_hash.clone();
+        if !node.knows_block(&hash) && !node.requested.contains_key(&hash) {
+            self.request_items(to, from, vec![InvItem::Block(hash)]);
+        }
+
+        self.relay_finality(to, id, Message::Proposal(Box::new(proposal)));
+        self.drive_finality(to);
+    }
+
+    // Komşudan gelen oyu işle
+    pub(crate) fn handle_vote(&mut self, from: usize, to: usize, vote: Vote) {
+        let id = vote.id();
+        let node = match self.nodes.get_mut(to) {
+            Some(node) => node,
+            None => return,
+        };
+        node.mark_known(from, &id);
+        let state = match node.finality.as_mut() {
+            Some(state) => state,
+            None => return,
+        };
+
+        // İmzası geçersiz veya validator kümesinde olmayan oylar yok sayılır
+        let signer = match vote.verify() {
+            Some(signer) if state.is_validator(&signer) => signer,
+            _ => return,
+        };
+        if vote.height < state.height {
+            self.send_commit(to, from, vote.height);
+            return;
+        }
+        if !state.record_vote(signer, vote.clone()) {
+            return;
+        }
+
+        self.relay_finality(to, id, Message::Vote(Box::new(vote)));
+        self.drive_finality(to);
+    }
+
+    // Zaman aşımı: önericiden öneri gelmediyse nil ön oy, ön oylar dağınıksa nil kesin oy,
+    // kesin oylar dağınıksa bir sonraki tur
+    pub(crate) fn handle_finality_timeout(&mut self, node_id: usize, height: usize, round: u32, step: BftStep) {
+        let state = match self.nodes.get(node_id).and_then(|node| node.finality.as_ref()) {
+            Some(state) => state,
+            None => return,
+        };
+        if state.height != height || state.round != round || !state.started {
+            return;
+        }
+
+        let action = match (step, state.step) {
+            (BftStep::Propose, BftStep::Propose) => FinalityAction::Prevote(None),
+            (BftStep::Prevote, BftStep::Prevote) => FinalityAction::Precommit(None),
+            (BftStep::Precommit, _) => FinalityAction::StartRound(round + 1),
+            _ => return,
+        };
+        self.apply_finality_action(node_id, action);
+        self.drive_finality(node_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::link::{LatencyModel, LinkConfig};
+    use crate::stake::ConsensusMode;
+    use crate::topology::Topology;
+
+    // Tam bağlı, kayıpsız 4 validator'lı ağ; tüm node'lar validator
+    fn network(seed: u64, consensus: ConsensusMode, faults: &[(usize, BftFault)]) -> BlockchainNetwork {
+        let mut network = BlockchainNetwork::with_seed(seed);
+        network.set_block_time(10);
+        network.default_link = LinkConfig { latency: LatencyModel::Uniform { min: 50, max: 300 }, loss_rate: 0.0, bandwidth: 0 };
+        for _ in 0..4 {
+            network.add_node();
+        }
+        network.apply_topology(&Topology::FullMesh).unwrap();
+        network.set_consensus(consensus);
+        if consensus == ConsensusMode::ProofOfWork {
+            network.select_random_validator().unwrap();
+        }
+        network.mine_block().unwrap();
+        network.enable_finality(&[0, 1, 2, 3]).unwrap();
+        for &(node_id, fault) in faults {
+            network.set_bft_fault(node_id, Some(fault)).unwrap();
+        }
+        network.start_automatic_mining().unwrap();
+        network
+    }
+
+    fn finalized_heights(network: &BlockchainNetwork) -> Vec<usize> {
+        network.finality_status().iter().map(|status| status.finalized_height).collect()
+    }
+
+    #[test]
+    fn quorum_is_more_than_two_thirds() {
+        let validators = |count: usize| FinalityState::new((0..count).map(|i| i.to_string()).collect());
+        assert_eq!((validators(4).quorum(), validators(4).max_faulty()), (3, 1));
+        assert_eq!((validators(6).quorum(), validators(6).max_faulty()), (5, 1));
+        assert_eq!((validators(7).quorum(), validators(7).max_faulty()), (5, 2));
+        assert_eq!((validators(1).quorum(), validators(1).max_faulty()), (1, 0));
+    }
+
+    #[test]
+    fn one_silent_validator_is_tolerated() {
+        let mut network = network(4, ConsensusMode::ProofOfStake, &[(2, BftFault::Silent)]);
+        network.run_blocks(20);
+
+        assert!(finalized_heights(&network).iter().all(|&height| height >= 10), "{:?}", finalized_heights(&network));
+        assert!(network.finality_conflicts().is_empty());
+    }
+
+    #[test]
+    fn one_equivocating_validator_cannot_break_safety() {
+        let mut network = network(5, ConsensusMode::ProofOfStake, &[(1, BftFault::Equivocate)]);
+        network.run_blocks(20);
+
+        assert!(network.finality_conflicts().is_empty());
+        assert!(finalized_heights(&network).iter().all(|&height| height >= 10), "{:?}", finalized_heights(&network));
+    }
+
+    #[test]
+    fn two_faulty_of_four_stall_finality() {
+        let mut network = network(6, ConsensusMode::ProofOfStake, &[(1, BftFault::Silent), (2, BftFault::Silent)]);
+        network.run_blocks(10);
+
+        // Bloklar üretilmeye devam eder ama 2/3+ kesin oy toplanamaz
+        assert!(network.nodes.iter().all(|node| node.blockchain.len() > 5));
+        assert!(finalized_heights(&network).iter().all(|&height| height == 0), "{:?}", finalized_heights(&network));
+        assert!(network.finality_conflicts().is_empty());
+    }
+
+    #[test]
+    fn longer_chain_below_finalized_height_is_rejected() {
+        let mut network = network(3, ConsensusMode::ProofOfWork, &[]);
+        network.run_blocks(10);
+        network.stop_automatic_mining().unwrap();
+        network.run_for(10_000);
+
+        let finalized = network.nodes[0].finalized_height().unwrap();
+        assert!(finalized >= 3);
+        let chain: Vec<String> = network.nodes[0].blockchain.iter().map(|block| block.hash.clone()).collect();
+        let reorgs = network.reorgs.len();
+
+        // Kesinleşmiş bloğun ebeveyninden çatallanan, aktif zincirden daha uzun bir dal
+        let difficulty = network.difficulty;
+        let mut fork = network.nodes[0].clone();
+        fork.finality = None;
+        fork.is_validator = true;
+        fork.mempool.clear();
+        let base = fork.branch_to(&chain[finalized - 1]);
+        fork.switch_chain(base, difficulty).unwrap();
+        let mut timestamp = network.timestamp();
+        let mut branch = Vec::new();
+        while fork.blockchain.len() <= chain.len() {
+            timestamp += 1;
+            branch.push(fork.mine_block_at(difficulty, timestamp).unwrap());
+        }
+
+        let node = &mut network.nodes[0];
+        assert_eq!(
+            node.receive_block(branch[0].clone(), difficulty).unwrap_err(),
+            ValidationError::ConflictsWithFinalized { index: finalized, finalized }
+        );
+        for block in branch.into_iter().skip(1) {
+            let _ = node.receive_block(block, difficulty);
+        }
+        assert_eq!(node.blockchain.iter().map(|block| block.hash.clone()).collect::<Vec<_>>(), chain);
+        assert_eq!(node.finalized_height(), Some(finalized));
+        assert_eq!(network.reorgs.len(), reorgs);
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crate::byzantine::Misbehavior;
+use crate::message::{InvItem, Message};
+use crate::network::{BlockchainNetwork, SimEvent};
+use crate::sim::SimTime;
+
+// Doğrulanan blok ve işlemlerin komşulara nasıl iletileceği
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelayMode {
+    // Tam içerik doğrudan komşulara gönderilir
+    Flood,
+    // Önce sadece hash duyurulur (inv), komşu bilmiyorsa içeriği ister (getdata)
+    Announce,
+}
+
+impl FromStr for RelayMode {
+    type Err = String;
+
+    // "flood" veya "announce"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "flood" => Ok(RelayMode::Flood),
+            "announce" | "inv" => Ok(RelayMode::Announce),
+            other => Err(format!("bilinmeyen yayılma şekli: {} (flood veya announce)", other)),
+        }
+    }
+}
+
+// Bir node'un gönderdiği ve aldığı trafik
+#[derive(Debug, Clone, Default)]
+pub struct TrafficStats {
+    pub messages_sent: u64,
+    pub bytes_sent: u64,
+    pub messages_received: u64,
+    pub bytes_received: u64,
+    pub bytes_sent_by_kind: BTreeMap<&'static str, u64>, // Mesaj türü -> gönderilen byte
+}
+
+impl TrafficStats {
+    // Gönderilen mesajı kaydet (yolda kaybolsa bile bant genişliği harcanmıştır)
+    pub fn record_sent(&mut self, message: &Message) {
+        let size = message.size() as u64;
+        self.messages_sent += 1;
+        self.bytes_sent += size;
+        *self.bytes_sent_by_kind.entry(message.kind()).or_insert(0) += size;
+    }
+
+    // Ulaşan mesajı kaydet
+    pub fn record_received(&mut self, message: &Message) {
+        self.messages_received += 1;
+        self.bytes_received += message.size() as u64;
+    }
+}
+
+impl BlockchainNetwork {
+    // Doğrulanmış bir bloğu veya işlemi, henüz bilmeyen komşulara ilet
+    // Announce modunda sadece hash duyurulur, Flood modunda içerik doğrudan gönderilir
+    pub(crate) fn relay(&mut self, origin: usize, message: Message) {
+        let item = match &message {
+            Message::Block(block) => InvItem::Block(block.hash.clone()),
+            Message::Transaction(tx) => InvItem::Transaction(tx.id.clone()),
+            _ => return,
+        };
+
+        let peers = match self.nodes.get(origin) {
+            Some(node) => node.connections.clone(),
+            None => return,
+        };
+
+        // Kötü niyetli node mesajı düşürebilir veya bozabilir; bozuk bloklar duyurulmadan doğrudan gönderilir
+        let message = match self.byzantine_outgoing(origin, message) {
+            Some(message) => message,
+            None => return,
+        };
+        let corrupted = matches!(message, Message::Block(_)) && self.misbehaves(origin, |b| *b == Misbehavior::RelayInvalidBlocks);
+
+        for peer in peers {
+            // Komşu bu öğeyi zaten biliyorsa (ona gönderdik ya da o bize gönderdi) tekrar gönderme
+            if self.nodes[origin].peer_knows(peer, item.id()) {
+                continue;
+            }
+
+            let outgoing = match self.relay_mode {
+                RelayMode::Announce if !corrupted => Message::Inv(vec![item.clone()]),
+                _ => message.clone(),
+            };
+            if corrupted {
+                self.record_byzantine(origin, |effects| effects.invalid_blocks_sent += 1);
+            }
+            // Öğe ancak mesaj gerçekten yola çıktıysa bilinir sayılır; düşen duyuru sonraki yaymada tekrar gönderilir
+            if self.send_message(origin, peer, outgoing) {
+                self.nodes[origin].mark_known(peer, item.id());
+                // Yalancı node sahte duyuruyu sadece gerçekten gönderilen bloğun ardından yapar
+                if let InvItem::Block(_) = item {
+                    self.announce_fake_block(origin, peer);
+                }
+            }
+        }
+    }
+
+    // Komşudan gelen duyuruyu işle: bilinmeyen ve henüz istenmemiş öğeleri iste
+    pub(crate) fn handle_inv(&mut self, from: usize, to: usize, items: Vec<InvItem>) {
+        let node = match self.nodes.get_mut(to) {
+            Some(node) => node,
+            None => return,
+        };
+
+        let mut wanted = Vec::new();
+        for item in items {
+            node.mark_known(from, item.id());
+            if !node.has_item(&item) && !node.requested.contains_key(item.id()) {
+                wanted.push(item);
+            }
+        }
+
+        self.request_items(to, from, wanted);
+    }
+
+    // Komşunun istediği öğelerden elimizde olanları gönder
+    pub(crate) fn handle_getdata(&mut self, from: usize, to: usize, items: Vec<InvItem>) {
+        let node = match self.nodes.get(to) {
+            Some(node) => node,
+            None => return,
+        };
+
+        let mut replies = Vec::new();
+        for item in items {
+            match item {
+                InvItem::Block(hash) => {
+                    let block = node
+                        .find_block(&hash)
+                        .or_else(|| node.orphan_blocks.iter().find(|orphan| orphan.hash == hash));
+                    if let Some(block) = block {
+                        replies.push(Message::Block(block.clone()));
+                    }
+                }
+                InvItem::Transaction(id) => {
+                    if let Some(tx) = node.mempool.iter().find(|tx| tx.id == id) {
+                        replies.push(Message::Transaction(tx.clone()));
+                    }
+                }
+            }
+        }
+
+        for reply in replies {
+            // Kötü niyetli node istenen öğeyi de saklayabilir veya bozabilir
+            let corrupted = matches!(reply, Message::Block(_)) && self.misbehaves(to, |b| *b == Misbehavior::RelayInvalidBlocks);
+            if let Some(reply) = self.byzantine_outgoing(to, reply) {
+                if corrupted {
+                    self.record_byzantine(to, |effects| effects.invalid_blocks_sent += 1);
+                }
+                self.send_message(to, from, reply);
+            }
+        }
+    }
+
+    // Öğeleri bir komşudan iste ve cevap gelmezse zaman aşımı planla
+    pub(crate) fn request_items(&mut self, node_id: usize, peer: usize, items: Vec<InvItem>) {
+        if items.is_empty() {
+            return;
+        }
+
+        let now = self.now();
+        let node = match self.nodes.get_mut(node_id) {
+            Some(node) => node,
+            None => return,
+        };
+        for item in &items {
+            node.requested.insert(item.id().to_string(), (peer, now));
+            // Zaman aşımında istek bu öğeyi bildiği düşünülen başka bir komşuya yönlendirilir
+            self.scheduler.schedule_in(self.request_timeout, SimEvent::RequestTimeout {
+                node: node_id,
+                item: item.clone(),
+            });
+        }
+
+        self.send_message(node_id, peer, Message::GetData(items));
+    }
+
+    // Cevapsız kalan isteği, öğeyi duyurmuş başka bir komşudan tekrar iste
+    pub(crate) fn handle_request_timeout(&mut self, node_id: usize, item: InvItem) {
+        let now = self.now();
+        let timeout = self.request_timeout;
+        let node = match self.nodes.get_mut(node_id) {
+            Some(node) => node,
+            None => return,
+        };
+
+        // İstek bu arada karşılandıysa ya da yenilendiyse bir şey yapma
+        let peer = match node.requested.get(item.id()) {
+            Some(&(peer, requested_at)) if requested_at + timeout <= now => peer,
+            _ => return,
+        };
+        node.requested.remove(item.id());
+        if node.has_item(&item) {
+            return;
+        }
+        self.record_byzantine(peer, |effects| effects.unanswered_requests += 1);
+        let node = &self.nodes[node_id];
+
+        // Komşular cevap vermeyenden sonraki sırayla denenir; böylece cevap vermeyen iki komşu arasında gidip gelinmez
+        let start = node.connections.iter().position(|&candidate| candidate == peer).map(|position| position + 1).unwrap_or(0);
+        let alternative = node
+            .connections
+            .iter()
+            .cycle()
+            .skip(start)
+            .take(node.connections.len())
+            .copied()
+            .find(|&candidate| candidate != peer && node.peer_knows(candidate, item.id()));
+
+        // Başka kimse bu öğeyi duyurmadıysa vazgeç; sonraki duyurular veya yetim bloklar isteği yeniden başlatır
+        if let Some(alternative) = alternative {
+            self.request_items(node_id, alternative, vec![item]);
+        } else {
+            // Senkronize olan node'da indirme yuvası boşaldı, eksik gövdeler yeniden paylaştırılır
+            self.advance_sync(node_id);
+        }
+    }
+
+    // Bir node'un trafik istatistikleri
+    pub fn traffic(&self, node_id: usize) -> Option<&TrafficStats> {
+        self.traffic.get(node_id)
+    }
+
+    // Her node'un simülasyon boyunca ortalama giden bant genişliği kullanımı (byte/s)
+    pub fn bandwidth_per_node(&self) -> Vec<f64> {
+        let elapsed: SimTime = self.now().max(1);
+        self.traffic
+            .iter()
+            .map(|stats| stats.bytes_sent as f64 * 1000.0 / elapsed as f64)
+            .collect()
+    }
+}
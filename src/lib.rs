@@ -0,0 +1,67 @@
+// Blockchain simülasyonu kütüphanesi
+// Blok, işlem, cüzdan, node ve ağ yapıları buradan dışa açılır;
+// etkileşimli komut arayüzü (src/main.rs) bu kütüphanenin üzerinde ince bir katmandır.
+
+pub mod ban;
+pub mod block;
+pub mod byzantine;
+pub mod churn;
+pub mod consensus;
+pub mod distribution;
+pub mod double_spend;
+pub mod eclipse;
+pub mod error;
+pub mod events;
+pub mod finality;
+pub mod gossip;
+pub mod link;
+pub mod message;
+pub mod metrics;
+pub mod mining;
+pub mod network;
+pub mod node;
+pub mod partition;
+pub mod scenario;
+pub mod selfish;
+pub mod propagation;
+pub mod sim;
+pub mod slashing;
+pub mod stake;
+pub mod sync;
+pub mod tcp;
+pub mod topology;
+pub mod transaction;
+pub mod wallet;
+pub mod wire;
+pub mod workload;
+
+pub use ban::{BanRecord, Offense};
+pub use block::{Block, BlockHeader};
+pub use byzantine::{ByzantineEffects, ByzantineNode, ByzantineReport, Misbehavior};
+pub use churn::{ChurnEvent, ChurnModel, ChurnRecord, ChurnReport, SessionLength};
+pub use consensus::{ConsensusEngine, ProofOfStakeEngine, ProofOfWorkEngine};
+pub use double_spend::{DoubleSpendAttack, DoubleSpendReport, DoubleSpendStatus};
+pub use eclipse::{EclipseAttack, EclipseDefense, EclipseReport};
+pub use error::{EvidenceError, NetworkError, ScenarioError, TransactionError, TopologyError, ValidationError, WalletError, WireError};
+pub use events::{Event, EventBus, EventRecord, EventSink, JsonlSink, StdoutSink};
+pub use finality::{BftFault, BftStep, FinalityState, FinalityStatus, Proposal, Vote, VoteKind};
+pub use gossip::{RelayMode, TrafficStats};
+pub use link::{LatencyModel, Link, LinkConfig};
+pub use metrics::{BlockMetrics, MetricRow, MetricValue, MetricsExport, MetricsFormat, MetricsRecorder, NodeMetrics};
+pub use message::{InvItem, Message};
+pub use mining::{MinerShare, MiningModel};
+pub use network::{BlockchainNetwork, MinedBlock, ReorgRecord, SimEvent};
+pub use node::{BlockStatus, Node, BLOCK_TRANSACTION_LIMIT};
+pub use partition::PartitionReport;
+pub use scenario::{Metric, NetworkSpec, Scenario, ScenarioAction, ScenarioResult, TimedAction};
+pub use selfish::{SelfishMiner, SelfishMiningReport};
+pub use propagation::{BlockPropagation, PropagationTracker};
+pub use sim::{Scheduler, SimTime};
+pub use slashing::{EquivocationEvidence, SlashRecord};
+pub use stake::ConsensusMode;
+pub use sync::{SyncProgress, SyncState};
+pub use tcp::{TcpNode, TcpNodeConfig};
+pub use topology::{GraphStats, Topology};
+pub use transaction::{Transaction, TxInput, TxOutput, UTXO};
+pub use wallet::Wallet;
+pub use workload::{Workload, WorkloadGenerator, WorkloadReport, WorkloadTransaction};
@@ -0,0 +1,132 @@
+use rand::Rng;
+use std::str::FromStr;
+
+use crate::distribution;
+use crate::sim::SimTime;
+
+// Bir bağlantının gecikme (latency) dağılımı, milisaniye cinsinden
+#[derive(Debug, Clone, PartialEq)]
+pub enum LatencyModel {
+    // Her mesaj aynı sürede ulaşır
+    Constant(SimTime),
+    // [min, max] aralığında düzgün dağılım
+    Uniform { min: SimTime, max: SimTime },
+    // Normal dağılım (negatif örnekler 0'a çekilir)
+    Normal { mean: f64, std_dev: f64 },
+    // Üstel dağılım
+    Exponential { mean: f64 },
+}
+
+impl LatencyModel {
+    // Bir gecikme örneği çek
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> SimTime {
+        match self {
+            LatencyModel::Constant(latency) => *latency,
+            LatencyModel::Uniform { min, max } => {
+                distribution::uniform(rng, *min as f64, *max as f64).round() as SimTime
+            }
+            LatencyModel::Normal { mean, std_dev } => {
+                distribution::normal(rng, *mean, *std_dev).max(0.0).round() as SimTime
+            }
+            LatencyModel::Exponential { mean } => {
+                distribution::exponential(rng, *mean).round() as SimTime
+            }
+        }
+    }
+}
+
+impl FromStr for LatencyModel {
+    type Err = String;
+
+    // "const:ms", "uniform:min:max", "normal:ortalama:sapma" veya "exp:ortalama"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(':').collect();
+        let number = |index: usize| -> Result<f64, String> {
+            let value = parts.get(index).ok_or_else(|| format!("eksik parametre: {}", s))?;
+            match value.parse::<f64>() {
+                Ok(number) if number.is_finite() && number >= 0.0 => Ok(number),
+                _ => Err(format!("geçersiz gecikme: {}", value)),
+            }
+        };
+
+        match parts[0] {
+            "const" => Ok(LatencyModel::Constant(number(1)? as SimTime)),
+            "uniform" => Ok(LatencyModel::Uniform { min: number(1)? as SimTime, max: number(2)? as SimTime }),
+            "normal" => Ok(LatencyModel::Normal { mean: number(1)?, std_dev: number(2)? }),
+            "exp" => Ok(LatencyModel::Exponential { mean: number(1)? }),
+            other => Err(format!("bilinmeyen gecikme modeli: {} (const:ms, uniform:min:max, normal:ortalama:sapma, exp:ortalama)", other)),
+        }
+    }
+}
+
+// Bağlantı ayarları
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkConfig {
+    pub latency: LatencyModel,
+    pub loss_rate: f64, // Paket kaybı olasılığı (0.0 - 1.0)
+    pub bandwidth: u64, // Saniyede byte; 0 sınırsız bant genişliği demektir
+}
+
+impl Default for LinkConfig {
+    // Varsayılan bağlantı gecikmesiz, kayıpsız ve sınırsızdır
+    fn default() -> Self {
+        LinkConfig {
+            latency: LatencyModel::Constant(0),
+            loss_rate: 0.0,
+            bandwidth: 0,
+        }
+    }
+}
+
+// İki node arasındaki tek yönlü bağlantı
+#[derive(Debug, Clone)]
+pub struct Link {
+    pub config: LinkConfig,
+    pub up: bool,               // Bağlantı açık mı (ağ bölünmesinde kesilen bağlantılar false)
+    pub busy_until: SimTime,    // Bağlantının önceki mesajları göndermeyi bitireceği zaman
+    pub messages_sent: u64,
+    pub messages_dropped: u64,
+    pub bytes_sent: u64,
+}
+
+impl Link {
+    pub fn new(config: LinkConfig) -> Self {
+        Link {
+            config,
+            up: true,
+            busy_until: 0,
+            messages_sent: 0,
+            messages_dropped: 0,
+            bytes_sent: 0,
+        }
+    }
+
+    // Mesajı bağlantıya ver ve karşı tarafa ulaşacağı zamanı döndür
+    // Mesaj kaybolursa None döner
+    pub fn transmit<R: Rng + ?Sized>(&mut self, now: SimTime, size: usize, rng: &mut R) -> Option<SimTime> {
+        self.messages_sent += 1;
+
+        // Kesik bağlantıdan mesaj geçmez
+        if !self.up {
+            self.messages_dropped += 1;
+            return None;
+        }
+
+        if self.config.loss_rate > 0.0 && rng.random::<f64>() < self.config.loss_rate {
+            self.messages_dropped += 1;
+            return None;
+        }
+
+        // Bant genişliği: mesajlar bağlantıdan sırayla geçer, büyük mesajlar daha uzun sürer
+        let start = now.max(self.busy_until);
+        let transmission_time = if self.config.bandwidth == 0 {
+            0
+        } else {
+            (size as u64 * 1000).div_ceil(self.config.bandwidth)
+        };
+        self.busy_until = start + transmission_time;
+        self.bytes_sent += size as u64;
+
+        Some(self.busy_until + self.config.latency.sample(rng))
+    }
+}
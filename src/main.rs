@@ -1,91 +1,214 @@
-use std::time::{SystemTime, UNIX_EPOCH};
 use std::thread;
 use std::time::Duration;
 use std::io::{self, Write};
-use std::process::Command;
 use std::sync::{Arc, Mutex};
-use std::sync::mpsc;
 
-mod node;
-mod network;
-mod block;
-mod wallet;
-mod transaction;
+use blockchain_sim::{BlockchainNetwork, ConsensusMode, Event, JsonlSink, MiningModel, Scenario, StdoutSink, TcpNode, TcpNodeConfig, Topology};
 
-use network::BlockchainNetwork;
-use block::Block;
-
-// Blok oluşturulduğunda gönderilecek mesaj için kanal
-struct BlockchainMessage {
-    block: Block,
-    validator_id: usize,
-    next_validator_id: usize,
+// Komut satırındaki `--ad <değer>` argümanının değerini oku ve çözümle
+fn parse_flag<T>(args: &[String], name: &str) -> Option<T>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    let position = args.iter().position(|arg| arg == name)?;
+    
+    match args.get(position + 1).map(|value| value.parse::<T>()) {
+        Some(Ok(value)) => Some(value),
+        Some(Err(e)) => {
+            println!("Hata: {} için geçersiz değer: {}", name, e);
+            std::process::exit(1);
+        }
+        None => {
+            println!("Hata: {} için değer belirtilmedi", name);
+            std::process::exit(1);
+        }
+    }
 }
 
-fn main() {
-    // Blockchain ağını oluştur
-    let network = Arc::new(Mutex::new(BlockchainNetwork::new()));
+// Tek bir node'u kendi sürecinde, gerçek TCP bağlantılarıyla çalıştır
+// Kullanım: node --id <n> --port <port> [--peer 127.0.0.1:<port> ...] [--mine <saniye>] [--genesis] [--seed <n>] [--difficulty <n>]
+fn run_tcp_node(args: &[String]) {
+    let mut config = TcpNodeConfig {
+        node_id: parse_flag(args, "--id").unwrap_or(0),
+        port: parse_flag(args, "--port").unwrap_or(0),
+        difficulty: parse_flag(args, "--difficulty").unwrap_or(2),
+        mine_interval: parse_flag::<u64>(args, "--mine").map(Duration::from_secs),
+        create_genesis: args.iter().any(|arg| arg == "--genesis"),
+        seed: parse_flag(args, "--seed"),
+        verbose: true,
+        ..TcpNodeConfig::default()
+    };
     
-    // Blok mesajları için kanal oluştur
-    let (block_sender, block_receiver) = mpsc::channel::<BlockchainMessage>();
+    // Birden fazla --peer verilebilir
+    for (position, arg) in args.iter().enumerate() {
+        if arg == "--peer" {
+            match args.get(position + 1).map(|value| value.parse()) {
+                Some(Ok(addr)) => config.peers.push(addr),
+                _ => {
+                    println!("Hata: Geçersiz komşu adresi. Kullanım: --peer 127.0.0.1:<port>");
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
     
-    // Yeni bir terminal aç ve blok bilgilerini göster
-    let block_display_thread = thread::spawn(move || {
-        println!("Blok görüntüleme terminali başlatılıyor...");
-        
-        // Yeni bir dosya oluştur ve blok bilgilerini oraya yaz
-        let log_file_path = "/tmp/blockchain_blocks.log";
-        let _ = std::fs::write(log_file_path, "Blockchain Blok Görüntüleyici - Yeni bloklar burada görüntülenecek...\n");
+    let node = match TcpNode::spawn(config) {
+        Ok(node) => node,
+        Err(e) => {
+            println!("Node başlatılamadı: {}", e);
+            std::process::exit(1);
+        }
+    };
+    println!("Node {} {} adresinde dinliyor", node.node_id, node.local_addr);
+    println!("Komutlar: durum, eşler, bakiye, transfer <komşu_id> <miktar>, blok, çıkış");
+    
+    loop {
+        print!("Node {}> ", node.node_id);
+        io::stdout().flush().unwrap();
         
-        // Yeni bir terminal aç ve log dosyasını göster
-        #[cfg(target_os = "linux")]
-        let _ = Command::new("x-terminal-emulator")
-            .arg("-e")
-            .arg("bash")
-            .arg("-c")
-            .arg(format!("tail -f {} || read", log_file_path))
-            .spawn();
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input).unwrap_or(0) == 0 {
+            break;
+        }
+        let parts: Vec<&str> = input.split_whitespace().collect();
         
-        // Blok mesajlarını al ve dosyaya yaz
-        while let Ok(message) = block_receiver.recv() {
-            // Ana terminalde kısa bir bilgi göster
-            println!("Yeni blok oluşturuldu: {} (Node {})", message.block.hash, message.validator_id);
-            
-            // Detaylı bilgileri log dosyasına yaz
-            let log_entry = format!(
-                "\n=== YENİ BLOK OLUŞTURULDU ===\n\
-                Blok Hash: {}\n\
-                Blok İndeksi: {}\n\
-                Blok Zaman Damgası: {}\n\
-                Blok İşlem Sayısı: {}\n\
-                Oluşturan Madenci: Node {}\n\
-                Yeni Madenci: Node {}\n\
-                ==============================\n",
-                message.block.hash,
-                message.block.index,
-                message.block.timestamp,
-                message.block.transactions.len(),
-                message.validator_id,
-                message.next_validator_id
-            );
-            
-            // Log dosyasına ekle
-            if let Err(e) = std::fs::OpenOptions::new()
-                .append(true)
-                .open(log_file_path)
-                .and_then(|mut file| std::io::Write::write_all(&mut file, log_entry.as_bytes()))
-            {
-                println!("Log dosyasına yazılamadı: {}", e);
+        match parts.first().copied() {
+            None => continue,
+            Some("durum") => node.with_node(|n| {
+                let tip = n.blockchain.last().map(|block| block.hash.as_str()).unwrap_or("-");
+                println!("Zincir uzunluğu: {}, uç: {}, mempool: {} işlem", n.blockchain.len(), tip, n.mempool.len());
+            }),
+            Some("eşler") => {
+                for peer_id in node.peer_ids() {
+                    println!("Node {}: {}", peer_id, node.peer_address(peer_id).unwrap_or_default());
+                }
             }
-            
-            // Masaüstü bildirimi gönder
-            #[cfg(target_os = "linux")]
-            let _ = Command::new("notify-send")
-                .arg("Yeni Blok Oluşturuldu")
-                .arg(format!("Hash: {}\nValidator: Node {}", message.block.hash, message.validator_id))
-                .spawn();
+            Some("bakiye") => {
+                println!("Bakiye: {} coin", node.with_node(|n| n.get_balance()) as f64 / 100_000_000.0);
+            }
+            Some("transfer") => {
+                let (peer_id, amount) = match (parts.get(1).map(|p| p.parse::<usize>()), parts.get(2).map(|p| p.parse::<f64>())) {
+                    (Some(Ok(peer_id)), Some(Ok(amount))) => (peer_id, amount),
+                    _ => {
+                        println!("Kullanım: transfer <komşu_id> <miktar>");
+                        continue;
+                    }
+                };
+                let recipient = match node.peer_address(peer_id) {
+                    Some(address) => address,
+                    None => {
+                        println!("Hata: Node {} bağlı bir komşu değil", peer_id);
+                        continue;
+                    }
+                };
+                match node.create_transaction(&recipient, (amount * 100_000_000.0) as u64) {
+                    Ok(tx) => println!("İşlem oluşturuldu ve duyuruldu: {}", tx.id),
+                    Err(e) => println!("İşlem oluşturulamadı: {}", e),
+                }
+            }
+            Some("blok") => match node.mine_block() {
+                Ok(block) => println!("Blok #{} üretildi", block.index),
+                Err(e) => println!("Blok üretilemedi: {}", e),
+            },
+            Some("çıkış") | Some("exit") | Some("quit") => break,
+            Some(command) => println!("Bilinmeyen komut: {}", command),
         }
-    });
+    }
+    
+    node.shutdown();
+}
+
+// Senaryo dosyasını arayüz olmadan çalıştır ve sonuçları yaz
+// Kullanım: run <senaryo.toml> [--output <dosya>]
+fn run_scenario(args: &[String]) {
+    let path = match args.first() {
+        Some(path) if !path.starts_with("--") => path,
+        _ => {
+            println!("Kullanım: run <senaryo.toml> [--output <dosya>]");
+            std::process::exit(1);
+        }
+    };
+    let scenario = match Scenario::from_file(path) {
+        Ok(scenario) => scenario,
+        Err(e) => {
+            println!("Hata: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let result = match scenario.run() {
+        Ok(result) => result,
+        Err(e) => {
+            println!("Hata: {}", e);
+            std::process::exit(1);
+        }
+    };
+    
+    // Çıktı dosyası önceliği: --output, senaryodaki output, standart çıktı
+    match parse_flag::<String>(args, "--output").or(scenario.output.clone()) {
+        Some(output) => match result.write(&output) {
+            Ok(()) => println!("Sonuçlar {} dosyasına yazıldı (seed {})", output, result.seed),
+            Err(e) => {
+                println!("Hata: {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => print!("{}", result),
+    }
+}
+
+fn main() {
+    // `node` alt komutu: simülasyon yerine tek bir node'u TCP üzerinden çalıştır
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(|arg| arg == "node").unwrap_or(false) {
+        run_tcp_node(&args[2..]);
+        return;
+    }
+    // `run` alt komutu: senaryo dosyasını arayüz olmadan çalıştır
+    if args.get(1).map(|arg| arg == "run").unwrap_or(false) {
+        run_scenario(&args[2..]);
+        return;
+    }
+    
+    // Blockchain ağını oluştur
+    // --seed verilirse tüm rastgelelik (anahtarlar, validator seçimleri, coinbase nonce'ları) bu seed'den türetilir
+    let network = match parse_flag::<u64>(&args, "--seed") {
+        Some(seed) => BlockchainNetwork::with_seed(seed),
+        None => BlockchainNetwork::new(),
+    };
+    println!("Simülasyon seed'i: {} (aynı çalıştırmayı tekrarlamak için --seed {})", network.seed, network.seed);
+    let network = Arc::new(Mutex::new(network));
+    
+    // Olay aboneleri: --events ile tüm olaylar JSON satırları olarak dosyaya yazılır,
+    // --events-stdout ile ekrana yazılacak olay türleri seçilir (varsayılan üretilen bloklar, zincir değişimleri, yasaklar, node giriş çıkışları ve senkronizasyon ilerlemesi)
+    {
+        let mut network_lock = network.lock().unwrap();
+        if let Some(path) = parse_flag::<String>(&args, "--events") {
+            match JsonlSink::create(&path) {
+                Ok(sink) => {
+                    network_lock.events.subscribe(sink);
+                    println!("Olaylar {} dosyasına yazılıyor.", path);
+                }
+                Err(e) => {
+                    println!("Hata: Olay dosyası açılamadı: {}: {}", path, e);
+                    return;
+                }
+            }
+        }
+        match parse_flag::<String>(&args, "--events-stdout").as_deref() {
+            Some("none") => {}
+            Some("all") => network_lock.events.subscribe(StdoutSink::new()),
+            Some(kinds) => {
+                let kinds: Vec<&str> = kinds.split(',').map(str::trim).collect();
+                if let Some(unknown) = kinds.iter().find(|kind| !Event::kinds().contains(kind)) {
+                    println!("Hata: Bilinmeyen olay türü: {} ({}, all, none)", unknown, Event::kinds().join(", "));
+                    return;
+                }
+                network_lock.events.subscribe(StdoutSink::only(&kinds));
+            }
+            None => network_lock.events.subscribe(StdoutSink::only(&["block_mined", "reorg", "peer_banned", "churn", "sync_progress"])),
+        }
+    }
     
     // Blockchain ağını başlat
     {
@@ -99,31 +222,78 @@ fn main() {
         
         println!("Blockchain simülasyonu başlatılıyor...");
 
-        // 5 tane node oluştur
+        // Node'ları oluştur (--nodes ile değiştirilebilir, varsayılan 5)
         println!("\n--- NODE'LAR OLUŞTURULUYOR ---");
-        for _i in 0..5 {
+        for _i in 0..parse_flag::<usize>(&args, "--nodes").unwrap_or(5) {
             let node_id = network_lock.add_node();
             println!("Node {} oluşturuldu", node_id);
         }
+        
+        // Uzlaşma modu (--consensus pow|pos, varsayılan PoW)
+        // PoS'ta genesis bloğu her node için stake kilitler ve her slotta stake ağırlıklı bir önerici seçilir
+        network_lock.set_consensus(parse_flag::<ConsensusMode>(&args, "--consensus").unwrap_or(ConsensusMode::ProofOfWork));
+        
+        // Madencilik modeli (--mining random|race, varsayılan hash yarışı)
+        // --hashrate ile node'ların hash güçleri virgülle ayrılmış olarak verilebilir (örn. 1,1,2,4,8)
+        network_lock.mining_model = parse_flag::<MiningModel>(&args, "--mining").unwrap_or(MiningModel::HashRace);
+        if let Some(rates) = parse_flag::<String>(&args, "--hashrate") {
+            for (node_id, rate) in rates.split(',').enumerate() {
+                let result = rate
+                    .trim()
+                    .parse::<f64>()
+                    .map_err(|e| e.to_string())
+                    .and_then(|rate| network_lock.set_hashrate(node_id, rate).map_err(|e| e.to_string()));
+                if let Err(e) = result {
+                    println!("Hata: Node {} için hash gücü ayarlanamadı: {}", node_id, e);
+                    return;
+                }
+            }
+        }
 
-        // Node'ları birbirine bağla (tam bağlı ağ)
+        // Node'ları topolojiye göre birbirine bağla (--topology ile değiştirilebilir, varsayılan tam bağlı ağ)
         println!("\n--- NODE'LAR ARASI BAĞLANTILAR KURULUYOR ---");
-        for i in 0..network_lock.node_count() {
-            for j in (i + 1)..network_lock.node_count() {
-                network_lock.connect_nodes(i, j);
+        let topology = parse_flag::<Topology>(&args, "--topology").unwrap_or(Topology::FullMesh);
+        match network_lock.apply_topology(&topology) {
+            Ok(edges) => println!("{:?} topolojisine göre {} bağlantı kuruldu.", topology, edges),
+            Err(e) => {
+                println!("Bağlantılar kurulamadı: {}", e);
+                return;
+            }
+        }
+        
+        let stats = network_lock.graph_stats();
+        match stats.diameter {
+            Some(diameter) => println!("Ortalama derece: {:.2}, en yüksek derece: {}, çap: {}", stats.average_degree, stats.max_degree, diameter),
+            None => println!("Uyarı: Ağ bağlı değil, bazı node'lar blokları hiç almayacak."),
+        }
+
+        // BFT kesinlik katmanı (--finality): tüm node'lar validator olur, bloklar 2/3+ kesin oyla kesinleşir
+        if args.iter().any(|arg| arg == "--finality") {
+            let validators: Vec<usize> = (0..network_lock.node_count()).collect();
+            match network_lock.enable_finality(&validators) {
+                Ok(()) => println!("BFT kesinlik katmanı {} validator ile açıldı.", validators.len()),
+                Err(e) => {
+                    println!("Kesinlik katmanı açılamadı: {}", e);
+                    return;
+                }
             }
         }
-        println!("Tüm node'lar arasında bağlantılar kuruldu.");
 
         // Başlangıç durumunu görüntüle
         println!("\n--- BLOCKCHAIN AĞI OLUŞTURULDU ---");
         network_lock.print_network_state();
 
-        // İlk madenci seç
-        println!("\n--- MADENCİ SEÇİLİYOR ---");
-        network_lock.select_random_validator();
-        let validator_id = network_lock.current_val_id().unwrap();
-        println!("Node {} madenci olarak seçildi.", validator_id);
+        // İlk madenci seç (hash yarışında bloğu kim bulursa o üretir, seçim gerekmez)
+        if network_lock.consensus == ConsensusMode::ProofOfWork && network_lock.mining_model == MiningModel::RandomValidator {
+            println!("\n--- MADENCİ SEÇİLİYOR ---");
+            match network_lock.select_random_validator() {
+                Ok(id) => println!("Node {} madenci olarak seçildi.", id),
+                Err(e) => {
+                    println!("Madenci seçilemedi: {}", e);
+                    return;
+                }
+            }
+        }
         
         // Otomatik madencilik işlemini başlat
         println!("\n--- OTOMATİK MADENCİLİK BAŞLATILIYOR ---");
@@ -137,19 +307,12 @@ fn main() {
         
         // Genesis bloğunu oluştur
         println!("Genesis bloğu oluşturuluyor...");
-        if let Some(block) = network_lock.mine_block() {
-            println!("Genesis bloğu oluşturuldu: {}", block.hash);
-            
-            // Blok mesajını gönder
-            let message = BlockchainMessage {
-                block: block.clone(),
-                validator_id: validator_id, // Bloğu oluşturan madenci (mevcut validator)
-                next_validator_id: network_lock.current_val_id().unwrap(), // Yeni seçilen madenci
-            };
-            let _ = block_sender.send(message);
-        } else {
-            println!("Genesis bloğu oluşturulamadı!");
-            return;
+        match network_lock.mine_block() {
+            Ok(block) => println!("Genesis bloğu oluşturuldu: {}", block.hash),
+            Err(e) => {
+                println!("Genesis bloğu oluşturulamadı: {}", e);
+                return;
+            }
         }
         
         // Genesis blok sonrası ağın durumunu görüntüle
@@ -159,48 +322,15 @@ fn main() {
     
     // Blockchain ağı için bir klon oluştur
     let network_clone = Arc::clone(&network);
-    let block_sender_clone = block_sender.clone();
     
     // Madencilik thread'i
-    let mining_thread = thread::spawn(move || {
-        // Son blok oluşturma zamanını takip et
-        let mut last_block_time = SystemTime::now();
-        
+    // Ağ sanal saatle çalışır; etkileşimli modda her saniye sanal saati 1 saniye ilerletiyoruz
+    let _mining_thread = thread::spawn(move || {
         loop {
-            // Kısa aralıklarla kontrol et
             thread::sleep(Duration::from_secs(1));
             
-            let mut network_lock = network_clone.lock().unwrap();
-            let block_time_secs = network_lock.block_time;
-            
-            // Şu anki zamanı al
-            let now = SystemTime::now();
-            
-            // Son blok oluşturulduğundan beri geçen süreyi hesapla
-            let elapsed = now.duration_since(last_block_time).unwrap_or(Duration::from_secs(0));
-            
-            // Block time geçtiyse yeni blok oluştur
-            if elapsed.as_secs() >= block_time_secs {
-                // Mine_block öncesinde şu anki validator'u kaydet
-                let current_validator = network_lock.current_val_id().unwrap_or(0);
-                
-                // Yeni bir blok oluşturulduğunda, blok mesajını gönder
-                if let Some(block) = network_lock.mine_block() {
-                    // Yeni validator ID'sini al
-                    let new_validator = network_lock.current_val_id().unwrap_or(0);
-                    
-                    // Blok mesajını gönder
-                    let message = BlockchainMessage {
-                        block: block.clone(),
-                        validator_id: current_validator, // Bloğu oluşturan madenci (eski validator)
-                        next_validator_id: new_validator, // Yeni seçilen madenci
-                    };
-                    let _ = block_sender_clone.send(message);
-                    
-                    // Son blok zamanını güncelle
-                    last_block_time = now;
-                }
-            }
+            // Bu sürede üretilen bloklar ve diğer olaylar ağın olay abonelerine iletilir
+            network_clone.lock().unwrap().run_for(1000);
         }
     });
     
@@ -212,7 +342,25 @@ fn main() {
     println!("3. durum - Ağın genel durumunu gösterir");
     println!("4. blockchain <node_id> - Belirtilen node'un blockchain'ini gösterir");
     println!("5. mempool - Mempool'daki işlemleri gösterir");
-    println!("6. çıkış - Simülasyonu sonlandır");
+    println!("6. katıl [komşu_id ...] - Ağa yeni bir node ekler ve zinciri komşularından senkronize eder");
+    println!("7. böl <grup> <grup> ... - Ağı gruplara böler (grup: virgülle ayrılmış node ID'leri, örn. 0,1,2)");
+    println!("8. birleştir - Kesilen bağlantıları açar ve geri alınan blokları raporlar");
+    println!("9. hashgücü <node_id> <değer> - Node'un madencilik hash gücünü ayarlar");
+    println!("10. madenciler - Hash gücü payları ile üretilen blok paylarını karşılaştırır");
+    println!("11. stake <node_id> <miktar> - Node'un coinlerini stake olarak kilitler (PoS modu)");
+    println!("12. çiftimza <node_id> - Slot önericisi aynı yükseklikte iki blok imzalar (PoS saldırı senaryosu)");
+    println!("13. cezalar - Zincirde uygulanan çifte imza cezalarını gösterir");
+    println!("14. kesinlik - BFT kesinlik katmanında node'ların kesinleşmiş yüksekliklerini ve turlarını gösterir");
+    println!("15. bizans <node_id> <silent|equivocate|honest> - Validator'a Bizans davranışı atar (kesinlik katmanı)");
+    println!("16. bencil <node_id> <hash_payı> <gamma> | bencil rapor | bencil dur - Bencil madencilik saldırısı ve gelir payı raporu");
+    println!("17. çifteharcama <saldırgan_id> <satıcı_id> <miktar> <k> <hash_payı> | çifteharcama rapor - %51 çifte harcama saldırısı");
+    println!("18. tutulma <kurban_id> <sybil_sayısı> <hash_payı> [çeşitli] [çapa <n>] | tutulma rapor | tutulma dur - Sybil node'larla tutulma (eclipse) saldırısı");
+    println!("19. davranış <node_id> <profil[,profil...]|honest> | davranış rapor - Node'a ağ katmanında kötü niyetli davranış atar (droptx, withhold, invalid, spam[:adet], delay[:ms], lie)");
+    println!("20. yasaklar [node_id] | yasaklar kaldır <node_id> <komşu_id> - Kötü davranan komşulara konulan yasakları ve ceza puanlarını gösterir");
+    println!("21. oturum ayrıl|çök|başlat <node_id> | oturum model <oturum> <kapalı_kalma> <çökme_olasılığı> [node_id...] | oturum rapor | oturum dur - Node'ları ağdan çıkarır, çökertir ve yeniden başlatır (dağılımlar: fixed:ms, exp:ms, pareto:ms:şekil, weibull:ms:şekil)");
+    println!("22. yük başlat <işlem/sn> [miktar <en_az>:<şekil>] [sıcak <adet>:<pay>] [toplu <alıcı>:<olasılık>] [gönderici <id,id...>] | yük rapor | yük dur - Node cüzdanları arasında sentetik işlem yükü üretir");
+    println!("23. metrik <csv|jsonl> <dosya_öneki> - Blok ve node zaman serilerini <önek>_blocks ve <önek>_nodes dosyalarına yazar");
+    println!("24. çıkış - Simülasyonu sonlandır");
     println!("==============================\n");
     
     // Komut döngüsü
@@ -284,10 +432,9 @@ fn main() {
                         amount_satoshi
                     );
                     
-                    if let Some(_) = tx {
-                        println!("İşlem oluşturuldu ve mempool'a eklendi");
-                    } else {
-                        println!("İşlem oluşturulamadı! Bakiye yetersiz olabilir.");
+                    match tx {
+                        Ok(_) => println!("İşlem oluşturuldu ve mempool'a eklendi"),
+                        Err(e) => println!("İşlem oluşturulamadı: {}", e),
                     }
                 } else {
                     println!("Hata: Geçersiz parametre formatı. Sayısal değerler girin.");
@@ -332,6 +479,513 @@ fn main() {
                 }
                 println!("-----------------------------\n");
             },
+            "katıl" => {
+                let mut network_lock = network.lock().unwrap();
+                
+                // Komşu belirtilmezse yeni node mevcut tüm node'lara bağlanır
+                let peers: Vec<usize> = if parts.len() > 1 {
+                    match parts[1..].iter().map(|part| part.parse::<usize>()).collect::<Result<Vec<_>, _>>() {
+                        Ok(peers) => peers,
+                        Err(_) => {
+                            println!("Hata: Geçersiz Node ID formatı. Sayısal değerler girin.");
+                            continue;
+                        }
+                    }
+                } else {
+                    (0..network_lock.node_count()).collect()
+                };
+                
+                if let Some(&invalid) = peers.iter().find(|&&peer| peer >= network_lock.node_count()) {
+                    println!("Hata: Geçersiz Node ID: {}", invalid);
+                    continue;
+                }
+                
+                match network_lock.join_node(&peers) {
+                    Ok(node_id) => println!("Node {} ağa katıldı, senkronizasyon başladı (ilerleme için: durum)", node_id),
+                    Err(e) => println!("Node ağa katılamadı: {}", e),
+                }
+            },
+            "böl" => {
+                if parts.len() < 3 {
+                    println!("Hata: En az iki grup belirtin. Kullanım: böl 0,1,2 3,4");
+                    continue;
+                }
+                
+                let groups: Result<Vec<Vec<usize>>, _> = parts[1..]
+                    .iter()
+                    .map(|group| group.split(',').map(|id| id.parse::<usize>()).collect())
+                    .collect();
+                match groups {
+                    Ok(groups) => {
+                        let mut network_lock = network.lock().unwrap();
+                        let cut = network_lock.partition(&groups);
+                        println!("Ağ {} gruba bölündü, {} bağlantı kesildi", groups.len(), cut);
+                    }
+                    Err(_) => println!("Hata: Geçersiz Node ID formatı. Sayısal değerler girin."),
+                }
+            },
+            "birleştir" => {
+                let mut network_lock = network.lock().unwrap();
+                if !network_lock.is_partitioned() {
+                    println!("Ağ bölünmüş değil.");
+                    continue;
+                }
+                
+                let restored = network_lock.heal();
+                println!("{} bağlantı tekrar açıldı", restored);
+                if let Some(report) = network_lock.heal_report() {
+                    println!("{}", report);
+                }
+            },
+            "hashgücü" => {
+                if parts.len() < 3 {
+                    println!("Hata: Eksik parametreler. Kullanım: hashgücü <node_id> <değer>");
+                    continue;
+                }
+                
+                if let (Ok(node_id), Ok(hashrate)) = (parts[1].parse::<usize>(), parts[2].parse::<f64>()) {
+                    let mut network_lock = network.lock().unwrap();
+                    match network_lock.set_hashrate(node_id, hashrate) {
+                        Ok(_) => println!("Node {} hash gücü {} olarak ayarlandı", node_id, hashrate),
+                        Err(e) => println!("Hata: {}", e),
+                    }
+                } else {
+                    println!("Hata: Geçersiz parametre formatı. Sayısal değerler girin.");
+                }
+            },
+            "madenciler" => {
+                let network_lock = network.lock().unwrap();
+                for share in network_lock.miner_shares() {
+                    println!("{}", share);
+                }
+            },
+            "stake" => {
+                if parts.len() < 3 {
+                    println!("Hata: Eksik parametreler. Kullanım: stake <node_id> <miktar>");
+                    continue;
+                }
+                
+                if let (Ok(node_id), Ok(amount)) = (parts[1].parse::<usize>(), parts[2].parse::<f64>()) {
+                    // Coin miktarını satoshi'ye çevir
+                    let amount_satoshi = (amount * 100_000_000.0) as u64;
+                    let mut network_lock = network.lock().unwrap();
+                    match network_lock.stake(node_id, amount_satoshi) {
+                        Ok(tx) => println!("Stake işlemi oluşturuldu: {}", tx.id),
+                        Err(e) => println!("Hata: {}", e),
+                    }
+                    for (id, staked) in network_lock.stake_table() {
+                        println!("Node {}: {} coin stake", id, staked as f64 / 100_000_000.0);
+                    }
+                } else {
+                    println!("Hata: Geçersiz parametre formatı. Sayısal değerler girin.");
+                }
+            },
+            #[cfg(feature = "attacks")]
+            "çiftimza" => {
+                if parts.len() < 2 {
+                    println!("Hata: Eksik parametreler. Kullanım: çiftimza <node_id>");
+                    continue;
+                }
+                
+                if let Ok(node_id) = parts[1].parse::<usize>() {
+                    let mut network_lock = network.lock().unwrap();
+                    match network_lock.equivocate(node_id) {
+                        Ok((first, second)) => {
+                            println!("Node {} #{} yüksekliğinde iki blok imzaladı:", node_id, first.index);
+                            println!("  {}", first.hash);
+                            println!("  {}", second.hash);
+                        },
+                        Err(e) => println!("Hata: {}", e),
+                    }
+                } else {
+                    println!("Hata: Geçersiz node ID.");
+                }
+            },
+            "cezalar" => {
+                let network_lock = network.lock().unwrap();
+                let records = network_lock.slashings();
+                if records.is_empty() {
+                    println!("Henüz uygulanmış bir ceza yok");
+                }
+                for record in records {
+                    println!("{}", record);
+                }
+            },
+            "kesinlik" => {
+                let network_lock = network.lock().unwrap();
+                let status = network_lock.finality_status();
+                if status.is_empty() {
+                    println!("BFT kesinlik katmanı açık değil (--finality ile başlatın)");
+                }
+                for node in status {
+                    println!("{}", node);
+                }
+                let conflicts = network_lock.finality_conflicts();
+                if !conflicts.is_empty() {
+                    println!("UYARI: Farklı blokların kesinleştiği yükseklikler: {:?}", conflicts);
+                }
+            },
+            #[cfg(feature = "attacks")]
+            "bizans" => {
+                if parts.len() < 3 {
+                    println!("Hata: Eksik parametreler. Kullanım: bizans <node_id> <silent|equivocate|honest>");
+                    continue;
+                }
+                
+                let fault = match parts[2] {
+                    "honest" => Ok(None),
+                    other => other.parse::<blockchain_sim::BftFault>().map(Some),
+                };
+                match (parts[1].parse::<usize>(), fault) {
+                    (Ok(node_id), Ok(fault)) => {
+                        let mut network_lock = network.lock().unwrap();
+                        match network_lock.set_bft_fault(node_id, fault) {
+                            Ok(()) => println!("Node {} davranışı güncellendi", node_id),
+                            Err(e) => println!("Hata: {}", e),
+                        }
+                    },
+                    (Err(_), _) => println!("Hata: Geçersiz node ID."),
+                    (_, Err(e)) => println!("Hata: {}", e),
+                }
+            },
+            #[cfg(feature = "attacks")]
+            "bencil" => {
+                let mut network_lock = network.lock().unwrap();
+                match parts.get(1).copied() {
+                    Some("rapor") => match network_lock.selfish_mining_report() {
+                        Some(report) => println!("{}", report),
+                        None => println!("Bencil madencilik saldırısı sürmüyor"),
+                    },
+                    Some("dur") => match network_lock.stop_selfish_mining() {
+                        Some(report) => {
+                            println!("{}", report);
+                            println!("Gizli bloklar yayınlandı, saldırı durduruldu");
+                        },
+                        None => println!("Bencil madencilik saldırısı sürmüyor"),
+                    },
+                    _ if parts.len() < 4 => {
+                        println!("Hata: Eksik parametreler. Kullanım: bencil <node_id> <hash_payı> <gamma> | bencil rapor | bencil dur");
+                    },
+                    _ => match (parts[1].parse::<usize>(), parts[2].parse::<f64>(), parts[3].parse::<f64>()) {
+                        (Ok(node_id), Ok(hash_share), Ok(gamma)) => match network_lock.start_selfish_mining(node_id, hash_share, gamma) {
+                            Ok(()) => println!("Node {} bencil madencilik yapıyor (hash gücü payı %{:.1}, gamma {:.2})", node_id, hash_share * 100.0, gamma),
+                            Err(e) => println!("Hata: {}", e),
+                        },
+                        _ => println!("Hata: Geçersiz parametre formatı. Sayısal değerler girin."),
+                    },
+                }
+            },
+            #[cfg(feature = "attacks")]
+            "çifteharcama" => {
+                let mut network_lock = network.lock().unwrap();
+                if parts.get(1) == Some(&"rapor") {
+                    match network_lock.double_spend_report() {
+                        Some(report) => println!("{}", report),
+                        None => println!("Henüz bir çifte harcama saldırısı başlatılmadı"),
+                    }
+                    continue;
+                }
+                if parts.len() < 6 {
+                    println!("Hata: Eksik parametreler. Kullanım: çifteharcama <saldırgan_id> <satıcı_id> <miktar> <k> <hash_payı> | çifteharcama rapor");
+                    continue;
+                }
+                
+                match (
+                    parts[1].parse::<usize>(),
+                    parts[2].parse::<usize>(),
+                    parts[3].parse::<f64>(),
+                    parts[4].parse::<usize>(),
+                    parts[5].parse::<f64>()
+                ) {
+                    (Ok(attacker), Ok(merchant), Ok(amount), Ok(confirmations), Ok(hash_share)) => {
+                        let amount_satoshi = (amount * 100_000_000.0) as u64;
+                        match network_lock.start_double_spend(attacker, merchant, amount_satoshi, confirmations, hash_share) {
+                            Ok(payment) => {
+                                println!("Node {} Node {}'e {} coin ödedi (işlem {})", attacker, merchant, amount, payment.id);
+                                println!("Saldırgan aynı coinleri kendine harcayan gizli bir dalda madencilik yapıyor; sonuç için: çifteharcama rapor");
+                            },
+                            Err(e) => println!("Hata: {}", e),
+                        }
+                    },
+                    _ => println!("Hata: Geçersiz parametre formatı. Sayısal değerler girin."),
+                }
+            },
+            #[cfg(feature = "attacks")]
+            "tutulma" => {
+                let mut network_lock = network.lock().unwrap();
+                match parts.get(1).copied() {
+                    Some("rapor") => match network_lock.eclipse_report() {
+                        Some(report) => println!("{}", report),
+                        None => println!("Henüz bir tutulma saldırısı başlatılmadı"),
+                    },
+                    Some("dur") => match network_lock.stop_eclipse() {
+                        Some(report) => {
+                            println!("{}", report);
+                            println!("Sybil'ler çekildi, kurban eski komşularına yeniden bağlandı");
+                        },
+                        None => println!("Tutulma saldırısı sürmüyor"),
+                    },
+                    _ if parts.len() < 4 => {
+                        println!("Hata: Eksik parametreler. Kullanım: tutulma <kurban_id> <sybil_sayısı> <hash_payı> [çeşitli] [çapa <n>] | tutulma rapor | tutulma dur");
+                    },
+                    _ => {
+                        // İsteğe bağlı savunmalar
+                        let mut defense = blockchain_sim::EclipseDefense::default();
+                        let mut valid = true;
+                        let mut options = parts[4..].iter();
+                        while let Some(option) = options.next() {
+                            match *option {
+                                "çeşitli" => defense.diverse_outbound = true,
+                                "çapa" => match options.next().and_then(|value| value.parse::<usize>().ok()) {
+                                    Some(anchors) => defense.anchors = anchors,
+                                    None => valid = false,
+                                },
+                                _ => valid = false,
+                            }
+                        }
+                        
+                        match (parts[1].parse::<usize>(), parts[2].parse::<usize>(), parts[3].parse::<f64>()) {
+                            (Ok(victim), Ok(sybil_count), Ok(hash_share)) if valid => match network_lock.start_eclipse(victim, sybil_count, hash_share, defense) {
+                                Ok(sybils) => {
+                                    println!("{} Sybil node eklendi: {:?}", sybils.len(), sybils);
+                                    println!("Node {} komşu yuvalarını yeniden kurdu; sapma için: tutulma rapor", victim);
+                                },
+                                Err(e) => println!("Hata: {}", e),
+                            },
+                            _ => println!("Hata: Geçersiz parametre formatı. Savunmalar: çeşitli, çapa <n>"),
+                        }
+                    },
+                }
+            },
+            #[cfg(feature = "attacks")]
+            "davranış" => {
+                let mut network_lock = network.lock().unwrap();
+                if parts.get(1) == Some(&"rapor") {
+                    println!("{}", network_lock.byzantine_report());
+                    continue;
+                }
+                if parts.len() < 3 {
+                    println!("Hata: Eksik parametreler. Kullanım: davranış <node_id> <profil[,profil...]|honest> | davranış rapor");
+                    continue;
+                }
+                
+                let behaviors = match parts[2] {
+                    "honest" => Ok(Vec::new()),
+                    list => list.split(',').map(|name| name.parse::<blockchain_sim::Misbehavior>()).collect::<Result<Vec<_>, _>>(),
+                };
+                match (parts[1].parse::<usize>(), behaviors) {
+                    (Ok(node_id), Ok(behaviors)) => match network_lock.set_misbehavior(node_id, behaviors) {
+                        Ok(()) => println!("Node {} davranışı güncellendi", node_id),
+                        Err(e) => println!("Hata: {}", e),
+                    },
+                    (Err(_), _) => println!("Hata: Geçersiz node ID."),
+                    (_, Err(e)) => println!("Hata: {}", e),
+                }
+            },
+            "yasaklar" => {
+                let mut network_lock = network.lock().unwrap();
+                if parts.get(1) == Some(&"kaldır") {
+                    if parts.len() < 4 {
+                        println!("Hata: Eksik parametreler. Kullanım: yasaklar kaldır <node_id> <komşu_id>");
+                        continue;
+                    }
+                    match (parts[2].parse::<usize>(), parts[3].parse::<usize>()) {
+                        (Ok(node_id), Ok(peer)) => match network_lock.unban(node_id, peer) {
+                            Ok(true) => println!("Node {}'in Node {} üzerindeki yasağı kaldırıldı", node_id, peer),
+                            Ok(false) => println!("Node {}, Node {}'i yasaklamamış", node_id, peer),
+                            Err(e) => println!("Hata: {}", e),
+                        },
+                        _ => println!("Hata: Geçersiz node ID."),
+                    }
+                    continue;
+                }
+                
+                let node_id = match parts.get(1).map(|id| id.parse::<usize>()) {
+                    Some(Ok(node_id)) if node_id < network_lock.node_count() => Some(node_id),
+                    Some(_) => {
+                        println!("Hata: Geçersiz node ID.");
+                        continue;
+                    },
+                    None => None,
+                };
+                
+                let bans = network_lock.active_bans(node_id);
+                if bans.is_empty() {
+                    println!("Süresi dolmamış yasak yok");
+                }
+                for record in bans {
+                    println!("{}", record);
+                }
+                if let Some(node_id) = node_id {
+                    let scores = network_lock.ban_scores(node_id);
+                    if !scores.is_empty() {
+                        println!("Node {} ceza puanları (eşik {}):", node_id, network_lock.ban_threshold);
+                        for (peer, score) in scores {
+                            println!("  Node {}: {}", peer, score);
+                        }
+                    }
+                }
+            },
+            "oturum" => {
+                let mut network_lock = network.lock().unwrap();
+                match parts.get(1).copied() {
+                    Some("rapor") => {
+                        println!("{}", network_lock.churn_report());
+                        for record in network_lock.churn_log.iter().rev().take(10).rev() {
+                            println!("  {}", record);
+                        }
+                    },
+                    Some("dur") => {
+                        network_lock.stop_churn();
+                        println!("Oturum modelleri kaldırıldı, çevrimdışı node'lar yeniden başlatıldı");
+                    },
+                    Some("model") => {
+                        if parts.len() < 5 {
+                            println!("Hata: Eksik parametreler. Kullanım: oturum model <oturum> <kapalı_kalma> <çökme_olasılığı> [node_id...]");
+                            continue;
+                        }
+                        let model = match (parts[2].parse::<blockchain_sim::SessionLength>(), parts[3].parse::<blockchain_sim::SessionLength>(), parts[4].parse::<f64>()) {
+                            (Ok(session), Ok(downtime), Ok(crash_probability)) => blockchain_sim::ChurnModel { session, downtime, crash_probability },
+                            (Err(e), _, _) | (_, Err(e), _) => {
+                                println!("Hata: {}", e);
+                                continue;
+                            },
+                            _ => {
+                                println!("Hata: Geçersiz çökme olasılığı.");
+                                continue;
+                            },
+                        };
+                        // Node verilmezse tüm node'lar modele uyar
+                        let node_ids: Result<Vec<usize>, _> = if parts.len() > 5 {
+                            parts[5..].iter().map(|id| id.parse::<usize>()).collect()
+                        } else {
+                            Ok((0..network_lock.node_count()).collect())
+                        };
+                        match node_ids {
+                            Ok(node_ids) => match network_lock.set_churn(&node_ids, model) {
+                                Ok(()) => println!("{} node için oturum modeli: {}", node_ids.len(), model),
+                                Err(e) => println!("Hata: {}", e),
+                            },
+                            Err(_) => println!("Hata: Geçersiz node ID."),
+                        }
+                    },
+                    Some(action @ ("ayrıl" | "çök" | "başlat")) => {
+                        let node_id = match parts.get(2).map(|id| id.parse::<usize>()) {
+                            Some(Ok(node_id)) => node_id,
+                            _ => {
+                                println!("Hata: Geçersiz node ID. Kullanım: oturum {} <node_id>", action);
+                                continue;
+                            },
+                        };
+                        let result = match action {
+                            "ayrıl" => network_lock.leave_node(node_id),
+                            "çök" => network_lock.crash_node(node_id),
+                            _ => network_lock.restart_node(node_id),
+                        };
+                        if let Err(e) = result {
+                            println!("Hata: {}", e);
+                        }
+                    },
+                    _ => println!("Kullanım: oturum ayrıl|çök|başlat <node_id> | oturum model <oturum> <kapalı_kalma> <çökme_olasılığı> [node_id...] | oturum rapor | oturum dur"),
+                }
+            },
+            "yük" => {
+                let mut network_lock = network.lock().unwrap();
+                match parts.get(1).copied() {
+                    Some("rapor") => match network_lock.workload_report() {
+                        Some(report) => println!("{}", report),
+                        None => println!("Henüz işlem yükü başlatılmadı"),
+                    },
+                    Some("dur") => match network_lock.stop_workload() {
+                        Some(report) => println!("İşlem yükü durduruldu\n{}", report),
+                        None => println!("Çalışan bir işlem yükü yok"),
+                    },
+                    Some("başlat") => {
+                        let mut workload = blockchain_sim::Workload::default();
+                        match parts.get(2).map(|rate| rate.parse::<f64>()) {
+                            Some(Ok(rate)) => workload.rate = rate,
+                            _ => {
+                                println!("Hata: Geçersiz işlem oranı. Kullanım: yük başlat <işlem/sn> [miktar <en_az>:<şekil>] [sıcak <adet>:<pay>] [toplu <alıcı>:<olasılık>] [gönderici <id,id...>]");
+                                continue;
+                            },
+                        }
+                        
+                        // Seçenekler anahtar kelime ve "a:b" biçiminde değer çiftleridir
+                        let mut senders = Vec::new();
+                        let mut valid = true;
+                        for option in parts[3..].chunks(2) {
+                            let value = option.get(1).copied().unwrap_or("");
+                            let pair = value.split_once(':');
+                            let parsed = match (option[0], pair) {
+                                ("miktar", Some((min, shape))) => match (min.parse::<f64>(), shape.parse::<f64>()) {
+                                    (Ok(min), Ok(shape)) => {
+                                        workload.min_amount = (min * 100_000_000.0) as u64;
+                                        workload.amount_shape = shape;
+                                        true
+                                    },
+                                    _ => false,
+                                },
+                                ("sıcak", Some((count, share))) => match (count.parse::<usize>(), share.parse::<f64>()) {
+                                    (Ok(count), Ok(share)) => {
+                                        workload.hotspots = count;
+                                        workload.hotspot_share = share;
+                                        true
+                                    },
+                                    _ => false,
+                                },
+                                ("toplu", Some((fan_out, probability))) => match (fan_out.parse::<usize>(), probability.parse::<f64>()) {
+                                    (Ok(fan_out), Ok(probability)) => {
+                                        workload.fan_out = fan_out;
+                                        workload.batch_probability = probability;
+                                        true
+                                    },
+                                    _ => false,
+                                },
+                                ("gönderici", _) => match value.split(',').map(|id| id.parse::<usize>()).collect::<Result<Vec<_>, _>>() {
+                                    Ok(ids) => {
+                                        senders = ids;
+                                        true
+                                    },
+                                    Err(_) => false,
+                                },
+                                _ => false,
+                            };
+                            if !parsed {
+                                println!("Hata: Geçersiz seçenek: {} {}", option[0], value);
+                                valid = false;
+                                break;
+                            }
+                        }
+                        if !valid {
+                            continue;
+                        }
+                        
+                        match network_lock.start_workload(workload, &senders) {
+                            Ok(()) => println!("İşlem yükü başlatıldı: {}", workload),
+                            Err(e) => println!("Hata: {}", e),
+                        }
+                    },
+                    _ => println!("Kullanım: yük başlat <işlem/sn> [miktar <en_az>:<şekil>] [sıcak <adet>:<pay>] [toplu <alıcı>:<olasılık>] [gönderici <id,id...>] | yük rapor | yük dur"),
+                }
+            },
+            "metrik" => {
+                if parts.len() < 3 {
+                    println!("Kullanım: metrik <csv|jsonl> <dosya_öneki>");
+                    continue;
+                }
+                let format = match parts[1].parse::<blockchain_sim::MetricsFormat>() {
+                    Ok(format) => format,
+                    Err(e) => {
+                        println!("Hata: {}", e);
+                        continue;
+                    },
+                };
+                let network_lock = network.lock().unwrap();
+                match network_lock.export_metrics(parts[2], format) {
+                    Ok(export) => println!("{}", export),
+                    Err(e) => println!("Hata: Metrikler yazılamadı: {}", e),
+                }
+            },
             "çıkış" | "exit" | "quit" => {
                 println!("Simülasyon sonlandırılıyor...");
                 
@@ -344,23 +998,14 @@ fn main() {
                     }
                 }
                 
-                // Thread'leri sonlandır
-                drop(block_sender); // Kanalı kapat
-                
-                // Programı sonlandır
-                std::process::exit(0);
+                break;
             },
             _ => {
                 println!("Bilinmeyen komut: {}", parts[0]);
-                println!("Kullanabileceğiniz komutlar: bakiye, transfer, durum, blockchain, mempool, çıkış");
+                println!("Kullanabileceğiniz komutlar: bakiye, transfer, durum, blockchain, mempool, katıl, böl, birleştir, hashgücü, madenciler, stake, çiftimza, cezalar, kesinlik, bizans, bencil, çifteharcama, tutulma, davranış, yasaklar, oturum, yük, metrik, çıkış");
             }
         }
     }
     
-    // Thread'leri sonlandır
-    drop(block_sender);
-    let _ = mining_thread.join();
-    let _ = block_display_thread.join();
-    
     println!("Blockchain Simülasyonu sonlandırıldı.");
 }
\ No newline at end of file
@@ -0,0 +1,75 @@
+use crate::block::{Block, BlockHeader};
+use crate::finality::{Proposal, Vote};
+use crate::slashing::EquivocationEvidence;
+use crate::transaction::Transaction;
+
+// Duyurulan/istenen bir envanter öğesi (blok veya işlem hash'i)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum InvItem {
+    Block(String),
+    Transaction(String),
+}
+
+impl InvItem {
+    // Öğenin hash'i / ID'si
+    pub fn id(&self) -> &str {
+        match self {
+            InvItem::Block(hash) => hash,
+            InvItem::Transaction(id) => id,
+        }
+    }
+}
+
+// Node'lar arasında bağlantılar üzerinden gönderilen mesajlar
+#[derive(Debug, Clone)]
+pub enum Message {
+    Block(Block),
+    Transaction(Transaction),
+    // Gönderenin yeni doğruladığı öğelerin duyurusu (sadece hash'ler)
+    Inv(Vec<InvItem>),
+    // Duyurulan ama bilinmeyen öğelerin istenmesi
+    GetData(Vec<InvItem>),
+    // Bilinen zincirin özetinden (locator) sonraki başlıkların istenmesi
+    GetHeaders { locator: Vec<String> },
+    // Aktif zincirdeki ardışık blok başlıkları
+    Headers(Vec<BlockHeader>),
+    // Bir validator'un çifte imza kanıtı (iki başlık taşıdığı için kutulanır)
+    Evidence(Box<EquivocationEvidence>),
+    // BFT kesinlik katmanında önericinin blok önerisi
+    Proposal(Box<Proposal>),
+    // BFT kesinlik katmanında bir validator'un ön oyu veya kesin oyu
+    Vote(Box<Vote>),
+}
+
+impl Message {
+    // Mesajın yaklaşık boyutu (byte), bant genişliği hesabı için
+    pub fn size(&self) -> usize {
+        // Her mesajın 24 byte'lık bir başlığı olduğunu varsayıyoruz (Bitcoin'deki gibi)
+        24 + match self {
+            Message::Block(block) => block.size(),
+            Message::Transaction(tx) => tx.size(),
+            // Her envanter öğesi 4 byte tür + 32 byte hash
+            Message::Inv(items) | Message::GetData(items) => 1 + items.len() * 36,
+            Message::GetHeaders { locator } => 1 + locator.len() * 32,
+            Message::Headers(headers) => 1 + headers.iter().map(|header| header.size()).sum::<usize>(),
+            Message::Evidence(evidence) => evidence.size(),
+            Message::Proposal(proposal) => proposal.size(),
+            Message::Vote(vote) => vote.size(),
+        }
+    }
+
+    // Mesaj türünün adı
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Message::Block(_) => "block",
+            Message::Transaction(_) => "tx",
+            Message::Inv(_) => "inv",
+            Message::GetData(_) => "getdata",
+            Message::GetHeaders { .. } => "getheaders",
+            Message::Headers(_) => "headers",
+            Message::Evidence(_) => "evidence",
+            Message::Proposal(_) => "proposal",
+            Message::Vote(_) => "vote",
+        }
+    }
+}
@@ -0,0 +1,389 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::str::FromStr;
+
+use crate::network::BlockchainNetwork;
+use crate::sim::SimTime;
+
+// Zaman serilerinin dışa aktarılma biçimi
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricsFormat {
+    // Başlık satırlı virgülle ayrılmış değerler; boş hücre değerin olmadığını gösterir
+    Csv,
+    // Her satırda bir JSON nesnesi; olmayan değerler null yazılır
+    JsonLines,
+}
+
+impl MetricsFormat {
+    // Dosya uzantısı
+    pub fn extension(&self) -> &'static str {
+        match self {
+            MetricsFormat::Csv => "csv",
+            MetricsFormat::JsonLines => "jsonl",
+        }
+    }
+}
+
+impl FromStr for MetricsFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(MetricsFormat::Csv),
+            "jsonl" | "json" => Ok(MetricsFormat::JsonLines),
+            other => Err(format!("bilinmeyen biçim: {} (csv, jsonl)", other)),
+        }
+    }
+}
+
+// Tek bir ölçüm değeri
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetricValue {
+    Integer(u64),
+    Float(f64),
+    Bool(bool),
+    Text(String),
+    Missing,
+}
+
+impl From<Option<SimTime>> for MetricValue {
+    fn from(value: Option<SimTime>) -> Self {
+        value.map(MetricValue::Integer).unwrap_or(MetricValue::Missing)
+    }
+}
+
+impl From<Option<f64>> for MetricValue {
+    fn from(value: Option<f64>) -> Self {
+        value.map(MetricValue::Float).unwrap_or(MetricValue::Missing)
+    }
+}
+
+impl MetricValue {
+    fn csv(&self) -> String {
+        match self {
+            MetricValue::Integer(value) => value.to_string(),
+            MetricValue::Float(value) => value.to_string(),
+            MetricValue::Bool(value) => value.to_string(),
+            // Metinler (hash'ler) virgül veya tırnak içermez
+            MetricValue::Text(value) => value.clone(),
+            MetricValue::Missing => String::new(),
+        }
+    }
+
+    pub(crate) fn json(&self) -> String {
+        match self {
+            MetricValue::Integer(value) => value.to_string(),
+            MetricValue::Float(value) if value.is_finite() => value.to_string(),
+            MetricValue::Float(_) | MetricValue::Missing => "null".to_string(),
+            MetricValue::Bool(value) => value.to_string(),
+            MetricValue::Text(value) => format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\"")),
+        }
+    }
+}
+
+// Zaman serisindeki bir satır: sabit sırada (alan adı, değer) çiftleri
+pub trait MetricRow {
+    fn fields(&self) -> Vec<(&'static str, MetricValue)>;
+}
+
+// Bir bloğun üretimi, yayılması ve içeriğiyle ilgili ölçümler
+#[derive(Debug, Clone)]
+pub struct BlockMetrics {
+    pub time: SimTime,                         // Bloğun üretildiği an (ms)
+    pub height: usize,
+    pub hash: String,
+    pub producer: usize,
+    pub interval: Option<SimTime>,             // Ebeveyn bloğun üretiminden bu yana geçen süre (ms)
+    pub transactions: usize,                   // Coinbase hariç işlem sayısı
+    pub tps: Option<f64>,                      // İşlem sayısı / blok aralığı (saniye)
+    pub confirmation_latency: Option<f64>,     // İşlemlerin gönderilmesinden bu bloğa girmesine kadar geçen süre ortalaması (ms)
+    pub propagation_p50: Option<SimTime>,      // Node'ların yarısına ulaşma süresi (ms)
+    pub propagation_p90: Option<SimTime>,
+    pub propagation_full: Option<SimTime>,     // Tüm node'lara ulaşma süresi; ulaşmadıysa yok
+    pub reached: usize,                        // Bloğu kabul eden node sayısı (üretici dahil)
+    pub stale: bool,                           // Blok en uzun zincirde değil
+}
+
+impl MetricRow for BlockMetrics {
+    fn fields(&self) -> Vec<(&'static str, MetricValue)> {
+        vec![
+            ("time", MetricValue::Integer(self.time)),
+            ("height", MetricValue::Integer(self.height as u64)),
+            ("hash", MetricValue::Text(self.hash.clone())),
+            ("producer", MetricValue::Integer(self.producer as u64)),
+            ("interval_ms", self.interval.into()),
+            ("transactions", MetricValue::Integer(self.transactions as u64)),
+            ("tps", self.tps.into()),
+            ("confirmation_latency_ms", self.confirmation_latency.into()),
+            ("propagation_p50_ms", self.propagation_p50.into()),
+            ("propagation_p90_ms", self.propagation_p90.into()),
+            ("propagation_full_ms", self.propagation_full.into()),
+            ("reached", MetricValue::Integer(self.reached as u64)),
+            ("stale", MetricValue::Bool(self.stale)),
+        ]
+    }
+}
+
+// Bir node'un belirli bir andaki durumu
+#[derive(Debug, Clone)]
+pub struct NodeMetrics {
+    pub time: SimTime,
+    pub node_id: usize,
+    pub online: bool,
+    pub height: usize,        // Aktif zincir uzunluğu
+    pub mempool: usize,       // Bekleyen işlem sayısı
+    pub orphans: usize,       // Ebeveyni bilinmeyen bloklar
+    pub stale_blocks: usize,  // Bilinen ama aktif zincirde olmayan (yan daldaki) bloklar
+    pub hashrate: f64,
+    pub bytes_sent: u64,      // Şu ana kadar gönderilen toplam trafik
+    pub bytes_received: u64,
+}
+
+impl MetricRow for NodeMetrics {
+    fn fields(&self) -> Vec<(&'static str, MetricValue)> {
+        vec![
+            ("time", MetricValue::Integer(self.time)),
+            ("node", MetricValue::Integer(self.node_id as u64)),
+            ("online", MetricValue::Bool(self.online)),
+            ("height", MetricValue::Integer(self.height as u64)),
+            ("mempool", MetricValue::Integer(self.mempool as u64)),
+            ("orphans", MetricValue::Integer(self.orphans as u64)),
+            ("stale_blocks", MetricValue::Integer(self.stale_blocks as u64)),
+            ("hashrate", MetricValue::Float(self.hashrate)),
+            ("bytes_sent", MetricValue::Integer(self.bytes_sent)),
+            ("bytes_received", MetricValue::Integer(self.bytes_received)),
+        ]
+    }
+}
+
+// Simülasyon boyunca toplanan ham ölçümler
+// Blok satırları yayılma kayıtlarından dışa aktarma anında üretilir; burada yalnızca sonradan bulunamayacak değerler tutulur.
+#[derive(Debug, Clone, Default)]
+pub struct MetricsRecorder {
+    pub node_samples: Vec<NodeMetrics>,
+    pub submitted: HashMap<String, SimTime>, // İşlem ID -> ağa ilk verildiği an
+}
+
+impl MetricsRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // İşlemin gönderildiği anı kaydet (onay gecikmesi için)
+    pub fn record_submitted(&mut self, tx_id: &str, time: SimTime) {
+        self.submitted.entry(tx_id.to_string()).or_insert(time);
+    }
+}
+
+// Satırları verilen biçimde yaz
+pub fn write_rows<W: Write, R: MetricRow>(writer: &mut W, rows: &[R], format: MetricsFormat) -> io::Result<()> {
+    match format {
+        MetricsFormat::Csv => {
+            if let Some(first) = rows.first() {
+                let header: Vec<&str> = first.fields().iter().map(|(name, _)| *name).collect();
+                writeln!(writer, "{}", header.join(","))?;
+            }
+            for row in rows {
+                let cells: Vec<String> = row.fields().iter().map(|(_, value)| value.csv()).collect();
+                writeln!(writer, "{}", cells.join(","))?;
+            }
+        }
+        MetricsFormat::JsonLines => {
+            for row in rows {
+                let pairs: Vec<String> = row.fields().iter().map(|(name, value)| format!("\"{}\":{}", name, value.json())).collect();
+                writeln!(writer, "{{{}}}", pairs.join(","))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// Dışa aktarılan dosyalar
+#[derive(Debug, Clone)]
+pub struct MetricsExport {
+    pub blocks_path: String,
+    pub nodes_path: String,
+    pub blocks: usize,
+    pub node_samples: usize,
+}
+
+impl fmt::Display for MetricsExport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} blok satırı {} dosyasına, {} node satırı {} dosyasına yazıldı",
+            self.blocks, self.blocks_path, self.node_samples, self.nodes_path
+        )
+    }
+}
+
+impl BlockchainNetwork {
+    // Tüm node'ların şu anki durumunu zaman serisine ekle
+    // Her blok üretim anında kendiliğinden çağrılır; arada ek örnek almak için de kullanılabilir.
+    pub fn sample_nodes(&mut self) {
+        let now = self.now();
+        for node in &self.nodes {
+            let on_chain = |hash: &str, index: usize| node.blockchain.get(index).map(|block| block.hash == hash).unwrap_or(false);
+            let stale_blocks = node.block_store.values().filter(|block| !on_chain(&block.hash, block.index)).count();
+            let traffic = self.traffic.get(node.id);
+            self.metrics.node_samples.push(NodeMetrics {
+                time: now,
+                node_id: node.id,
+                online: node.online,
+                height: node.blockchain.len(),
+                mempool: node.mempool.len(),
+                orphans: node.orphan_blocks.len(),
+                stale_blocks,
+                hashrate: node.hashrate,
+                bytes_sent: traffic.map(|stats| stats.bytes_sent).unwrap_or(0),
+                bytes_received: traffic.map(|stats| stats.bytes_received).unwrap_or(0),
+            });
+        }
+    }
+
+    // Üretilen her bloğun ölçümleri, üretim sırasıyla
+    pub fn block_metrics(&self) -> Vec<BlockMetrics> {
+        let canonical: HashSet<&str> = self.canonical_chain().iter().map(|block| block.hash.as_str()).collect();
+        let node_count = self.nodes.len();
+
+        self.propagation
+            .blocks
+            .iter()
+            .map(|record| {
+                let block = self.nodes.iter().find_map(|node| node.find_block(&record.hash));
+                let interval = block
+                    .and_then(|block| self.propagation.get(&block.previous_hash))
+                    .map(|parent| record.mined_at.saturating_sub(parent.mined_at));
+
+                // Coinbase işlemi sayılmaz
+                let transactions: Vec<&str> = block
+                    .map(|block| {
+                        block.transactions
+                            .iter()
+                            .filter(|tx| !tx.is_coinbase())
+                            .map(|tx| tx.id.as_str())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let latencies: Vec<SimTime> = transactions
+                    .iter()
+                    .filter_map(|id| self.metrics.submitted.get(*id))
+                    .map(|&submitted| record.mined_at.saturating_sub(submitted))
+                    .collect();
+                let tps = interval.filter(|&interval| interval > 0).map(|interval| transactions.len() as f64 * 1000.0 / interval as f64);
+
+                BlockMetrics {
+                    time: record.mined_at,
+                    height: record.index,
+                    hash: record.hash.clone(),
+                    producer: record.producer,
+                    interval,
+                    transactions: transactions.len(),
+                    tps,
+                    confirmation_latency: if latencies.is_empty() {
+                        None
+                    } else {
+                        Some(latencies.iter().sum::<SimTime>() as f64 / latencies.len() as f64)
+                    },
+                    propagation_p50: record.time_to_reach(0.5, node_count),
+                    propagation_p90: record.time_to_reach(0.9, node_count),
+                    propagation_full: record.time_to_reach(1.0, node_count),
+                    reached: record.received_at.len(),
+                    stale: !canonical.contains(record.hash.as_str()),
+                }
+            })
+            .collect()
+    }
+
+    // Blok ve node zaman serilerini "<önek>_blocks.<uzantı>" ve "<önek>_nodes.<uzantı>" dosyalarına yaz
+    pub fn export_metrics(&self, prefix: &str, format: MetricsFormat) -> io::Result<MetricsExport> {
+        let blocks = self.block_metrics();
+        let blocks_path = format!("{}_blocks.{}", prefix, format.extension());
+        let nodes_path = format!("{}_nodes.{}", prefix, format.extension());
+
+        let mut writer = BufWriter::new(File::create(&blocks_path)?);
+        write_rows(&mut writer, &blocks, format)?;
+        writer.flush()?;
+
+        let mut writer = BufWriter::new(File::create(&nodes_path)?);
+        write_rows(&mut writer, &self.metrics.node_samples, format)?;
+        writer.flush()?;
+
+        Ok(MetricsExport {
+            blocks_path,
+            nodes_path,
+            blocks: blocks.len(),
+            node_samples: self.metrics.node_samples.len(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Üretildiği an aralığı, TPS'i ve yayılma süreleri bilinmeyen ilk blok
+    fn genesis_row() -> BlockMetrics {
+        BlockMetrics {
+            time: 0,
+            height: 1,
+            hash: "00ab".to_string(),
+            producer: 2,
+            interval: None,
+            transactions: 0,
+            tps: None,
+            confirmation_latency: None,
+            propagation_p50: Some(120),
+            propagation_p90: None,
+            propagation_full: None,
+            reached: 1,
+            stale: false,
+        }
+    }
+
+    fn written(rows: &[BlockMetrics], format: MetricsFormat) -> Vec<String> {
+        let mut buffer = Vec::new();
+        write_rows(&mut buffer, rows, format).unwrap();
+        String::from_utf8(buffer).unwrap().lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn csv_has_a_header_and_empty_cells_for_missing_values() {
+        let second = BlockMetrics { time: 2000, height: 2, interval: Some(2000), tps: Some(1.5), ..genesis_row() };
+        let lines = written(&[genesis_row(), second], MetricsFormat::Csv);
+        assert_eq!(
+            lines,
+            vec![
+                "time,height,hash,producer,interval_ms,transactions,tps,confirmation_latency_ms,propagation_p50_ms,propagation_p90_ms,propagation_full_ms,reached,stale",
+                "0,1,00ab,2,,0,,,120,,,1,false",
+                "2000,2,00ab,2,2000,0,1.5,,120,,,1,false",
+            ]
+        );
+    }
+
+    #[test]
+    fn csv_without_rows_is_empty() {
+        assert!(written(&[], MetricsFormat::Csv).is_empty());
+    }
+
+    #[test]
+    fn json_lines_write_missing_values_as_null() {
+        let row = BlockMetrics { tps: Some(f64::NAN), ..genesis_row() };
+        let lines = written(&[row], MetricsFormat::JsonLines);
+        assert_eq!(
+            lines,
+            vec![concat!(
+                "{\"time\":0,\"height\":1,\"hash\":\"00ab\",\"producer\":2,\"interval_ms\":null,\"transactions\":0,",
+                "\"tps\":null,\"confirmation_latency_ms\":null,\"propagation_p50_ms\":120,\"propagation_p90_ms\":null,",
+                "\"propagation_full_ms\":null,\"reached\":1,\"stale\":false}"
+            )]
+        );
+    }
+
+    #[test]
+    fn json_text_is_escaped() {
+        assert_eq!(MetricValue::Text("a\"b\\c".to_string()).json(), "\"a\\\"b\\\\c\"");
+    }
+}
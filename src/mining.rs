@@ -0,0 +1,169 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::distribution;
+use crate::error::NetworkError;
+use crate::events::Event;
+use crate::network::{BlockchainNetwork, SimEvent};
+use crate::sim::SimTime;
+
+// Bir sonraki bloğu hangi node'un üreteceğinin belirlenme şekli
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MiningModel {
+    // Her bloktan sonra tek bir validator eşit olasılıkla seçilir ve her block_time saniyede bir sadece o üretir
+    RandomValidator,
+    // Hash gücü olan tüm madenciler aynı anda yarışır; geçerli nonce'u ilk bulan bloğu üretir
+    HashRace,
+}
+
+impl FromStr for MiningModel {
+    type Err = String;
+
+    // "random" veya "race"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "random" | "validator" => Ok(MiningModel::RandomValidator),
+            "race" | "pow" => Ok(MiningModel::HashRace),
+            other => Err(format!("bilinmeyen madencilik modeli: {} (random veya race)", other)),
+        }
+    }
+}
+
+// Madencilerin hash gücü payı ve ürettikleri blok payı
+#[derive(Debug, Clone)]
+pub struct MinerShare {
+    pub node_id: usize,
+    pub hashrate: f64,
+    pub hash_share: f64,  // Çevrimiçi toplam hash gücü içindeki payı (çevrimdışı node için 0)
+    pub blocks: usize,    // Kanonik zincirde ürettiği blok sayısı (genesis hariç)
+    pub block_share: f64, // Kanonik zincirdeki blok payı
+}
+
+impl fmt::Display for MinerShare {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Node {}: hash gücü {} (%{:.1}), {} blok (%{:.1})",
+            self.node_id,
+            self.hashrate,
+            self.hash_share * 100.0,
+            self.blocks,
+            self.block_share * 100.0
+        )
+    }
+}
+
+impl BlockchainNetwork {
+    // Bir node'un hash gücünü ayarla (birimsiz, diğer node'lara göre oransal)
+    // 0 hash gücü olan node madencilik yarışına katılmaz
+    pub fn set_hashrate(&mut self, node_id: usize, hashrate: f64) -> Result<(), NetworkError> {
+        if !hashrate.is_finite() || hashrate < 0.0 {
+            return Err(NetworkError::InvalidHashrate(hashrate.to_string()));
+        }
+        let node = self.nodes.get_mut(node_id).ok_or(NetworkError::NodeNotFound(node_id))?;
+        node.hashrate = hashrate;
+        self.restart_race();
+        Ok(())
+    }
+
+    // Hash güçleri değişti: süren yarışı baştan örnekle
+    // Üstel dağılım hafızasız olduğu için süren yarışı yeni güçlerle baştan örneklemek sonucu bozmaz
+    pub(crate) fn restart_race(&mut self) {
+        if self.mining_active && self.mining_model == MiningModel::HashRace {
+            self.scheduler.retain(|event| !matches!(event, SimEvent::BlockFound { .. }));
+            self.schedule_next_block();
+        }
+    }
+
+    // Ağdaki toplam hash gücü (çevrimdışı node'lar yarışa katılmaz)
+    pub fn total_hashrate(&self) -> f64 {
+        self.nodes.iter().filter(|node| node.online).map(|node| node.hashrate).sum()
+    }
+
+    // Madencilik yarışını örnekle: bloğu ilk bulacak madenci ve bulana kadar geçecek süre (ms)
+    // Her madencinin bir blok bulma süresi, hash gücüyle ters orantılı ortalamaya sahip üstel dağılımdır.
+    // Zorluğun toplam hash gücüne göre ayarlandığı kabul edilir; böylece ağ ortalama block_time saniyede bir blok bulur
+    // ve her madencinin kazanma olasılığı hash gücü payına eşittir.
+    pub(crate) fn sample_race(&mut self) -> Option<(usize, SimTime)> {
+        let total = self.total_hashrate();
+        if total <= 0.0 {
+            return None;
+        }
+
+        let network_mean = (self.block_time * 1000) as f64;
+        let mut winner: Option<(usize, f64)> = None;
+        for (id, node) in self.nodes.iter().enumerate() {
+            if node.hashrate <= 0.0 || !node.online {
+                continue;
+            }
+            let time = distribution::exponential(&mut self.rng, network_mean * total / node.hashrate);
+            if winner.map(|(_, best)| time < best).unwrap_or(true) {
+                winner = Some((id, time));
+            }
+        }
+
+        winner.map(|(id, time)| (id, (time.round() as SimTime).max(1)))
+    }
+
+    // Yarışı kazanan madenciyi bloğu üretecek node olarak işaretle
+    pub(crate) fn set_block_producer(&mut self, miner: usize) {
+        for node in self.nodes.iter_mut() {
+            node.is_validator = node.id == miner;
+        }
+        self.current_validator_id = Some(miner);
+        self.emit(Event::ValidatorSelected { node: miner });
+    }
+
+    // Her madencinin hash gücü payı ile kanonik zincirdeki blok payını karşılaştır
+    pub fn miner_shares(&self) -> Vec<MinerShare> {
+        let mut blocks = vec![0usize; self.nodes.len()];
+        for block in self.canonical_chain().iter().skip(1) {
+            let coinbase = match block.transactions.first().filter(|tx| tx.is_coinbase()) {
+                Some(tx) => tx,
+                None => continue,
+            };
+            let address = &coinbase.outputs[0].recipient_address;
+            if let Some(miner) = self.nodes.iter().position(|node| node.get_address() == address) {
+                blocks[miner] += 1;
+            }
+        }
+
+        let total_hashrate = self.total_hashrate();
+        let total_blocks: usize = blocks.iter().sum();
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(id, node)| MinerShare {
+                node_id: id,
+                hashrate: node.hashrate,
+                // Toplam sadece çevrimiçi node'ları içerdiği için çevrimdışı node'un payı 0'dır; paylar toplamı 1 kalır
+                hash_share: if total_hashrate > 0.0 && node.online { node.hashrate / total_hashrate } else { 0.0 },
+                blocks: blocks[id],
+                block_share: if total_blocks > 0 { blocks[id] as f64 / total_blocks as f64 } else { 0.0 },
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offline_miners_have_no_hash_share() {
+        let mut network = BlockchainNetwork::with_seed(4);
+        for _ in 0..3 {
+            network.add_node();
+        }
+        network.set_hashrate(0, 1.0).unwrap();
+        network.set_hashrate(1, 1.0).unwrap();
+        network.set_hashrate(2, 2.0).unwrap();
+        network.leave_node(2).unwrap();
+
+        let shares = network.miner_shares();
+        assert_eq!(shares[2].hashrate, 2.0);
+        assert_eq!(shares[2].hash_share, 0.0);
+        assert_eq!(shares[0].hash_share, 0.5);
+        assert_eq!(shares.iter().map(|share| share.hash_share).sum::<f64>(), 1.0);
+    }
+}
@@ -1,13 +1,79 @@
-use rand::Rng;
-use std::collections::HashMap;
-use std::time::{SystemTime, UNIX_EPOCH, Duration};
-use std::sync::{Arc, Mutex, mpsc};
-use std::thread;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 // Gerekli modülleri kullan
+use crate::ban::{BanRecord, Offense};
 use crate::block::Block;
-use crate::node::Node;
-use crate::transaction::{Transaction, UTXO};
+use crate::byzantine::ByzantineNode;
+use crate::churn::{ChurnModel, ChurnRecord};
+use crate::consensus::ConsensusEngine;
+use crate::double_spend::DoubleSpendAttack;
+use crate::eclipse::EclipseAttack;
+use crate::events::{Event, EventBus};
+use crate::error::{NetworkError, ValidationError};
+use crate::finality::{BftStep, FinalityState};
+use crate::gossip::{RelayMode, TrafficStats};
+use crate::link::{Link, LinkConfig};
+use crate::message::{InvItem, Message};
+use crate::metrics::MetricsRecorder;
+use crate::mining::MiningModel;
+use crate::node::{BlockStatus, Node};
+use crate::propagation::PropagationTracker;
+use crate::selfish::SelfishMiner;
+use crate::sim::{Scheduler, SimTime};
+use crate::stake::{self, ConsensusMode};
+use crate::transaction::Transaction;
+use crate::workload::WorkloadGenerator;
+
+// Sanal saat üzerinde işlenen simülasyon olayları
+#[derive(Debug, Clone)]
+pub enum SimEvent {
+    // Blok üretim zamanı geldi, seçili validator yeni blok çıkarır
+    BlockArrival,
+    // Bir bağlantı üzerinden gönderilen mesaj hedef node'a ulaştı
+    Deliver { from: usize, to: usize, message: Message },
+    // Planlanmış bir transfer gönderilir
+    SubmitTransaction { sender_id: usize, recipient_id: usize, amount: u64 },
+    // Bir node'un getdata isteği için bekleme süresi doldu
+    RequestTimeout { node: usize, item: InvItem },
+    // Hash yarışında bir madenci geçerli nonce'u buldu
+    BlockFound { miner: usize },
+    // BFT kesinlik katmanında bir node'un tur adımı için bekleme süresi doldu
+    FinalityTimeout { node: usize, height: usize, round: u32, step: BftStep },
+    // Spam gönderen node'un bir sonraki sahte işlem grubunun zamanı geldi
+    Spam { node: usize },
+    // Node'un bir komşusuna koyduğu yasağın süresi doldu
+    BanExpired { node: usize, peer: usize },
+    // Oturum modeline göre node'un çevrimiçi kalma süresi doldu
+    SessionEnd { node: usize },
+    // Oturum modeline göre node'un çevrimdışı kalma süresi doldu
+    SessionStart { node: usize },
+    // İşlem yükünün bir sonraki işleminin zamanı geldi
+    WorkloadArrival,
+    // Senkronizasyon başlatan node'un komşularından başlık bekleme süresi doldu
+    SyncTimeout { node: usize, started_at: SimTime },
+}
+
+// Simülasyon sırasında üretilen bir bloğun kaydı
+#[derive(Debug, Clone)]
+pub struct MinedBlock {
+    pub block: Block,
+    pub validator_id: usize,      // Bloğu üreten validator
+    pub next_validator_id: usize, // Bloktan sonra seçilen validator
+    pub time: SimTime,            // Bloğun üretildiği sanal zaman
+}
+
+// Bir node'un daha uzun bir dala geçerek aktif zincirinden blok geri aldığı an
+#[derive(Debug, Clone)]
+pub struct ReorgRecord {
+    pub node_id: usize,
+    pub time: SimTime,
+    pub reverted: Vec<Block>, // Aktif zincirden çıkarılan bloklar
+    pub new_tip: String,      // Geçilen dalın ucu
+}
 
 pub struct BlockchainNetwork {
     pub nodes: Vec<Node>,
@@ -17,8 +83,43 @@ pub struct BlockchainNetwork {
     pub block_time: u64, // Saniye cinsinden blok oluşturma süresi
     pub last_block_time: u64, // Son bloğun oluşturulduğu zaman
     pub mining_active: bool, // Madencilik aktif mi?
-    pub mining_thread: Option<thread::JoinHandle<()>>, // Madencilik thread'i
-    pub stop_sender: Option<mpsc::Sender<bool>>, // Madencilik durdurma sinyali
+    pub start_time: u64, // Sanal saatin 0 anına karşılık gelen UNIX zamanı (saniye)
+    pub links: HashMap<(usize, usize), Link>, // (gönderen, alıcı) -> tek yönlü bağlantı
+    pub default_link: LinkConfig, // Yeni kurulan bağlantıların ayarları
+    pub propagation: PropagationTracker, // Blok yayılma gecikmeleri ve eskimiş bloklar
+    pub scheduler: Scheduler<SimEvent>, // Ayrık olay zamanlayıcısı
+    pub seed: u64, // Simülasyonun tüm rastgeleliğini belirleyen seed
+    pub relay_mode: RelayMode, // Blok ve işlemlerin komşulara iletilme şekli
+    pub request_timeout: SimTime, // Cevapsız getdata isteğinin başka komşuya yönlendirilme süresi (ms)
+    pub traffic: Vec<TrafficStats>, // Node ID -> gönderilen/alınan trafik
+    pub reorgs: Vec<ReorgRecord>, // Simülasyon boyunca gerçekleşen zincir değişimleri
+    pub healed_at: Option<SimTime>, // Ağ bölünmesinin en son giderildiği zaman
+    pub mining_model: MiningModel, // PoW'da blok üreticisinin belirlenme şekli
+    pub consensus: ConsensusMode, // Blokların mühürlenme ve doğrulanma şekli
+    pub engine: Arc<dyn ConsensusEngine>, // Lider seçimi, mühürleme ve çatal seçimini yapan uzlaşma motoru
+    pub genesis_stake: u64, // PoS genesis bloğunda her node için kilitlenen stake
+    pub finality_validators: Vec<usize>, // BFT kesinlik katmanının validator kümesi (boşsa katman kapalı)
+    pub finality_timeout: SimTime, // BFT turlarında ilk turun adım zaman aşımı (ms)
+    pub selfish: Option<SelfishMiner>, // Bencil madencilik saldırısı sürüyorsa saldırganın durumu
+    pub double_spend: Option<DoubleSpendAttack>, // Son çifte harcama saldırısı ve aşaması
+    pub eclipse: Option<EclipseAttack>, // Son tutulma (eclipse) saldırısı; kurban ve Sybil node'lar
+    pub byzantine: BTreeMap<usize, ByzantineNode>, // Node ID -> ağ katmanındaki kötü niyetli davranışlar ve etkileri
+    pub ban_threshold: u32, // Komşunun bağlantısının kesilip yasaklandığı ceza puanı
+    pub ban_duration: SimTime, // Yasak süresi (ms)
+    pub bans: Vec<BanRecord>, // Simülasyon boyunca konulan yasaklar
+    pub churn: BTreeMap<usize, ChurnModel>, // Node ID -> ağa girip çıkma düzeni (oturum modeli)
+    pub churn_log: Vec<ChurnRecord>, // Ayrılma, çökme ve yeniden başlama kayıtları
+    pub workload: Option<WorkloadGenerator>, // Sentetik işlem yükü ve gönderdiği işlemler
+    pub metrics: MetricsRecorder, // Node durumlarının zaman serisi ve işlemlerin gönderilme anları
+    pub events: EventBus, // Blok, işlem ve validator olaylarının abonelere dağıtıldığı veri yolu
+    pub(crate) rng: StdRng, // Ağ seviyesindeki rastgele sayı üreteci (validator seçimi, node akışları)
+    mined_blocks: Vec<MinedBlock>, // Henüz raporlanmamış üretilen bloklar
+}
+
+impl Default for BlockchainNetwork {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl BlockchainNetwork {
@@ -28,109 +129,221 @@ impl BlockchainNetwork {
             .duration_since(UNIX_EPOCH)
             .expect("Zaman alınamadı")
             .as_secs();
-            
+        
+        // Rastgele bir seed seç; seed alanından okunup sonraki çalıştırmada tekrar kullanılabilir
+        let mut network = Self::with_seed(rand::random());
+        network.start_time = now;
+        network.last_block_time = now;
+        network
+    }
+    
+    // Seed'li ağ oluştur
+    // Aynı seed ile aynı adımlar izlendiğinde anahtarlar, validator seçimleri ve
+    // coinbase nonce'ları dahil tüm sonuçlar birebir aynı olur.
+    // Sanal saat UNIX zamanı 0'dan başlar, böylece blok zaman damgaları da tekrarlanabilir.
+    pub fn with_seed(seed: u64) -> Self {
         BlockchainNetwork {
             nodes: Vec::new(),
             mempool: Vec::new(),
             current_validator_id: None,
             difficulty: 2, // Varsayılan zorluk seviyesi
             block_time: 10, // Varsayılan olarak 10 saniye
-            last_block_time: now, // Başlangıç zamanı
+            last_block_time: 0, // Başlangıç zamanı
             mining_active: false,
-            mining_thread: None,
-            stop_sender: None,
+            start_time: 0,
+            links: HashMap::new(),
+            default_link: LinkConfig::default(),
+            propagation: PropagationTracker::new(),
+            scheduler: Scheduler::new(),
+            mined_blocks: Vec::new(),
+            seed,
+            relay_mode: RelayMode::Announce,
+            request_timeout: 5000,
+            traffic: Vec::new(),
+            reorgs: Vec::new(),
+            healed_at: None,
+            mining_model: MiningModel::HashRace,
+            consensus: ConsensusMode::ProofOfWork,
+            engine: ConsensusMode::ProofOfWork.engine(),
+            genesis_stake: 10_0000_0000, // 10 coin
+            finality_validators: Vec::new(),
+            finality_timeout: 3000,
+            selfish: None,
+            double_spend: None,
+            eclipse: None,
+            byzantine: BTreeMap::new(),
+            ban_threshold: 100,
+            ban_duration: 24 * 60 * 60 * 1000, // 24 saat
+            bans: Vec::new(),
+            churn: BTreeMap::new(),
+            churn_log: Vec::new(),
+            workload: None,
+            metrics: MetricsRecorder::new(),
+            events: EventBus::new(),
+            rng: StdRng::seed_from_u64(seed),
         }
     }
     
+    // Şu anki sanal zaman (ms)
+    pub fn now(&self) -> SimTime {
+        self.scheduler.now()
+    }
+    
+    // Sanal saate karşılık gelen UNIX zaman damgası (saniye)
+    pub fn timestamp(&self) -> u64 {
+        self.start_time + self.scheduler.now() / 1000
+    }
+    
     // Otomatik madencilik işlemini başlat
-    pub fn start_automatic_mining(&mut self) -> Result<(), String> {
+    // Blok üretimleri sanal saat üzerinde her block_time saniyede bir planlanır
+    pub fn start_automatic_mining(&mut self) -> Result<(), NetworkError> {
         if self.mining_active {
-            return Err("Madencilik zaten aktif".to_string());
+            return Err(NetworkError::MiningAlreadyActive);
         }
         
-        // Önce bir validator seçilmiş olmalı
-        if self.current_validator_id.is_none() {
-            return Err("Madencilik başlamadan önce bir validator seçilmelidir".to_string());
-        }
-        
-        // Durdurma sinyali için kanal oluştur
-        let (stop_sender, stop_receiver) = mpsc::channel();
-        self.stop_sender = Some(stop_sender);
-        
-        // Thread için gerekli bilgileri kopyala
-        let block_time = self.block_time;
-        let validator_id = self.current_validator_id;
-        let difficulty = self.difficulty;
-        
-        // Thread'de kullanmak için network'un bir kopyasını oluştur
-        let nodes_clone = self.nodes.clone();
+        // Uzlaşma motorunun ön koşulları (ör. seçili validator veya hash gücü)
+        self.engine.check_ready(self)?;
         
-        // Madencilik thread'ini başlat
-        let mining_thread = thread::spawn(move || {
-            let mut last_mine_time = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .expect("Zaman alınamadı")
-                .as_secs();
-                
-            loop {
-                // Durdurma sinyali geldi mi kontrol et
-                if let Ok(_) = stop_receiver.try_recv() {
-                    println!("Madencilik durduruldu");
-                    break;
-                }
-                
-                // Şu anki zamanı al
-                let now = SystemTime::now()
-                    .duration_since(UNIX_EPOCH)
-                    .expect("Zaman alınamadı")
-                    .as_secs();
-                
-                // Son madencilikten beri geçen süreyi kontrol et
-                if now - last_mine_time >= block_time {
-                    // Son madencilik zamanını güncelle
-                    last_mine_time = now;
-                    
-                    // 1 saniye bekle - madencilik işleminin tamamlanmasını simule et
-                    thread::sleep(Duration::from_secs(1));
-                } else {
-                    // Kısa bir süre bekle
-                    thread::sleep(Duration::from_millis(1000));
-                }
-            }
-        });
-        
-        self.mining_thread = Some(mining_thread);
         self.mining_active = true;
+        self.schedule_next_block();
         
         Ok(())
     }
     
     // Otomatik madencilik işlemini durdur
-    pub fn stop_automatic_mining(&mut self) -> Result<(), String> {
+    pub fn stop_automatic_mining(&mut self) -> Result<(), NetworkError> {
         if !self.mining_active {
-            return Err("Madencilik zaten durdurulmuş".to_string());
+            return Err(NetworkError::MiningNotActive);
+        }
+        
+        // Bekleyen blok üretim olaylarını iptal et
+        self.scheduler.retain(|event| !matches!(event, SimEvent::BlockArrival | SimEvent::BlockFound { .. }));
+        self.mining_active = false;
+        
+        Ok(())
+    }
+    
+    // Bir sonraki blok üretimini uzlaşma motoruna göre planla
+    pub(crate) fn schedule_next_block(&mut self) {
+        let engine = self.engine.clone();
+        engine.schedule_next(self);
+    }
+    
+    // Verilen süre sonra bir blok üretim olayı planla
+    // found_by verilirse olay o üreticinin bloğu bulduğu an olarak işlenir (ör. hash yarışının kazananı)
+    pub fn schedule_block_event(&mut self, delay: SimTime, found_by: Option<usize>) {
+        let event = match found_by {
+            Some(miner) => SimEvent::BlockFound { miner },
+            None => SimEvent::BlockArrival,
+        };
+        self.scheduler.schedule_in(delay, event);
+    }
+    
+    // Bir transferi belirli bir sanal zamanda gönderilmek üzere planla
+    pub fn schedule_transaction(&mut self, at: SimTime, sender_id: usize, recipient_id: usize, amount: u64) {
+        self.scheduler.schedule_at(at, SimEvent::SubmitTransaction { sender_id, recipient_id, amount });
+    }
+    
+    // Verilen sanal zamana kadar tüm olayları işle ve bu sürede üretilen blokları döndür
+    pub fn run_until(&mut self, until: SimTime) -> Vec<MinedBlock> {
+        while let Some((_, event)) = self.scheduler.pop_until(until) {
+            self.handle_event(event);
         }
+        self.scheduler.advance_to(until);
         
-        // Durdurma sinyali gönder
-        if let Some(sender) = &self.stop_sender {
-            if let Err(_) = sender.send(true) {
-                return Err("Madencilik thread'ine sinyal gönderilemedi".to_string());
+        std::mem::take(&mut self.mined_blocks)
+    }
+    
+    // Sanal saati belirli bir süre ilerlet
+    pub fn run_for(&mut self, duration: SimTime) -> Vec<MinedBlock> {
+        self.run_until(self.now().saturating_add(duration))
+    }
+    
+    // Belirtilen sayıda blok üretilene kadar (veya olay kalmayana kadar) simülasyonu çalıştır
+    pub fn run_blocks(&mut self, count: usize) -> Vec<MinedBlock> {
+        while self.mined_blocks.len() < count {
+            match self.scheduler.pop() {
+                Some((_, event)) => self.handle_event(event),
+                None => break,
             }
-        } else {
-            return Err("Durdurma sinyali gönderici bulunamadı".to_string());
         }
         
-        // Thread'in tamamlanmasını bekle
-        if let Some(thread) = self.mining_thread.take() {
-            if let Err(_) = thread.join() {
-                return Err("Madencilik thread'i beklenirken hata oluştu".to_string());
+        // Son bloğun aynı andaki teslimatlarını da tamamla
+        self.process_due_events();
+        
+        std::mem::take(&mut self.mined_blocks)
+    }
+    
+    // Tek bir olayı işle
+    fn handle_event(&mut self, event: SimEvent) {
+        match event {
+            SimEvent::BlockArrival => self.handle_block_event(None),
+            SimEvent::Deliver { from, to, message } => {
+                self.deliver_message(from, to, message);
+            }
+            SimEvent::SubmitTransaction { sender_id, recipient_id, amount } => {
+                // Başarısız transferler (yetersiz bakiye vb.) simülasyonu durdurmaz
+                let recipient_address = self.get_node_address(recipient_id);
+                let _ = self.submit_transaction(sender_id, &recipient_address, amount);
+            }
+            SimEvent::RequestTimeout { node, item } => {
+                self.handle_request_timeout(node, item);
+            }
+            SimEvent::FinalityTimeout { node, height, round, step } => {
+                self.handle_finality_timeout(node, height, round, step);
             }
+            SimEvent::BlockFound { miner } => self.handle_block_event(Some(miner)),
+            SimEvent::Spam { node } => self.handle_spam(node),
+            SimEvent::BanExpired { node, peer } => self.handle_ban_expired(node, peer),
+            SimEvent::SessionEnd { node } => self.handle_session_end(node),
+            SimEvent::SessionStart { node } => self.handle_session_start(node),
+            SimEvent::WorkloadArrival => self.handle_workload_arrival(),
+            SimEvent::SyncTimeout { node, started_at } => self.handle_sync_timeout(node, started_at),
+        }
+    }
+    
+    // Planlanmış bir blok üretim anını işle
+    // Uzlaşma motorunun seçtiği her lider bloğu kendi zincir ucunun üzerine üretir, ardından sonraki üretim planlanır
+    fn handle_block_event(&mut self, found_by: Option<usize>) {
+        if !self.mining_active {
+            return;
         }
         
-        self.mining_active = false;
-        self.stop_sender = None;
+        let engine = self.engine.clone();
+        let leaders = engine.select_leaders(self, found_by).unwrap_or_default();
+        let now = self.now();
+        let mut produced = false;
+        for leader in leaders {
+            // Bu arada çevrimdışı olan lider blok üretemez
+            if !self.is_online(leader) {
+                continue;
+            }
+            self.set_block_producer(leader);
+            if let Ok(block) = self.produce_block() {
+                self.mined_blocks.push(MinedBlock {
+                    block,
+                    validator_id: leader,
+                    next_validator_id: self.current_validator_id.unwrap_or(leader),
+                    time: now,
+                });
+                produced = true;
+            }
+        }
         
-        Ok(())
+        // Her blok üretim anında node'ların durumu zaman serisine eklenir
+        if produced {
+            self.sample_nodes();
+        }
+        
+        self.schedule_next_block();
+    }
+    
+    // Şu ana kadar zamanı gelmiş olayları işle
+    pub(crate) fn process_due_events(&mut self) {
+        let now = self.now();
+        while let Some((_, event)) = self.scheduler.pop_until(now) {
+            self.handle_event(event);
+        }
     }
     
     // Not: Clone trait'i artık kullanmıyoruz, çünkü madencilik thread'i doğrudan ana network'e referans veriyor
@@ -141,8 +354,15 @@ impl BlockchainNetwork {
         
         // Tüm node'ları boş blockchain ile oluştur
         // Genesis bloğu madencilik işlemi sırasında oluşturulacak
-        let node = Node::new(id, None);
+        // Her node'un rastgele sayı akışı ağın üretecinden türetilir
+        let mut node = Node::with_rng(id, None, StdRng::from_rng(&mut self.rng));
+        node.consensus = self.engine.clone();
+        // Kesinlik katmanı açıksa yeni node validator olmadan izleyici olarak katılır
+        if !self.finality_validators.is_empty() {
+            node.finality = Some(FinalityState::new(self.finality_addresses()));
+        }
         self.nodes.push(node);
+        self.traffic.push(TrafficStats::default());
         
         id
     }
@@ -157,66 +377,297 @@ impl BlockchainNetwork {
     }
     
     // Yeni bir işlem oluştur
-    pub fn create_transaction(&mut self, sender_id: usize, recipient_address: &str, amount: u64) -> Option<Transaction> {
-        if let Some(sender_node) = self.nodes.get_mut(sender_id) {
-            // İşlemi oluştur
-            if let Some(tx) = sender_node.create_transaction(recipient_address, amount) {
-                // İşlemi ağ mempool'una ekle
-                self.mempool.push(tx.clone());
-                
-                // İşlemi tüm node'lara yay
-                self.broadcast_transaction(&tx);
-                
-                Some(tx)
-            } else {
-                None
+    pub fn create_transaction(&mut self, sender_id: usize, recipient_address: &str, amount: u64) -> Result<Transaction, NetworkError> {
+        let tx = self.submit_transaction(sender_id, recipient_address, amount)?;
+        
+        // Gecikmesiz bağlantılardaki teslimatları hemen tamamla
+        self.process_due_events();
+        
+        Ok(tx)
+    }
+    
+    // Bir node'dan birden fazla alıcıya tek işlemle ödeme yap (alıcı adresi, miktar)
+    pub fn create_payment(&mut self, sender_id: usize, payments: &[(String, u64)]) -> Result<Transaction, NetworkError> {
+        let tx = self.submit_payment(sender_id, payments)?;
+        self.process_due_events();
+        Ok(tx)
+    }
+    
+    // İşlemi gönderici node'da oluştur ve komşularına yayılmasını planla
+    pub(crate) fn submit_transaction(&mut self, sender_id: usize, recipient_address: &str, amount: u64) -> Result<Transaction, NetworkError> {
+        self.submit_payment(sender_id, &[(recipient_address.to_string(), amount)])
+    }
+    
+    // Ödeme işlemini gönderici node'da oluştur ve komşularına yayılmasını planla
+    pub(crate) fn submit_payment(&mut self, sender_id: usize, payments: &[(String, u64)]) -> Result<Transaction, NetworkError> {
+        let timestamp = self.timestamp();
+        let sender_node = self.nodes.get_mut(sender_id).ok_or(NetworkError::NodeNotFound(sender_id))?;
+        if !sender_node.online {
+            return Err(NetworkError::NodeOffline(sender_id));
+        }
+        
+        // İşlemi oluştur
+        let tx = sender_node.create_payment_at(payments, timestamp)?;
+        let now = self.now();
+        self.metrics.record_submitted(&tx.id, now);
+        self.emit(Event::TxAdmitted { node: sender_id, tx_id: tx.id.clone() });
+        
+        // İşlemi ağ mempool'una ekle
+        self.mempool.push(tx.clone());
+        
+        // İşlemi göndericinin komşularına yay
+        self.broadcast_transaction(sender_id, &tx);
+        
+        Ok(tx)
+    }
+    
+    // İşlemi bir node'un tüm komşularına gönder
+    pub fn broadcast_transaction(&mut self, origin: usize, transaction: &Transaction) {
+        self.relay(origin, Message::Transaction(transaction.clone()));
+    }
+    
+    // İki node arasında bağlantı oluşturma
+    // Her iki yönde de varsayılan ayarlarla bir bağlantı (link) kurulur
+    // Node'lardan biri diğerini yasaklamışsa bağlantı kurulmaz
+    pub fn connect_nodes(&mut self, node1_id: usize, node2_id: usize) -> Result<(), NetworkError> {
+        if node1_id == node2_id {
+            return Err(NetworkError::SelfConnection(node1_id));
+        }
+        
+        if node1_id >= self.nodes.len() || node2_id >= self.nodes.len() {
+            return Err(NetworkError::NodeNotFound(node1_id.max(node2_id)));
+        }
+        
+        if self.is_banned(node1_id, node2_id) {
+            return Err(NetworkError::Banned { node: node1_id, peer: node2_id });
+        }
+        
+        self.nodes[node1_id].add_connection(node2_id);
+        self.nodes[node2_id].add_connection(node1_id);
+        
+        for key in [(node1_id, node2_id), (node2_id, node1_id)] {
+            let config = self.default_link.clone();
+            self.links.entry(key).or_insert_with(|| Link::new(config));
+        }
+        Ok(())
+    }
+    
+    // İki node arasındaki bağlantıyı tamamen kaldır (komşuluk ve iki yöndeki link)
+    // Yolda olan mesajlar da kaybolur; node'lar gerçekten bağlıysa true döner
+    pub fn disconnect_nodes(&mut self, node1_id: usize, node2_id: usize) -> bool {
+        if node1_id >= self.nodes.len() || node2_id >= self.nodes.len() {
+            return false;
+        }
+        
+        let removed = self.nodes[node1_id].remove_connection(node2_id) | self.nodes[node2_id].remove_connection(node1_id);
+        self.links.remove(&(node1_id, node2_id));
+        self.links.remove(&(node2_id, node1_id));
+        self.scheduler.retain(|event| match event {
+            SimEvent::Deliver { from, to, .. } => !((*from == node1_id && *to == node2_id) || (*from == node2_id && *to == node1_id)),
+            _ => true,
+        });
+        removed
+    }
+    
+    // İki node arasındaki bağlantının ayarlarını değiştir (her iki yön)
+    pub fn set_link_config(&mut self, node1_id: usize, node2_id: usize, config: LinkConfig) {
+        for key in [(node1_id, node2_id), (node2_id, node1_id)] {
+            if let Some(link) = self.links.get_mut(&key) {
+                link.config = config.clone();
             }
-        } else {
-            None
         }
     }
     
-    // İşlemi tüm node'lara yay
-    pub fn broadcast_transaction(&mut self, transaction: &Transaction) {
-        // Gönderici node'un adresini al
-        let sender_address = transaction.inputs[0].sender_address.clone();
+    // Tüm bağlantıların ayarlarını değiştir; sonradan kurulacak bağlantılar da bu ayarları kullanır
+    pub fn set_all_link_configs(&mut self, config: LinkConfig) {
+        for link in self.links.values_mut() {
+            link.config = config.clone();
+        }
+        self.default_link = config;
+    }
+    
+    // Bir mesajı bağlantı üzerinden gönder
+    // Gecikme, kayıp ve bant genişliği bağlantı ayarlarına göre uygulanır
+    // Mesaj teslim edilmek üzere yola çıktıysa true, düştüyse (kayıp, bağlantı yok, çevrimdışı node) false döner
+    pub fn send_message(&mut self, from: usize, to: usize, message: Message) -> bool {
+        let now = self.now();
         
-        for node in self.nodes.iter_mut() {
-            // Eğer bu node işlemin göndericisi değilse işlemi doğrula ve mempool'a ekle
-            // Gönderici node zaten işlemi kendi mempool'una eklemiş olacak
-            if node.wallet.get_address() != sender_address {
-                if node.verify_transaction(transaction) {
-                    node.mempool.push(transaction.clone());
+        // Çevrimdışı node mesaj gönderemez ve alamaz
+        if !self.is_online(from) || !self.is_online(to) {
+            return false;
+        }
+        
+        // Bağlantı yoksa mesaj gönderilemez
+        let link = match self.links.get_mut(&(from, to)) {
+            Some(link) => link,
+            None => return false,
+        };
+        
+        let arrival = link.transmit(now, message.size(), &mut self.rng);
+        if let Some(stats) = self.traffic.get_mut(from) {
+            stats.record_sent(&message);
+        }
+        
+        // Geciktiren node mesajı bağlantıya vermeden önce bekletir
+        let delay = self.message_delay(from);
+        if let Some(delay) = delay {
+            self.record_byzantine(from, |effects| {
+                effects.delayed_messages += 1;
+                effects.added_delay += delay;
+            });
+        }
+        
+        match arrival.map(|arrival| arrival + delay.unwrap_or(0)) {
+            Some(arrival) => {
+                self.scheduler.schedule_at(arrival, SimEvent::Deliver { from, to, message });
+                true
+            }
+            None => false,
+        }
+    }
+    
+    // Hedef node'a ulaşan mesajı işle
+    pub(crate) fn deliver_message(&mut self, from: usize, to: usize, message: Message) {
+        // Yasaklı komşudan gelen mesajlar ve çevrimdışı node'a ulaşan mesajlar işlenmez
+        if self.nodes.get(to).map(|node| !node.online || node.banned.contains_key(&from)).unwrap_or(true) {
+            return;
+        }
+        
+        if let Some(stats) = self.traffic.get_mut(to) {
+            stats.record_received(&message);
+        }
+        
+        match message {
+            Message::Block(block) => {
+                self.receive_block_message(from, to, block);
+                self.advance_sync(to);
+            }
+            Message::Transaction(tx) => {
+                let node = match self.nodes.get_mut(to) {
+                    Some(node) => node,
+                    None => return,
+                };
+                
+                node.requested.remove(&tx.id);
+                node.mark_known(from, &tx.id);
+                
+                // Sadece yeni ve geçerli işlemler yayılır
+                match node.receive_transaction(tx.clone()) {
+                    Ok(true) => {
+                        self.emit(Event::TxAdmitted { node: to, tx_id: tx.id.clone() });
+                        self.relay(to, Message::Transaction(tx));
+                    }
+                    Ok(false) => {}
+                    Err(error) => {
+                        self.record_byzantine(from, |effects| effects.rejected_transactions += 1);
+                        self.penalize(to, from, Offense::for_transaction(&error));
+                    }
                 }
             }
+            Message::Inv(items) => self.handle_inv(from, to, items),
+            Message::GetData(items) => self.handle_getdata(from, to, items),
+            Message::GetHeaders { locator } => self.handle_getheaders(from, to, locator),
+            Message::Headers(headers) => self.handle_headers(from, to, headers),
+            Message::Evidence(evidence) => self.handle_evidence(from, to, *evidence),
+            Message::Proposal(proposal) => self.handle_proposal(from, to, *proposal),
+            Message::Vote(vote) => self.handle_vote(from, to, *vote),
         }
     }
     
-    // İki node arasında bağlantı oluşturma
-    pub fn connect_nodes(&mut self, node1_id: usize, node2_id: usize) {
-        if node1_id == node2_id {
-            println!("Warning: Cannot connect a node to itself.");
+    // Komşudan gelen bloğu node'a işlet, kabul edilirse (ve bağlanan yetimlerle birlikte) yay
+    fn receive_block_message(&mut self, from: usize, to: usize, block: Block) {
+        let difficulty = self.difficulty;
+        let now = self.now();
+        let clock = self.timestamp();
+        
+        // Gizli dalında madencilik yapan çifte harcayıcı dürüst blokları kabul etmez
+        if self.is_double_spender(to) {
             return;
         }
-
-        if let Some(node1) = self.nodes.get_mut(node1_id) {
-            node1.add_connection(node2_id);
-        } else {
-            println!("Warning: Node {} not found.", node1_id);
+        
+        let node = match self.nodes.get_mut(to) {
+            Some(node) => node,
+            None => return,
+        };
+        node.requested.remove(&block.hash);
+        node.mark_known(from, &block.hash);
+        let parent = block.previous_hash.clone();
+        let hash = block.hash.clone();
+        
+        // Geçersiz, yinelenen veya yetim bloklar yayılmaz
+        // Zaman damgası node'un saatinin (sanal saat) izin verilen sapmadan ilerisindeki bloklar baştan reddedilir
+        let known_evidence = node.evidence_pool.len();
+        let result = match node.consensus.max_clock_drift() {
+            Some(drift) if block.timestamp > clock.saturating_add(drift) => {
+                Err(ValidationError::TimestampInFuture { index: block.index, timestamp: block.timestamp, now: clock })
+            }
+            _ => node.receive_block(block.clone(), difficulty),
+        };
+        
+        // Blok çifte imzayı ortaya çıkardıysa kanıt komşulara yayılır
+        let detected: Vec<_> = node.evidence_pool.iter().skip(known_evidence).cloned().collect();
+        for evidence in &detected {
+            self.relay_evidence(to, evidence);
         }
         
-        if let Some(node2) = self.nodes.get_mut(node2_id) {
-            node2.add_connection(node1_id);
-        } else {
-            println!("Warning: Node {} not found.", node2_id);
+        let status = match result {
+            Ok(status) => status,
+            Err(error) => {
+                // Geçersiz blok gönderen komşudan senkronizasyon sırasında başka gövde istenmez
+                if let Some(sync) = self.nodes[to].sync.as_mut() {
+                    sync.peers.retain(|&peer| peer != from);
+                }
+                self.emit(Event::BlockRejected { node: to, from, hash, reason: error.to_string() });
+                self.record_byzantine(from, |effects| effects.rejected_blocks += 1);
+                self.penalize(to, from, Offense::for_block(&error));
+                return;
+            }
+        };
+        let node = &mut self.nodes[to];
+        
+        // Ebeveyni bilinmeyen blok için eksik ebeveyn, bloğu gönderen komşudan istenir
+        if let BlockStatus::Orphan = status {
+            node.mark_known(from, &parent);
+            if !node.requested.contains_key(&parent) {
+                self.request_items(to, from, vec![InvItem::Block(parent)]);
+            }
+            return;
+        }
+        if !status.is_accepted() {
+            return;
+        }
+        
+        // Ebeveyni gelen bloğu bekleyen yetimler de artık bağlanabilir
+        let mut accepted = vec![(block, status)];
+        accepted.extend(node.process_orphans(difficulty));
+        
+        // Blok hop hop yayılır: kabul eden node onu kendi komşularına iletir
+        for (block, status) in accepted {
+            self.emit(Event::BlockAccepted { node: to, from, height: block.index, hash: block.hash.clone() });
+            if let BlockStatus::Reorganized { reverted } = status {
+                self.emit(Event::Reorg { node: to, depth: reverted.len(), new_tip: block.hash.clone() });
+                self.reorgs.push(ReorgRecord {
+                    node_id: to,
+                    time: now,
+                    reverted,
+                    new_tip: block.hash.clone(),
+                });
+            }
+            self.propagation.record_received(&block.hash, to, now);
+            self.relay(to, Message::Block(block));
         }
+        
+        // Satıcının onay sayısı arttıysa çifte harcayıcı gizli dalını yayınlayabilir
+        self.advance_double_spend();
+        
+        // Yeni blok bir BFT turunu başlatabilir veya bekleyen bir öneriyi oylanabilir kılabilir
+        self.drive_finality(to);
     }
     
     // Rasgele bir validator seç
-    pub fn select_random_validator(&mut self) {
+    pub fn select_random_validator(&mut self) -> Result<usize, NetworkError> {
         if self.nodes.is_empty() {
-            println!("Warning: No nodes available to select as validator.");
-            return;
+            return Err(NetworkError::NoNodes);
         }
 
         // Önce tüm node'ları validator olmaktan çıkar
@@ -224,78 +675,99 @@ impl BlockchainNetwork {
             node.is_validator = false;
         }  
 
-        // Rasgele bir node seç
-        let mut rng = rand::thread_rng();
-        let validator_id = rng.gen_range(0..self.nodes.len());
-
-        if let Some(node) = self.nodes.get_mut(validator_id) {
-            node.is_validator = true;
-            self.current_validator_id = Some(validator_id);
-            println!("Node {} is selected as the new validator.", validator_id);
+        // Rasgele bir çevrimiçi node seç
+        let online: Vec<usize> = self.nodes.iter().filter(|node| node.online).map(|node| node.id).collect();
+        if online.is_empty() {
+            return Err(NetworkError::NoNodes);
         }
+        let validator_id = online[self.rng.random_range(0..online.len())];
+
+        let node = self.nodes.get_mut(validator_id).ok_or(NetworkError::NodeNotFound(validator_id))?;
+        node.is_validator = true;
+        self.current_validator_id = Some(validator_id);
+        self.emit(Event::ValidatorSelected { node: validator_id });
+        
+        Ok(validator_id)
     }
 
     // Madencilik yaparak yeni bir blok oluştur
-    pub fn mine_block(&mut self) -> Option<Block> {
-        // Şu anki zamanı al
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("Zaman alınamadı")
-            .as_secs();
+    // Bloğu üretecek node uzlaşma motoruna göre seçilir (rastgele validator, hash yarışı veya slot önericisi)
+    pub fn mine_block(&mut self) -> Result<Block, NetworkError> {
+        let engine = self.engine.clone();
         
-        // Son blok zamanını güncelle
-        self.last_block_time = now;
+        // Zincir yoksa motor kendine özgü bir genesis oluşturabilir (ör. PoS'ta stake'li genesis)
+        let genesis = if self.nodes.iter().all(|node| node.blockchain.is_empty()) {
+            engine.create_genesis(self)
+        } else {
+            None
+        };
         
-        if let Some(validator_id) = self.current_validator_id {
-            // Validator'u al
-            let validator = match self.nodes.get_mut(validator_id) {
-                Some(v) => v,
-                None => {
-                    println!("Validator bulunamadı!");
-                    return None;
-                }
-            };
-            
-            // Mempool'dan işlemleri al ve yeni bir blok oluştur
-            // Önce ağ mempool'undan validator'un mempool'una işlemleri aktar
-            let mempool_copy = self.mempool.clone();
-            for tx in &mempool_copy {
-                if validator.verify_transaction(tx) {
-                    validator.mempool.push(tx.clone());
+        let block = match genesis {
+            Some(genesis) => genesis?,
+            None => {
+                // Birden fazla lider çıkarsa (ör. farklı zincir uçlarındaki PoS önericileri) hepsi üretir, ilk blok döner
+                let mut first = None;
+                let mut error = NetworkError::NoProposer;
+                for leader in engine.select_leaders(self, None)? {
+                    self.set_block_producer(leader);
+                    match self.produce_block() {
+                        Ok(block) => {
+                            first.get_or_insert(block);
+                        }
+                        Err(e) => error = e,
+                    }
                 }
+                first.ok_or(error)?
             }
-            
-            // Validator'un madencilik yapmasını iste
-            let new_block = validator.create_block(self.difficulty);
-            
-            if let Some(block) = &new_block {
-                // Son blok zamanını güncelle
-                self.last_block_time = now;
-                
-                // İşlemleri ağ mempool'undan çıkar
-                self.mempool.retain(|tx| {
-                    !block.transactions.iter().any(|block_tx| block_tx.id == tx.id)
-                });
-                
-                // Validator'un blockchain'ine bloğu ekle
-                validator.blockchain.push(block.clone());
-                validator.update_utxo_set(block);
-                validator.wallet.update_utxos(&block.transactions);
-                
-                // Yeni bloğu tüm node'lara yay
-                self.broadcast_block(block);
-                
-                // Yeni bir validator seç
-                self.select_random_validator();
-            } else {
-                println!("Blok oluşturulamadı!");
-            }
-            
-            new_block
-        } else {
-            println!("Madencilik için seçili validator yok!");
-            None
+        };
+        
+        // Gecikmesiz yayılan blokları hemen teslim et
+        self.process_due_events();
+        
+        Ok(block)
+    }
+    
+    // Seçili validator ile blok üret ve yayılmasını planla
+    pub(crate) fn produce_block(&mut self) -> Result<Block, NetworkError> {
+        // Sanal saate göre zaman damgası
+        let timestamp = self.timestamp();
+        let difficulty = self.difficulty;
+        
+        let validator_id = self.current_validator_id.ok_or(NetworkError::NoValidatorSelected)?;
+        
+        // Validator'u al
+        let validator = self.nodes.get_mut(validator_id).ok_or(NetworkError::NodeNotFound(validator_id))?;
+        
+        // Validator kendi mempool'undaki işlemlerle madencilik yapar ve bloğu kendi zincirine ekler
+        // Blok reddedilirse işlemler validator'un mempool'unda kalır
+        let block = validator.mine_block_at(difficulty, timestamp)?;
+        
+        // Son blok zamanını güncelle
+        self.last_block_time = timestamp;
+        let transactions = block.transactions.iter().filter(|tx| !tx.is_coinbase()).count();
+        self.emit(Event::BlockMined { node: validator_id, height: block.index, hash: block.hash.clone(), transactions });
+        
+        // İşlemleri ağ mempool'undan çıkar
+        self.mempool.retain(|tx| {
+            !block.transactions.iter().any(|block_tx| block_tx.id == tx.id)
+        });
+        
+        // Yeni bloğu validator'un komşularına yay; bencil madencinin blokları gizli tutulur ve stratejiye göre yayınlanır
+        if self.is_selfish_miner(validator_id) {
+            self.selfish_private_block();
+        } else if !self.is_double_spender(validator_id) {
+            self.propagation.record_mined(&block, validator_id, self.now());
+            self.broadcast_block(validator_id, &block);
+            self.drive_finality(validator_id);
+            self.selfish_public_block(validator_id, &block);
         }
+        self.advance_double_spend();
+        
+        // Motora özgü blok sonrası adımlar (ör. rastgele validator modelinde yeni validator seçimi)
+        let engine = self.engine.clone();
+        engine.after_block(self, validator_id)?;
+        
+        Ok(block)
     }
 
     // Hash'i tüm bağlı node'lara gönder
@@ -306,18 +778,59 @@ impl BlockchainNetwork {
         println!("Broadcasted hash {} to all nodes.", hash);
     }
     
-    // Yeni bir bloğu tüm node'lara yay
-    pub fn broadcast_block(&mut self, block: &Block) {
-        for (id, node) in self.nodes.iter_mut().enumerate() {
-            if let Some(validator_id) = self.current_validator_id {
-                if id != validator_id { // Validator dışındaki tüm node'lara
-                    let _ = node.add_block_from_network(block.clone(), self.difficulty);
-                }
-            } else {
-                // Validator seçilmemişse tüm node'lara gönder
-                let _ = node.add_block_from_network(block.clone(), self.difficulty);
+    // Yeni bir bloğu node'un komşularına yay
+    // Blok bağlantılar üzerinden hop hop ilerler; her node kabul ettiği bloğu kendi komşularına iletir
+    pub fn broadcast_block(&mut self, origin: usize, block: &Block) {
+        self.relay(origin, Message::Block(block.clone()));
+    }
+    
+    // Node'a, kaynak node'un zincirinde bilmediği blokları bağlantıları atlayarak sırayla işlet
+    // Ağı doğrudan gözleyen saldırganların dürüst zinciri anında öğrenmesi için kullanılır
+    pub(crate) fn catch_up(&mut self, node_id: usize, source: usize) {
+        let difficulty = self.difficulty;
+        let mut missing: Vec<Block> = self.nodes[source]
+            .blockchain
+            .iter()
+            .rev()
+            .take_while(|block| !self.nodes[node_id].knows_block(&block.hash))
+            .cloned()
+            .collect();
+        missing.reverse();
+        for block in missing {
+            let _ = self.nodes[node_id].receive_block(block, difficulty);
+        }
+    }
+    
+    // En uzun zincire sahip node'un zinciri (eşitlikte en küçük ID)
+    pub fn canonical_chain(&self) -> &[Block] {
+        let mut best: &[Block] = &[];
+        for node in &self.nodes {
+            if node.blockchain.len() > best.len() {
+                best = &node.blockchain;
+            }
+        }
+        best
+    }
+    
+    // Saldırgan dışındaki node'ların en uzun zinciri (eşitlikte en küçük ID)
+    pub(crate) fn honest_chain(&self, attacker: usize) -> &[Block] {
+        let mut best: &[Block] = &[];
+        for node in self.nodes.iter().filter(|node| node.id != attacker) {
+            if node.blockchain.len() > best.len() {
+                best = &node.blockchain;
             }
         }
+        best
+    }
+    
+    // Kanonik zincire giremeyen (eskimiş) blokların oranı
+    pub fn stale_block_rate(&self) -> f64 {
+        self.propagation.stale_rate(self.canonical_chain())
+    }
+    
+    // Blokların node'ların belirli bir oranına ulaşma süresinin ortalaması (ms)
+    pub fn average_propagation_delay(&self, fraction: f64) -> Option<f64> {
+        self.propagation.average_time_to_reach(fraction, self.nodes.len())
     }
 
     // Blockchain'i tüm node'lara yayınla
@@ -325,16 +838,17 @@ impl BlockchainNetwork {
         for (id, node) in self.nodes.iter_mut().enumerate() {
             if let Some(validator_id) = self.current_validator_id {
                 if id != validator_id { // Validator dışındaki tüm node'lara
-                    node.update_blockchain(blockchain.clone(), self.difficulty);
+                    let _ = node.update_blockchain(blockchain.clone(), self.difficulty);
                 }
             } else {
                 // Validator seçilmemişse tüm node'lara gönder
-                node.update_blockchain(blockchain.clone(), self.difficulty);
+                let _ = node.update_blockchain(blockchain.clone(), self.difficulty);
             }
         }
     }
 
     // Bir node'un hash'ini manipüle etmeyi dene
+    #[cfg(feature = "attacks")]
     pub fn try_manipulate_hash(&mut self, node_id: usize, fake_hash: String) -> bool {
         if let Some(validator_id) = self.current_validator_id {
             if node_id == validator_id {
@@ -381,13 +895,11 @@ impl BlockchainNetwork {
     }
 
     // Bir node'un blockchain'ini manipüle etmeyi dene
+    #[cfg(feature = "attacks")]
     pub fn try_manipulate_blockchain(&mut self, node_id: usize, custom_hash: Option<String>) -> bool {
-        let mut manipulation_successful = false;
-        let mut manipulated_chain_is_valid = true;
-        let mut last_block_index = 0;
-        
         // Önce node'un blockchain'ini al
         let difficulty = self.difficulty; // Zorluk seviyesini al
+        let timestamp = self.timestamp();
         
         {
             let node = match self.nodes.get_mut(node_id) {
@@ -402,7 +914,7 @@ impl BlockchainNetwork {
             
             // Son bloğu al
             let last_block = node.blockchain.last().unwrap();
-            last_block_index = last_block.index;
+            let original_block = last_block.clone();
             
             // Eğer özel bir hash verilmişse, son bloğun hash'ini değiştir
             if let Some(hash) = custom_hash {
@@ -413,11 +925,9 @@ impl BlockchainNetwork {
                 manipulated_block.hash = hash;
                 
                 // Son bloğu değiştir
+                // Bu durumda zincir geçersiz olacak
                 node.blockchain.pop();
                 node.blockchain.push(manipulated_block);
-                
-                // Bu durumda zincir geçersiz olacak
-                manipulated_chain_is_valid = false;
             } else {
                 // Özel hash verilmemişse, son bloğun içeriğini değiştir ama hash'i yeniden hesapla
                 println!("Attempting to manipulate Node {}'s blockchain by changing the last block content and recalculating hash.", node_id);
@@ -425,11 +935,8 @@ impl BlockchainNetwork {
                 // Yeni bir blok oluştur
                 let mut manipulated_block = last_block.clone();
                 
-                // Bloğun timestamp'ini değiştir
-                manipulated_block.timestamp = SystemTime::now()
-                    .duration_since(UNIX_EPOCH)
-                    .expect("Time went backwards")
-                    .as_secs();
+                // Bloğun timestamp'ini değiştir (sanal saate göre, en az 1 saniye ileri)
+                manipulated_block.timestamp = timestamp.max(last_block.timestamp + 1);
                 
                 // Hash'i yeniden hesapla
                 manipulated_block.hash = manipulated_block.calculate_hash();
@@ -438,20 +945,23 @@ impl BlockchainNetwork {
                 manipulated_block.mine_block(difficulty);
                 
                 // Son bloğu değiştir
+                // Bu durumda zincir geçerli olacak (PoW yapıldığı için)
                 node.blockchain.pop();
                 node.blockchain.push(manipulated_block);
-                
-                // Bu durumda zincir geçerli olacak (PoW yapıldığı için)
-                manipulated_chain_is_valid = true;
             }
             
             // Zincirin geçerliliğini kontrol et
-            if !node.is_chain_valid_with_difficulty(&node.blockchain, difficulty) {
-                println!("Node {}'s manipulated blockchain is invalid.", node_id);
-                manipulated_chain_is_valid = false;
-            } else {
-                println!("Node {}'s manipulated blockchain is still valid (has valid PoW).", node_id);
-                manipulated_chain_is_valid = true;
+            match node.validate_chain(&node.blockchain, difficulty) {
+                Ok(()) => println!("Node {}'s manipulated blockchain is still valid (has valid PoW).", node_id),
+                Err(e) => println!("Node {}'s manipulated blockchain is invalid: {}", node_id, e),
+            }
+            
+            // Kesinlik katmanı açıksa karar çoğunluk sayımına değil, 2/3+ kesin oyla kesinleşmiş bloğa dayanır
+            if node.finalized_height().map(|finalized| original_block.index <= finalized).unwrap_or(false) {
+                println!("Manipulation rejected! Block #{} was finalized by 2/3+ precommits of the validator set and can never be replaced.", original_block.index);
+                node.blockchain.pop();
+                node.blockchain.push(original_block);
+                return false;
             }
         }
         
@@ -478,8 +988,10 @@ impl BlockchainNetwork {
             let valid_blockchain = if let Some((source_id, blockchain)) = valid_blockchain_source {
                 // Geçerli bir zinciri manipüle edilen node'a gönder
                 if let Some(node) = self.nodes.get_mut(node_id) {
-                    node.update_blockchain(blockchain.clone(), self.difficulty);
-                    println!("Node {}'s blockchain restored from Node {}.", node_id, source_id);
+                    match node.update_blockchain(blockchain.clone(), self.difficulty) {
+                        Ok(_) => println!("Node {}'s blockchain restored from Node {}.", node_id, source_id),
+                        Err(e) => println!("Node {}'s blockchain could not be restored: {}", node_id, e),
+                    }
                 }
                 Some(blockchain)
             } else {
@@ -492,13 +1004,11 @@ impl BlockchainNetwork {
                 println!("Valid blockchain broadcasted to all nodes to ensure consistency.");
             }
             
-            manipulation_successful = false;
+            false
         } else {
             println!("WARNING: Manipulation successful! Node {}'s manipulated blockchain (with valid PoW) is accepted.", node_id);
-            manipulation_successful = true;
+            true
         }
-        
-        manipulation_successful
     }
 
     // Ağın durumunu görüntüle
@@ -509,8 +1019,20 @@ impl BlockchainNetwork {
             let balance = node.wallet.get_balance();
             let blockchain_len = node.blockchain.len();
             let is_validator = if Some(id) == self.current_validator_id { "(Validator)" } else { "" };
-            println!("Node {}{}: {} coin, Blockchain Length: {}", 
-                id, is_validator, balance as f64 / 100_000_000.0, blockchain_len);
+            match self.consensus {
+                ConsensusMode::ProofOfWork => println!("Node {}{}: {} coin, Blockchain Length: {}, Hashrate: {}", 
+                    id, is_validator, balance as f64 / 100_000_000.0, blockchain_len, node.hashrate),
+                ConsensusMode::ProofOfStake => {
+                    let staked = stake::stakes(&node.utxo_set).get(node.get_address()).copied().unwrap_or(0);
+                    println!("Node {}{}: {} coin, Stake: {} coin, Blockchain Length: {}", 
+                        id, is_validator, balance as f64 / 100_000_000.0, staked as f64 / 100_000_000.0, blockchain_len)
+                }
+            }
+            
+            // Senkronizasyonu süren node'ların ilerlemesi
+            if let Some(progress) = self.sync_progress(id).filter(|progress| !progress.complete) {
+                println!("    Senkronizasyon: {}", progress);
+            }
         }
         println!("---------------------------------\n");
     }
@@ -559,3 +1081,117 @@ impl BlockchainNetwork {
         hashes
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::events::EventRecord;
+    use crate::metrics::{write_rows, MetricsFormat};
+    use crate::scenario::Scenario;
+
+    const SCENARIO: &str = r#"
+name = "tekrarlanabilirlik"
+seed = 7
+duration = 600000
+
+[network]
+nodes = 5
+difficulty = 1
+block_time = 30
+topology = "regular:2"
+
+[network.link]
+latency = "normal:200:50"
+loss_rate = 0.05
+
+[[events]]
+at = 60000
+action = "transfer"
+from = 0
+to = 3
+amount = 1.5
+
+[[events]]
+at = 120000
+action = "partition"
+groups = [[0, 1], [2, 3, 4]]
+
+[[events]]
+at = 300000
+action = "heal"
+
+[[events]]
+at = 350000
+action = "crash"
+node = 4
+
+[[events]]
+at = 450000
+action = "restart"
+node = 4
+
+[metrics]
+collect = ["chain", "propagation", "miners", "reorgs", "traffic", "partition", "churn"]
+"#;
+
+    // Bir çalıştırmanın karşılaştırılabilir özeti
+    #[derive(Debug, PartialEq)]
+    struct Run {
+        chains: Vec<Vec<String>>,
+        mempools: Vec<Vec<String>>,
+        blocks: String,
+        nodes: String,
+        events: Vec<String>,
+        errors: Vec<String>,
+    }
+
+    fn run(scenario: &Scenario) -> Run {
+        let mut network = scenario.build_network().expect("ağ kurulamadı");
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&events);
+        network.events.subscribe(move |record: &EventRecord| log.lock().unwrap().push(record.to_json()));
+
+        let mut errors = Vec::new();
+        for event in &scenario.events {
+            network.run_until(event.at);
+            if let Err(e) = event.action.apply(&mut network) {
+                errors.push(format!("{} ms, {}: {}", event.at, event.action.name(), e));
+            }
+        }
+        network.run_until(scenario.duration);
+
+        let mut blocks = Vec::new();
+        let mut nodes = Vec::new();
+        write_rows(&mut blocks, &network.block_metrics(), MetricsFormat::JsonLines).unwrap();
+        write_rows(&mut nodes, &network.metrics.node_samples, MetricsFormat::JsonLines).unwrap();
+        let events = events.lock().unwrap().clone();
+        Run {
+            chains: network.nodes.iter().map(|node| node.blockchain.iter().map(|block| block.hash.clone()).collect()).collect(),
+            mempools: network.nodes.iter().map(|node| node.mempool.iter().map(|tx| tx.id.clone()).collect()).collect(),
+            blocks: String::from_utf8(blocks).unwrap(),
+            nodes: String::from_utf8(nodes).unwrap(),
+            events,
+            errors,
+        }
+    }
+
+    #[test]
+    fn same_seed_reproduces_chains_and_metrics() {
+        let scenario: Scenario = SCENARIO.parse().unwrap();
+        let first = run(&scenario);
+        assert!(first.errors.is_empty(), "{:?}", first.errors);
+        assert!(first.chains.iter().all(|chain| chain.len() > 5), "yeterli blok üretilmedi");
+        assert_eq!(first, run(&scenario));
+
+        let summary = scenario.run().unwrap();
+        assert_eq!(summary.to_toml(), scenario.run().unwrap().to_toml());
+    }
+
+    #[test]
+    fn different_seed_changes_the_run() {
+        let scenario: Scenario = SCENARIO.parse().unwrap();
+        let other = Scenario { seed: Some(8), ..scenario.clone() };
+        assert_ne!(run(&scenario).chains, run(&other).chains);
+    }
+}
@@ -1,23 +1,74 @@
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 // Gerekli modülleri kullan
-use crate::block::Block;
+use crate::block::{Block, BlockHeader};
+use crate::error::{EvidenceError, NetworkError, TransactionError, ValidationError};
+use crate::message::InvItem;
+use crate::sim::SimTime;
+use crate::finality::FinalityState;
+use crate::slashing::{self, EquivocationEvidence};
+use crate::consensus::ConsensusEngine;
+use crate::stake::{self, ConsensusMode};
+use crate::sync::SyncState;
 use crate::wallet::Wallet;
-use crate::transaction::{Transaction, UTXO, TxInput, TxOutput, get_utxo_id};
+use crate::transaction::{Transaction, UTXO, get_utxo_id};
+
+// Bir bloğa girebilecek en fazla işlem sayısı (coinbase dahil)
+pub const BLOCK_TRANSACTION_LIMIT: usize = 10;
 
 //Node sınıfı
 #[derive(Debug, Clone)]
 pub struct Node {
     pub id: usize,
     pub connections: Vec<usize>, // Bağlı nodeların id'leri
+    pub netgroup: u16,           // Node'un IP adres bloğu (/16); çeşitli bağlantı seçimi bu bloklara bakar
     pub is_validator: bool,
     pub blockchain: Vec<Block>,  // Blok zinciri
     pub wallet: Wallet,         // Cüzdan
     pub mempool: Vec<Transaction>, // Henüz bloklara eklenmemiş işlemler
     pub utxo_set: Vec<UTXO>,    // Tüm harcanmamış çıktılar (UTXO seti)
     pub mining_reward: u64,     // Madencilik ödülü
+    pub rng: StdRng,            // Node'a özel rastgele sayı akışı (anahtarlar, coinbase nonce'ları)
+    pub block_store: HashMap<String, Block>, // Bilinen tüm geçerli bloklar (yan dallar dahil)
+    pub orphan_blocks: Vec<Block>, // Ebeveyni henüz bilinmeyen bloklar
+    pub known_inventory: HashMap<usize, HashSet<String>>, // Komşu ID -> o komşunun bildiği blok/işlem hash'leri
+    pub requested: HashMap<String, (usize, SimTime)>, // İstenmiş ama henüz gelmemiş öğeler: hash -> (komşu, istek zamanı)
+    pub sync: Option<SyncState>, // İlk senkronizasyon durumu (ağa sonradan katılan node'lar için)
+    pub hashrate: f64,           // Madencilik yarışındaki hash gücü (diğer node'lara göre oransal)
+    pub consensus: Arc<dyn ConsensusEngine>, // Blokların mühürlenme, doğrulanma ve çatal seçimi kuralları
+    pub evidence_pool: Vec<EquivocationEvidence>, // Henüz bloğa girmemiş çifte imza kanıtları
+    pub last_signed_height: Option<usize>, // PoS: imzalanan son blok yüksekliği (çifte imza koruması)
+    pub finality: Option<FinalityState>, // BFT kesinlik katmanı açıksa tur durumu ve kesinleşmiş yükseklik
+    pub ban_scores: HashMap<usize, u32>, // Komşu ID -> kötü davranışlarla biriken ceza puanı
+    pub banned: HashMap<usize, SimTime>, // Yasaklanan komşu ID -> yasağın bittiği sanal zaman
+    pub online: bool,            // Node ağda mı; çevrimdışı node mesaj almaz, göndermez ve blok üretmez
+}
+
+// Ağdan gelen bir bloğun node tarafından nasıl işlendiği
+#[derive(Debug, Clone)]
+pub enum BlockStatus {
+    // Blok aktif zincirin ucuna eklendi
+    Extended,
+    // Blok daha uzun bir dalı tamamladı ve node o dala geçti; geri alınan bloklar döner
+    Reorganized { reverted: Vec<Block> },
+    // Blok geçerli ama aktif zincirden uzun olmayan bir dalda
+    SideChain,
+    // Bloğun ebeveyni henüz bilinmiyor
+    Orphan,
+    // Blok zaten biliniyor
+    Duplicate,
+}
+
+impl BlockStatus {
+    // Blok node tarafından yeni ve geçerli olarak kabul edildi mi (yayılmaya değer mi)
+    pub fn is_accepted(&self) -> bool {
+        matches!(self, BlockStatus::Extended | BlockStatus::Reorganized { .. } | BlockStatus::SideChain)
+    }
 }
 
 impl fmt::Display for Node {
@@ -30,7 +81,12 @@ impl fmt::Display for Node {
 
 impl Node {
     pub fn new(id: usize, genesis_block: Option<Block>) -> Self {
-        let wallet = Wallet::new(); // Yeni bir cüzdan oluştur
+        Self::with_rng(id, genesis_block, StdRng::from_rng(&mut rand::rng()))
+    }
+    
+    // Verilen rastgele sayı akışıyla node oluştur (tekrarlanabilir simülasyonlar için)
+    pub fn with_rng(id: usize, genesis_block: Option<Block>, mut rng: StdRng) -> Self {
+        let wallet = Wallet::from_rng(&mut rng); // Yeni bir cüzdan oluştur
         let mut blockchain = Vec::new();
         let mut utxo_set = Vec::new();
         let mut wallet_clone = wallet.clone();
@@ -62,12 +118,27 @@ impl Node {
         Node {
             id,
             connections: Vec::new(),
+            netgroup: id as u16, // Varsayılan olarak her node farklı bir adres bloğundadır
             is_validator: false,
             blockchain,
             wallet: wallet_clone,
             mempool: Vec::new(),
             utxo_set,
             mining_reward: 50_0000_0000, // 50 coin (BTC'de olduğu gibi)
+            rng,
+            block_store: HashMap::new(),
+            orphan_blocks: Vec::new(),
+            known_inventory: HashMap::new(),
+            requested: HashMap::new(),
+            sync: None,
+            hashrate: 1.0,
+            consensus: ConsensusMode::ProofOfWork.engine(),
+            evidence_pool: Vec::new(),
+            last_signed_height: None,
+            finality: None,
+            ban_scores: HashMap::new(),
+            banned: HashMap::new(),
+            online: true,
         }
     }
 
@@ -82,165 +153,277 @@ impl Node {
     }
     
     // İşlem oluştur ve mempool'a ekle
-    pub fn create_transaction(&mut self, recipient_address: &str, amount: u64) -> Option<Transaction> {
-        // Cüzdanın işlem oluşturmasını iste
-        if let Some(transaction) = self.wallet.create_transaction(recipient_address, amount) {
-            // İşlemi doğrula
-            if self.verify_transaction(&transaction) {
-                // İşlemi mempool'a ekle
-                self.mempool.push(transaction.clone());
-                Some(transaction)
-            } else {
-                // Transaction doğrulanamadı
-                None
-            }
-        } else {
-            // Transaction oluşturulamadı
-            None
-        }
+    pub fn create_transaction(&mut self, recipient_address: &str, amount: u64) -> Result<Transaction, TransactionError> {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        self.create_transaction_at(recipient_address, amount, timestamp)
     }
     
-    // İşlemi doğrula
-    pub fn verify_transaction(&self, transaction: &Transaction) -> bool {
-        // Coinbase işlemleri her zaman geçerlidir
-        if transaction.inputs.is_empty() && !transaction.outputs.is_empty() {
-            return true;
-        }
+    // Verilen zaman damgasıyla işlem oluştur ve mempool'a ekle
+    pub fn create_transaction_at(&mut self, recipient_address: &str, amount: u64, timestamp: u64) -> Result<Transaction, TransactionError> {
+        self.create_payment_at(&[(recipient_address.to_string(), amount)], timestamp)
+    }
+    
+    // Birden fazla alıcıya ödeyen işlem oluştur ve mempool'a ekle (alıcı adresi, miktar)
+    pub fn create_payment_at(&mut self, payments: &[(String, u64)], timestamp: u64) -> Result<Transaction, TransactionError> {
+        // Mempool'daki işlemlerin harcadığı UTXO'lar tekrar seçilmez; böylece blok beklemeden art arda işlem gönderilebilir
+        let pending: HashSet<String> = self.mempool
+            .iter()
+            .flat_map(|tx| tx.inputs.iter().map(|input| input.utxo_id.clone()))
+            .collect();
         
-        // İşlemin geçerli olup olmadığını kontrol et
-        if !transaction.is_valid(&self.utxo_set) {
-            // İşlem geçersiz - UTXO doğrulaması başarısız
-            return false;
-        }
+        // Cüzdanın işlem oluşturmasını iste
+        let transaction = self.wallet.create_payment_at(payments, timestamp, &pending)?;
         
-        // Bu aşamada işlem geçerli kabul edilir
+        // İşlemi doğrula ve mempool'a ekle
+        self.receive_transaction(transaction.clone())?;
+        
+        Ok(transaction)
+    }
+    
+    // İşlemi doğrula
+    pub fn verify_transaction(&self, transaction: &Transaction) -> Result<(), TransactionError> {
+        // Bu aşamada UTXO varlığı ve miktar kontrolü yapılır
         // Gerçek bir sistemde imza doğrulaması yapılır, ancak bu simülasyonda basitleştiriyoruz
         // Çünkü her node kendi cüzdanını kullanıyor ve diğer node'ların public key'lerine erişimimiz yok
+        transaction.validate(&self.utxo_set)
+    }
+    
+    // Ağdan (veya cüzdandan) gelen işlemi mempool'a al
+    // İşlem yeni eklendiyse true, zaten biliniyorsa false döner
+    pub fn receive_transaction(&mut self, transaction: Transaction) -> Result<bool, TransactionError> {
+        if self.mempool.iter().any(|tx| tx.id == transaction.id) {
+            return Ok(false);
+        }
         
-        // Her girdi için UTXO'nun var olduğunu kontrol et
+        self.verify_transaction(&transaction)?;
+        
+        // Mempool'daki başka bir işlemin harcadığı UTXO'yu tekrar harcamaya izin verme
         for input in &transaction.inputs {
-            // UTXO'yu bul
-            let utxo = self.utxo_set.iter().find(|utxo| {
-                let utxo_id = get_utxo_id(&utxo.transaction_id, utxo.output_index);
-                utxo_id == input.utxo_id
-            });
-            
-            if utxo.is_none() {
-                // İşlem geçersiz - UTXO bulunamadı
-                return false;
+            let conflict = self.mempool
+                .iter()
+                .any(|tx| tx.inputs.iter().any(|other| other.utxo_id == input.utxo_id));
+            if conflict {
+                return Err(TransactionError::DoubleSpend { utxo_id: input.utxo_id.clone() });
             }
         }
         
-        // Tüm kontroller geçildi, işlem geçerli
-        true
+        self.mempool.push(transaction);
+        Ok(true)
     }
     
     // Mempool'dan işlemleri al ve yeni bir blok oluştur
-    pub fn create_block(&mut self, difficulty: usize) -> Option<Block> {
+    pub fn create_block(&mut self, difficulty: usize) -> Result<Block, NetworkError> {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        self.create_block_at(difficulty, timestamp)
+    }
+    
+    // Verilen zaman damgasıyla yeni bir blok oluştur
+    // Mempool ve imza durumu değişmez; blok node'un zincirine girdiğinde mempool temizlenir (bkz. mine_block_at)
+    pub fn create_block_at(&mut self, difficulty: usize, timestamp: u64) -> Result<Block, NetworkError> {
         if !self.is_validator {
-            // Node is not a validator
-            return None;
+            return Err(NetworkError::NotValidator(self.id));
+        }
+        
+        // Zincir boşsa genesis bloğu oluşturulur (previous hash "0")
+        let (index, previous_hash) = match self.blockchain.last() {
+            Some(last_block) => (last_block.index + 1, last_block.hash.clone()),
+            None => (0, "0".to_string()),
+        };
+        
+        // Dürüst validator aynı yükseklikte ikinci bir blok imzalamaz
+        if self.consensus.signed_blocks() && self.last_signed_height.map(|height| height >= index).unwrap_or(false) {
+            return Err(NetworkError::DoubleSign { node: self.id, height: index });
         }
         
-        // Mempool'dan en fazla 10 işlem al
+        // Mempool'dan en fazla BLOCK_TRANSACTION_LIMIT işlem al (coinbase dahil)
         let mut block_transactions = Vec::new();
-        let transaction_limit = 10;
         
         // Önce coinbase işlemini ekle (madencilik ödülü)
-        let coinbase_tx = Transaction::new_coinbase(
+        let coinbase_tx = Transaction::new_coinbase_at(
             self.wallet.get_address().to_string(),
-            self.mining_reward
+            self.mining_reward,
+            timestamp,
+            self.rng.random()
         );
         block_transactions.push(coinbase_tx);
         
         // Mempool'dan geçerli işlemleri seç
-        let mut selected_tx_indices = Vec::new();
+        // İşlemler sırayla bir çalışma UTXO setine uygulanır, böylece aynı UTXO blok içinde iki kez harcanamaz
+        let mut working_utxo_set = self.utxo_set.clone();
         
-        for (i, tx) in self.mempool.iter().enumerate() {
-            if block_transactions.len() >= transaction_limit {
+        for tx in &self.mempool {
+            if block_transactions.len() >= BLOCK_TRANSACTION_LIMIT {
                 break;
             }
             
-            if self.verify_transaction(tx) {
+            if tx.validate(&working_utxo_set).is_ok() {
+                Self::apply_transaction(&mut working_utxo_set, tx);
                 block_transactions.push(tx.clone());
-                selected_tx_indices.push(i);
             }
         }
         
-        // Seçilen işlemleri mempool'dan çıkar (büyükten küçüğe doğru silmek için)
-        selected_tx_indices.sort_by(|a, b| b.cmp(a));
-        for &i in &selected_tx_indices {
-            self.mempool.remove(i);
+        // Bekleyen çifte imza kanıtları ceza işlemleriyle birlikte bloğa eklenir
+        let mut evidence = Vec::new();
+        for candidate in &self.evidence_pool {
+            let id = candidate.id();
+            if slashing::already_slashed(&self.blockchain, &id) || evidence.iter().any(|included: &EquivocationEvidence| included.id() == id) {
+                continue;
+            }
+            if let Ok(slash) = slashing::slashing_transaction(candidate, &working_utxo_set, self.wallet.get_address()) {
+                Self::apply_transaction(&mut working_utxo_set, &slash);
+                block_transactions.push(slash);
+                evidence.push(candidate.clone());
+            }
         }
         
-        // Yeni blok oluştur
-        if let Some(last_block) = self.blockchain.last() {
-            // Normal blok oluşturma (zincirde zaten en az bir blok var)
-            let new_index = last_block.index + 1;
-            let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-            
-            let mut new_block = Block::new(
-                new_index,
-                timestamp,
-                block_transactions,
-                last_block.hash.clone(),
-            );
-            
-            // Blok madenciliği
-            new_block.mine_block(difficulty);
-            
-            Some(new_block)
-        } else {
-            // Blockchain boş, genesis bloğu oluştur
-            let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-            
-            let mut genesis_block = Block::new(
-                0,
-                timestamp,
-                block_transactions,
-                "0".to_string(), // Genesis bloğunun previous hash'i "0" olur
-            );
-            
-            // Genesis bloğu madenciliği
-            genesis_block.mine_block(difficulty);
-            
-            Some(genesis_block)
+        let mut new_block = Block::with_evidence(index, timestamp, block_transactions, evidence, previous_hash);
+        
+        // Bloğu uzlaşma motoruna göre mühürle: PoW'da nonce aranır, PoS'ta önerici imzalar
+        self.consensus.seal(&mut new_block, &self.wallet, difficulty);
+        
+        Ok(new_block)
+    }
+    
+    // Yeni blok oluştur ve kendi zincirine ekle
+    // Bloğa giren işlemler ancak blok kabul edilince mempool'dan çıkar; imzalanan yükseklik de o zaman kaydedilir
+    pub fn mine_block_at(&mut self, difficulty: usize, timestamp: u64) -> Result<Block, NetworkError> {
+        let block = self.create_block_at(difficulty, timestamp)?;
+        let status = self.receive_block(block.clone(), difficulty)?;
+        if status.is_accepted() && self.consensus.signed_blocks() {
+            self.last_signed_height = Some(block.index);
         }
+        Ok(block)
+    }
+    
+    // PoS: zincir ucuna göre verilen slotun önericisi (stake sahibinin adresi)
+    // Zincir ucunun slotu veya daha önceki slotlar için önerici yoktur
+    pub fn slot_proposer(&self, slot: u64) -> Option<String> {
+        let tip = self.blockchain.last().filter(|tip| slot > tip.timestamp)?;
+        stake::select_proposer(&stake::stakes(&self.utxo_set), &stake::slot_seed(&tip.hash, slot))
     }
     
     // Blok içindeki işlemlere göre UTXO setini güncelle
     pub fn update_utxo_set(&mut self, block: &Block) {
-        // UTXO seti güncelleniyor
-        
         for tx in &block.transactions {
-            // Harcanan UTXO'ları çıkar
-            for input in &tx.inputs {
-                if let Some(index) = self.utxo_set.iter().position(|utxo| {
-                    let utxo_id = get_utxo_id(&utxo.transaction_id, utxo.output_index);
-                    utxo_id == input.utxo_id
-                }) {
-                    let removed_utxo = &self.utxo_set[index];
-                    // UTXO harcanıyor
-                    self.utxo_set.remove(index);
+            Self::apply_transaction(&mut self.utxo_set, tx);
+        }
+    }
+    
+    // Bir işlemi UTXO setine uygula: harcanan UTXO'ları çıkar, yeni çıktıları ekle
+    fn apply_transaction(utxo_set: &mut Vec<UTXO>, tx: &Transaction) {
+        // Harcanan UTXO'ları çıkar
+        for input in &tx.inputs {
+            if let Some(index) = utxo_set.iter().position(|utxo| {
+                let utxo_id = get_utxo_id(&utxo.transaction_id, utxo.output_index);
+                utxo_id == input.utxo_id
+            }) {
+                // UTXO harcanıyor
+                utxo_set.remove(index);
+            }
+        }
+        
+        // Yeni UTXO'ları ekle
+        for (i, output) in tx.outputs.iter().enumerate() {
+            utxo_set.push(UTXO {
+                transaction_id: tx.id.clone(),
+                output_index: i,
+                amount: output.amount,
+                recipient_address: output.recipient_address.clone(),
+            });
+        }
+    }
+    
+    // Bloktaki işlemleri sırayla doğrulayarak UTXO setine uygula
+    // Son işlemler, bloktaki kanıtlara karşılık gelen ceza işlemleridir ve beklenenle birebir aynı olmalıdır
+    pub(crate) fn apply_block_transactions(utxo_set: &mut Vec<UTXO>, block: &Block) -> Result<(), ValidationError> {
+        if block.evidence.len() >= block.transactions.len() && !block.evidence.is_empty() {
+            return Err(ValidationError::InvalidSlashing { index: block.index });
+        }
+        let slash_start = block.transactions.len() - block.evidence.len();
+        // Ceza ödülü bloğun üreticisine, yani coinbase alıcısına gider
+        let producer = block.transactions.first().and_then(|tx| tx.outputs.first()).map(|output| output.recipient_address.as_str()).unwrap_or_default();
+        
+        for (i, tx) in block.transactions.iter().enumerate() {
+            // İlk işlem coinbase olmalı
+            if i == 0 {
+                if !tx.inputs.is_empty() {
+                    return Err(ValidationError::InvalidCoinbase { index: block.index });
                 }
+            } else if i >= slash_start {
+                let expected = slashing::slashing_transaction(&block.evidence[i - slash_start], utxo_set, producer)
+                    .map_err(|error| ValidationError::Evidence { index: block.index, error })?;
+                if *tx != expected {
+                    return Err(ValidationError::InvalidSlashing { index: block.index });
+                }
+            } else if let Err(error) = tx.validate(utxo_set) {
+                return Err(ValidationError::InvalidTransaction { tx_id: tx.id.clone(), error });
             }
             
-            // Yeni UTXO'ları ekle
-            for (i, output) in tx.outputs.iter().enumerate() {
-                let utxo = UTXO {
-                    transaction_id: tx.id.clone(),
-                    output_index: i,
-                    amount: output.amount,
-                    recipient_address: output.recipient_address.clone(),
-                };
-                
-                // Yeni UTXO ekleniyor
-                
-                self.utxo_set.push(utxo);
-            }
+            Self::apply_transaction(utxo_set, tx);
+        }
+        
+        Ok(())
+    }
+    
+    // Bloğa giren veya artık geçersiz olan işlemleri mempool'dan, cezası uygulanmış kanıtları kanıt havuzundan çıkar
+    fn prune_mempool(&mut self) {
+        let utxo_set = &self.utxo_set;
+        self.mempool.retain(|tx| tx.validate(utxo_set).is_ok());
+        
+        let chain = &self.blockchain;
+        self.evidence_pool.retain(|evidence| !slashing::already_slashed(chain, &evidence.id()));
+    }
+    
+    // Çifte imza kanıtını doğrula ve havuza al
+    // Kanıt yeniyse true, zaten biliniyorsa veya cezası uygulanmışsa false döner
+    pub fn add_evidence(&mut self, evidence: EquivocationEvidence) -> Result<bool, EvidenceError> {
+        evidence.verify()?;
+        
+        let id = evidence.id();
+        if self.evidence_pool.iter().any(|known| known.id() == id) || slashing::already_slashed(&self.blockchain, &id) {
+            return Ok(false);
         }
         
-        // UTXO seti güncellendi
+        self.evidence_pool.push(evidence);
+        Ok(true)
+    }
+    
+    // Aynı önericinin aynı yükseklikte imzaladığı başka bir blok biliniyorsa kanıt oluştur
+    fn detect_equivocation(&mut self, block: &Block) {
+        if block.index == 0 || block.proposer.is_empty() {
+            return;
+        }
+        
+        let conflicting = self.block_store
+            .values()
+            .chain(self.blockchain.iter())
+            .find(|known| known.index == block.index && known.proposer == block.proposer && known.hash != block.hash)
+            .map(|known| known.header());
+        
+        if let Some(conflicting) = conflicting {
+            let evidence = EquivocationEvidence::new(conflicting, block.header());
+            let _ = self.add_evidence(evidence);
+        }
+    }
+
+    // Komşunun bir öğeyi bildiğini işaretle (ona duyurduk ya da o bize gönderdi)
+    pub fn mark_known(&mut self, peer: usize, id: &str) {
+        self.known_inventory.entry(peer).or_default().insert(id.to_string());
+    }
+    
+    // Komşu bu öğeyi biliyor mu
+    pub fn peer_knows(&self, peer: usize, id: &str) -> bool {
+        self.known_inventory.get(&peer).map(|known| known.contains(id)).unwrap_or(false)
+    }
+    
+    // Node bu öğeye zaten sahip mi
+    pub fn has_item(&self, item: &InvItem) -> bool {
+        match item {
+            InvItem::Block(hash) => {
+                self.knows_block(hash) || self.orphan_blocks.iter().any(|orphan| &orphan.hash == hash)
+            }
+            InvItem::Transaction(id) => self.mempool.iter().any(|tx| &tx.id == id),
+        }
     }
 
     //Node bağlantısı ekleme
@@ -256,14 +439,19 @@ impl Node {
         
         self.connections.push(node_id);
     }
+
+    // Node bağlantısını kaldırma; komşunun bildiği öğelerin kaydı da silinir
+    pub fn remove_connection(&mut self, node_id: usize) -> bool {
+        let before = self.connections.len();
+        self.connections.retain(|&peer| peer != node_id);
+        self.known_inventory.remove(&node_id);
+        self.connections.len() != before
+    }
     
     // Dışarıdan gelen bir bloğu ekle
-    pub fn add_block_from_network(&mut self, block: Block, difficulty: usize) -> bool {
+    pub fn add_block_from_network(&mut self, block: Block, difficulty: usize) -> Result<(), ValidationError> {
         // Blok zincirinin geçerliliğini kontrol et
-        if !self.is_valid_new_block(&block, difficulty) {
-            // Geçersiz blok reddedildi
-            return false;
-        }
+        self.validate_new_block(&block, difficulty)?;
         
         // Yeni blok ekleniyor
         
@@ -276,102 +464,345 @@ impl Node {
         // Cüzdanı güncelle
         self.wallet.update_utxos(&block.transactions);
         
-        true
+        // Bloğu sakla ve bloğa giren işlemleri mempool'dan çıkar
+        self.block_store.insert(block.hash.clone(), block);
+        self.prune_mempool();
+        
+        Ok(())
     }
     
-    // Yeni bir bloğun geçerli olup olmadığını kontrol et
-    pub fn is_valid_new_block(&self, block: &Block, difficulty: usize) -> bool {
-        if let Some(last_block) = self.blockchain.last() {
-            // Blok indeksini kontrol et
-            if block.index != last_block.index + 1 {
-                // Geçersiz blok indeksi
-                return false;
+    // Ağdan gelen bloğu çatallanmaları (fork) hesaba katarak işle
+    // Aktif zinciri uzatan bloklar eklenir, daha uzun bir dal oluşursa o dala geçilir (en uzun zincir kuralı)
+    pub fn receive_block(&mut self, block: Block, difficulty: usize) -> Result<BlockStatus, ValidationError> {
+        if self.knows_block(&block.hash) {
+            return Ok(BlockStatus::Duplicate);
+        }
+        
+        // Hash, PoW ve merkle kökü bloğun kendi içinde tutarlı olmalı
+        self.validate_block_header(&block, difficulty)?;
+        
+        // İmzası geçerli bir blok, önericisinin aynı yükseklikteki başka bir bloğuyla çelişiyorsa kanıt oluşur
+        if self.consensus.signed_blocks() {
+            self.detect_equivocation(&block);
+        }
+        
+        // Kesinleşmiş bir yükseklikte farklı bir blok asla kabul edilmez
+        if let Some(finalized) = self.finalized_height() {
+            if block.index <= finalized {
+                return Err(ValidationError::ConflictsWithFinalized { index: block.index, finalized });
             }
-            
-            // Önceki hash'i kontrol et
-            if block.previous_hash != last_block.hash {
-                // Geçersiz önceki hash
-                return false;
+        }
+        
+        // Aktif zincirin ucunu uzatıyorsa doğrudan ekle
+        let extends_tip = match self.blockchain.last() {
+            Some(tip) => block.previous_hash == tip.hash,
+            None => block.index == 0,
+        };
+        if extends_tip {
+            self.add_block_from_network(block, difficulty)?;
+            return Ok(BlockStatus::Extended);
+        }
+        
+        // Ebeveyni bilinmiyorsa yetim havuzuna al
+        if block.index > 0 && !self.knows_block(&block.previous_hash) {
+            if !self.orphan_blocks.iter().any(|orphan| orphan.hash == block.hash) {
+                self.orphan_blocks.push(block);
             }
-            
-            // Hash'i kontrol et
-            let calculated_hash = block.calculate_hash();
-            if block.hash != calculated_hash {
-                // Geçersiz blok hash'i
-                return false;
+            return Ok(BlockStatus::Orphan);
+        }
+        
+        // Yan dal: blok indeksi ebeveyninin devamı olmalı
+        if let Some(parent) = self.find_block(&block.previous_hash) {
+            if block.index != parent.index + 1 {
+                return Err(ValidationError::InvalidIndex { expected: parent.index + 1, found: block.index });
             }
-            
-            // Proof of Work kontrolü
-            let target = "0".repeat(difficulty);
-            if !block.hash.starts_with(&target) {
-                // Geçersiz Proof of Work
-                return false;
+        }
+        
+        let hash = block.hash.clone();
+        self.block_store.insert(hash.clone(), block);
+        
+        // Çatal seçimi kuralı dalı aktif zincire tercih etmiyorsa sadece sakla
+        let branch = self.branch_to(&hash);
+        if !self.consensus.prefer(&self.blockchain, &branch) {
+            return Ok(BlockStatus::SideChain);
+        }
+        
+        // Tercih edilen dala geç; kesinleşmiş bloğu içermeyen dal ne kadar uzun olursa olsun yan dal kalır
+        if !self.contains_finalized(&branch) {
+            return Ok(BlockStatus::SideChain);
+        }
+        match self.switch_chain(branch, difficulty) {
+            Ok(reverted) => Ok(BlockStatus::Reorganized { reverted }),
+            Err(e) => {
+                // Geçersiz dalın ucunu unut
+                self.block_store.remove(&hash);
+                Err(e)
             }
+        }
+    }
+    
+    // Ebeveyni artık bilinen yetim blokları işle ve kabul edilenleri döndür
+    pub fn process_orphans(&mut self, difficulty: usize) -> Vec<(Block, BlockStatus)> {
+        let mut accepted = Vec::new();
+        
+        loop {
+            let position = self.orphan_blocks
+                .iter()
+                .position(|orphan| self.knows_block(&orphan.previous_hash));
             
-            // Merkle kök hash'ini kontrol et
-            let calculated_merkle_root = block.calculate_merkle_root();
-            if block.merkle_root != calculated_merkle_root {
-                // Geçersiz merkle kök hash'i
-                return false;
-            }
+            let orphan = match position {
+                Some(position) => self.orphan_blocks.remove(position),
+                None => break,
+            };
             
-            // Tüm işlemleri doğrula
-            for (i, tx) in block.transactions.iter().enumerate() {
-                // İlk işlem coinbase olmalı
-                if i == 0 {
-                    if !tx.inputs.is_empty() {
-                        // Geçersiz coinbase işlemi
-                        return false;
-                    }
-                } else if !self.verify_transaction(tx) {
-                    // Geçersiz işlem
-                    return false;
+            if let Ok(status) = self.receive_block(orphan.clone(), difficulty) {
+                if status.is_accepted() {
+                    accepted.push((orphan, status));
                 }
             }
+        }
+        
+        accepted
+    }
+    
+    // Blok aktif zincirde veya yan dallarda biliniyor mu
+    pub fn knows_block(&self, hash: &str) -> bool {
+        self.block_store.contains_key(hash) || self.blockchain.iter().rev().any(|block| block.hash == hash)
+    }
+    
+    // Zincirin özeti (block locator): uçtan geriye önce tek tek, sonra katlanarak artan
+    // aralıklarla blok hash'leri ve en sonda genesis. Komşu bu listeden bildiği ilk bloktan
+    // sonrasını gönderir, böylece ayrılma noktası az sayıda hash ile bulunur.
+    pub fn block_locator(&self) -> Vec<String> {
+        let mut locator = Vec::new();
+        let mut step = 1;
+        let mut position = self.blockchain.len();
+        
+        while position > 0 {
+            position -= 1;
+            locator.push(self.blockchain[position].hash.clone());
+            if locator.len() >= 10 {
+                step *= 2;
+            }
+            position = position.saturating_sub(step - 1);
+        }
+        
+        if let Some(genesis) = self.blockchain.first() {
+            if locator.last() != Some(&genesis.hash) {
+                locator.push(genesis.hash.clone());
+            }
+        }
+        
+        locator
+    }
+    
+    // Locator'daki aktif zincirde bulunan ilk bloktan sonraki en fazla `max` başlık
+    // Hiçbiri bilinmiyorsa genesis'ten başlanır
+    pub fn headers_after(&self, locator: &[String], max: usize) -> Vec<BlockHeader> {
+        let start = locator
+            .iter()
+            .find_map(|hash| self.blockchain.iter().position(|block| &block.hash == hash))
+            .map(|position| position + 1)
+            .unwrap_or(0);
+        
+        self.blockchain.iter().skip(start).take(max).map(|block| block.header()).collect()
+    }
+    
+    // Bilinen bir bloğu hash'ine göre bul
+    pub fn find_block(&self, hash: &str) -> Option<&Block> {
+        self.block_store
+            .get(hash)
+            .or_else(|| self.blockchain.iter().rev().find(|block| block.hash == hash))
+    }
+    
+    // Verilen bloğa kadar uzanan tam zinciri oluştur (aktif zincirle ortak kısım + dal)
+    pub(crate) fn branch_to(&self, hash: &str) -> Vec<Block> {
+        let mut branch = Vec::new();
+        let mut current = self.find_block(hash).cloned();
+        
+        while let Some(block) = current {
+            // Aktif zincirle birleşme noktasına gelindi
+            if self.blockchain.get(block.index).map(|active| active.hash == block.hash).unwrap_or(false) {
+                let mut chain = self.blockchain[..=block.index].to_vec();
+                branch.reverse();
+                chain.extend(branch);
+                return chain;
+            }
+            
+            let previous_hash = block.previous_hash.clone();
+            let is_genesis = block.index == 0;
+            branch.push(block);
             
-            true
-        } else {
-            // Genesis blok kontrolü
-            if block.index == 0 {
-                return true;
-            } else {
-                // Blockchain boş ama gelen blok genesis değil
-                return false;
+            if is_genesis {
+                break;
+            }
+            current = self.find_block(&previous_hash).cloned();
+        }
+        
+        branch.reverse();
+        branch
+    }
+    
+    // Aktif zinciri verilen zincirle değiştir ve geri alınan blokları döndür
+    // Zincirdeki tüm işlemler baştan doğrulanır; UTXO seti, cüzdan ve mempool yeniden kurulur
+    pub(crate) fn switch_chain(&mut self, chain: Vec<Block>, difficulty: usize) -> Result<Vec<Block>, ValidationError> {
+        // Kesinleşmiş bloklar geri alınamaz
+        if !self.contains_finalized(&chain) {
+            let finalized = self.finalized_height().unwrap_or(0);
+            return Err(ValidationError::ConflictsWithFinalized { index: finalized, finalized });
+        }
+        let utxo_set = self.validate_branch(&chain, difficulty)?;
+        
+        // Ortak ata noktasını bul
+        let common = self.blockchain
+            .iter()
+            .zip(chain.iter())
+            .take_while(|(current, new)| current.hash == new.hash)
+            .count();
+        
+        let reverted: Vec<Block> = self.blockchain[common..].to_vec();
+        
+        // Geri alınan bloklar yan dal olarak saklanır
+        for block in &reverted {
+            self.block_store.insert(block.hash.clone(), block.clone());
+        }
+        for block in &chain[common..] {
+            self.block_store.insert(block.hash.clone(), block.clone());
+        }
+        
+        self.blockchain = chain;
+        self.utxo_set = utxo_set;
+        
+        // Cüzdanı sıfırla (anahtarlar korunur) ve tüm işlemleri yeniden işle
+        let all_transactions: Vec<Transaction> = self.blockchain
+            .iter()
+            .flat_map(|block| block.transactions.clone())
+            .collect();
+        self.wallet.reset_utxos();
+        self.wallet.update_utxos(&all_transactions);
+        
+        // Geri alınan bloklardaki işlemler hala geçerliyse mempool'a, kanıtlar kanıt havuzuna geri döner
+        for block in &reverted {
+            let slash_start = block.transactions.len() - block.evidence.len();
+            for tx in block.transactions[..slash_start].iter().skip(1) {
+                if !self.mempool.iter().any(|pending| pending.id == tx.id) {
+                    self.mempool.push(tx.clone());
+                }
+            }
+            for evidence in &block.evidence {
+                if !self.evidence_pool.iter().any(|known| known.id() == evidence.id()) {
+                    self.evidence_pool.push(evidence.clone());
+                }
+            }
+        }
+        self.prune_mempool();
+        
+        Ok(reverted)
+    }
+    
+    // Genesis'ten başlayan bir zinciri tüm işlemleriyle doğrula ve sonundaki UTXO setini döndür
+    pub(crate) fn validate_branch(&self, chain: &[Block], difficulty: usize) -> Result<Vec<UTXO>, ValidationError> {
+        // Zincir genesis bloğundan başlamalı
+        if let Some(first) = chain.first() {
+            if first.index != 0 {
+                return Err(ValidationError::ExpectedGenesis { found: first.index });
             }
         }
+        self.validate_chain(chain, difficulty)?;
+        
+        let mut utxo_set = Vec::new();
+        for (i, block) in chain.iter().enumerate() {
+            // Her blok, ebeveyninden sonraki duruma göre blok üretmeye yetkili bir lider tarafından üretilmiş olmalı
+            if i > 0 {
+                self.consensus.verify_leader(&block.header(), &chain[i - 1], &utxo_set)?;
+            }
+            slashing::validate_block_evidence(block, &chain[..i])?;
+            Self::apply_block_transactions(&mut utxo_set, block)?;
+        }
+        Ok(utxo_set)
+    }
+    
+    // Yeni bir bloğun geçerli olup olmadığını kontrol et
+    pub fn is_valid_new_block(&self, block: &Block, difficulty: usize) -> bool {
+        self.validate_new_block(block, difficulty).is_ok()
+    }
+    
+    // Yeni bir bloğu doğrula ve geçersizse nedenini döndür
+    pub fn validate_new_block(&self, block: &Block, difficulty: usize) -> Result<(), ValidationError> {
+        let last_block = match self.blockchain.last() {
+            Some(last_block) => last_block,
+            None => {
+                // Genesis blok kontrolü: blockchain boşken sadece genesis bloğu kabul edilir
+                if block.index == 0 {
+                    return Ok(());
+                }
+                return Err(ValidationError::ExpectedGenesis { found: block.index });
+            }
+        };
+        
+        // Blok indeksini kontrol et
+        if block.index != last_block.index + 1 {
+            return Err(ValidationError::InvalidIndex {
+                expected: last_block.index + 1,
+                found: block.index,
+            });
+        }
+        
+        // Önceki hash'i kontrol et
+        if block.previous_hash != last_block.hash {
+            return Err(ValidationError::InvalidPreviousHash {
+                expected: last_block.hash.clone(),
+                found: block.previous_hash.clone(),
+            });
+        }
+        
+        // Hash, PoW ve merkle kökü kontrolleri
+        self.validate_block_header(block, difficulty)?;
+        
+        // Blok bu yükseklik için yetkili lider tarafından üretilmiş olmalı (PoS'ta slotun önericisi)
+        self.consensus.verify_leader(&block.header(), last_block, &self.utxo_set)?;
+        
+        // Kanıtlar daha önce cezalandırılmış suçlar için olmamalı
+        slashing::validate_block_evidence(block, &self.blockchain)?;
+        
+        // Tüm işlemleri sırayla doğrula (aynı UTXO blok içinde iki kez harcanamaz)
+        let mut utxo_set = self.utxo_set.clone();
+        Self::apply_block_transactions(&mut utxo_set, block)
+    }
+    
+    // Bloğun kendi içindeki tutarlılığını kontrol et (hash, mühür, merkle kökü)
+    fn validate_block_header(&self, block: &Block, difficulty: usize) -> Result<(), ValidationError> {
+        // Hash'i kontrol et
+        if block.hash != block.calculate_hash() {
+            return Err(ValidationError::InvalidHash { index: block.index });
+        }
+        
+        // Mühür kontrolü: PoW'da hedef, PoS'ta önerici imzası (lider yetkisi zincir durumuna göre ayrıca kontrol edilir)
+        self.consensus.verify_seal(&block.header(), difficulty)?;
+        
+        // Merkle kök hash'ini kontrol et
+        if block.merkle_root != block.calculate_merkle_root() {
+            return Err(ValidationError::InvalidMerkleRoot { index: block.index });
+        }
+        
+        Ok(())
     }
     
     // Blockchain'i güncelle
-    pub fn update_blockchain(&mut self, blockchain: Vec<Block>, difficulty: usize) {
+    // Gelen zincir geçerli ve çatal seçimi kuralınca tercih ediliyorsa true döner
+    pub fn update_blockchain(&mut self, blockchain: Vec<Block>, difficulty: usize) -> Result<bool, ValidationError> {
         // Gelen blockchain'in geçerli olup olmadığını kontrol et
-        if !self.is_chain_valid_with_difficulty(&blockchain, difficulty) {
-            // Gelen blockchain geçerli değil
-            return;
-        }
+        self.validate_chain(&blockchain, difficulty)?;
         
-        // Zincir uzunluğunu kontrol et (en uzun zincir kuralı)
-        if blockchain.len() > self.blockchain.len() {
-            // Daha uzun bir blockchain alındı
-            
-            // Mevcut UTXO setini temizle
-            self.utxo_set.clear();
-            
-            // Yeni blockchain'i ayarla
-            self.blockchain = blockchain.clone();
-            
-            // UTXO setini yeniden oluştur
-            self.rebuild_utxo_set();
-            
-            // Cüzdanı güncelle
-            let all_transactions: Vec<Transaction> = self.blockchain
-                .iter()
-                .flat_map(|block| block.transactions.clone())
-                .collect();
-            
-            // Cüzdanı sıfırla ve tüm işlemleri yeniden işle
-            self.wallet = Wallet::new();
-            self.wallet.update_utxos(&all_transactions);
+        // Çatal seçimi kuralını uygula (varsayılan: en uzun zincir kuralı)
+        if !self.consensus.prefer(&self.blockchain, &blockchain) {
+            return Ok(false);
         }
+        
+        // Tercih edilen bir blockchain alındı, ona geç
+        self.switch_chain(blockchain, difficulty)?;
+        
+        Ok(true)
     }
     
     // UTXO setini blockchain'den yeniden oluştur
@@ -412,47 +843,35 @@ impl Node {
     
     // Belirli bir zorluk seviyesiyle blockchain'in geçerliliğini kontrol et
     pub fn is_chain_valid_with_difficulty(&self, chain: &[Block], difficulty: usize) -> bool {
-        let target = "0".repeat(difficulty);
-        
+        self.validate_chain(chain, difficulty).is_ok()
+    }
+    
+    // Zinciri doğrula ve ilk geçersiz bloğun hatasını döndür
+    pub fn validate_chain(&self, chain: &[Block], difficulty: usize) -> Result<(), ValidationError> {
         for i in 1..chain.len() {
             let current_block = &chain[i];
             let previous_block = &chain[i - 1];
             
-            // Hash doğrulaması
-            if current_block.hash != current_block.calculate_hash() {
-                // Geçersiz blok hash'i
-                return false;
-            }
-            
             // Previous hash doğrulaması
             if current_block.previous_hash != previous_block.hash {
-                // Geçersiz önceki hash
-                return false;
-            }
-            
-            // Proof of Work kontrolü
-            if !current_block.hash.starts_with(&target) {
-                // Geçersiz Proof of Work
-                return false;
+                return Err(ValidationError::InvalidPreviousHash {
+                    expected: previous_block.hash.clone(),
+                    found: current_block.previous_hash.clone(),
+                });
             }
             
-            // Merkle kök hash'ini kontrol et
-            if current_block.merkle_root != current_block.calculate_merkle_root() {
-                // Geçersiz merkle kök hash'i
-                return false;
-            }
+            // Hash, PoW ve merkle kökü kontrolleri
+            self.validate_block_header(current_block, difficulty)?;
             
             // Tüm işlemleri doğrula (basitleştirilmiş, gerçek bir sistemde daha karmaşık olur)
             // İlk işlem coinbase olmalı
-            if !current_block.transactions.is_empty() {
-                let coinbase_tx = &current_block.transactions[0];
+            if let Some(coinbase_tx) = current_block.transactions.first() {
                 if !coinbase_tx.inputs.is_empty() {
-                    // Geçersiz coinbase işlemi
-                    return false;
+                    return Err(ValidationError::InvalidCoinbase { index: current_block.index });
                 }
             }
         }
         
-        true
+        Ok(())
     }
-}
\ No newline at end of file
+}
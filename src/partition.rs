@@ -0,0 +1,173 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::message::Message;
+use crate::network::{BlockchainNetwork, SimEvent};
+use crate::sim::SimTime;
+
+// Bölünmüş ağ birleştikten sonra kaybeden taraftaki etkiler
+#[derive(Debug, Clone, Default)]
+pub struct PartitionReport {
+    pub healed_at: SimTime,
+    pub reorged_nodes: Vec<usize>,           // Zincir değiştirmek zorunda kalan node'lar
+    pub reverted_blocks: Vec<String>,        // Geri alınan (eskiyen) blokların hash'leri
+    pub reverted_transactions: Vec<String>,  // Geri alınan bloklardaki (coinbase hariç) işlemler
+    pub unconfirmed_transactions: Vec<String>, // Bunlardan kazanan zincire henüz girmemiş olanlar
+    pub converged: bool,                     // Tüm node'lar aynı zincir ucunda mı
+}
+
+impl fmt::Display for PartitionReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Ağ birleşmesi ({} ms):", self.healed_at)?;
+        writeln!(f, "  Zincir değiştiren node'lar: {:?}", self.reorged_nodes)?;
+        writeln!(f, "  Geri alınan blok sayısı: {}", self.reverted_blocks.len())?;
+        writeln!(f, "  Geri alınan işlem sayısı: {}", self.reverted_transactions.len())?;
+        writeln!(f, "  Henüz tekrar onaylanmayan işlem sayısı: {}", self.unconfirmed_transactions.len())?;
+        write!(f, "  Uzlaşma: {}", if self.converged { "tüm node'lar aynı zincirde" } else { "zincirler henüz farklı" })
+    }
+}
+
+impl BlockchainNetwork {
+    // İki node arasındaki bağlantıyı iki yönde de kes
+    // Yolda olan mesajlar da kaybolur; bağlantı gerçekten varsa ve açıksa true döner
+    pub fn cut_link(&mut self, a: usize, b: usize) -> bool {
+        let mut changed = false;
+        for key in [(a, b), (b, a)] {
+            if let Some(link) = self.links.get_mut(&key) {
+                changed |= link.up;
+                link.up = false;
+            }
+        }
+
+        if changed {
+            self.scheduler.retain(|event| match event {
+                SimEvent::Deliver { from, to, .. } => !((*from == a && *to == b) || (*from == b && *to == a)),
+                _ => true,
+            });
+        }
+        changed
+    }
+
+    // Kesilmiş bağlantıyı tekrar aç ve iki uç arasında zincir uçlarını eşitle
+    pub fn restore_link(&mut self, a: usize, b: usize) -> bool {
+        let mut changed = false;
+        for key in [(a, b), (b, a)] {
+            if let Some(link) = self.links.get_mut(&key) {
+                changed |= !link.up;
+                link.up = true;
+            }
+        }
+
+        // Bağlantı yeniden kurulunca iki taraf birbirinden bilmediği blokların başlıklarını ister
+        if changed {
+            for (from, to) in [(a, b), (b, a)] {
+                let locator = self.nodes[from].block_locator();
+                self.send_message(from, to, Message::GetHeaders { locator });
+            }
+        }
+        changed
+    }
+
+    // Birden fazla bağlantıyı kes; kesilen bağlantı sayısı döner
+    pub fn cut_links(&mut self, links: &[(usize, usize)]) -> usize {
+        links.iter().filter(|&&(a, b)| self.cut_link(a, b)).count()
+    }
+
+    // Birden fazla bağlantıyı tekrar aç; açılan bağlantı sayısı döner
+    pub fn restore_links(&mut self, links: &[(usize, usize)]) -> usize {
+        let restored = links.iter().filter(|&&(a, b)| self.restore_link(a, b)).count();
+        self.process_due_events();
+        restored
+    }
+
+    // Bir node grubunu ağın geri kalanından ayır
+    pub fn isolate(&mut self, group: &[usize]) -> usize {
+        let members: HashSet<usize> = group.iter().copied().collect();
+        let boundary: Vec<(usize, usize)> = self
+            .edges()
+            .into_iter()
+            .filter(|(a, b)| members.contains(a) != members.contains(b))
+            .collect();
+        self.cut_links(&boundary)
+    }
+
+    // Ağı gruplara böl: farklı gruplardaki node'lar arasındaki tüm bağlantılar kesilir
+    // Hiçbir gruba yazılmamış node'lar ayrı bir grup sayılır
+    pub fn partition(&mut self, groups: &[Vec<usize>]) -> usize {
+        let group_of = |node: usize| groups.iter().position(|group| group.contains(&node));
+        let boundary: Vec<(usize, usize)> = self
+            .edges()
+            .into_iter()
+            .filter(|&(a, b)| group_of(a) != group_of(b))
+            .collect();
+        self.cut_links(&boundary)
+    }
+
+    // Tüm kesik bağlantıları aç; birleşme raporu bu andan sonraki zincir değişimlerini sayar
+    pub fn heal(&mut self) -> usize {
+        let down: Vec<(usize, usize)> = self
+            .edges()
+            .into_iter()
+            .filter(|key| self.links.get(key).map(|link| !link.up).unwrap_or(false))
+            .collect();
+
+        self.healed_at = Some(self.now());
+        self.restore_links(&down)
+    }
+
+    // Kesik bağlantı var mı
+    pub fn is_partitioned(&self) -> bool {
+        self.links.values().any(|link| !link.up)
+    }
+
+    // Son birleşmeden bu yana geri alınan bloklar ve işlemler
+    // Zincirler uzlaşana kadar simülasyonu ilerletip tekrar çağırmak gerekir
+    pub fn heal_report(&self) -> Option<PartitionReport> {
+        let healed_at = self.healed_at?;
+        let mut report = PartitionReport { healed_at, ..PartitionReport::default() };
+
+        let mut reverted_blocks = HashSet::new();
+        let mut reverted_transactions = HashSet::new();
+        for reorg in self.reorgs.iter().filter(|reorg| reorg.time >= healed_at) {
+            if !report.reorged_nodes.contains(&reorg.node_id) {
+                report.reorged_nodes.push(reorg.node_id);
+            }
+            for block in &reorg.reverted {
+                if reverted_blocks.insert(block.hash.clone()) {
+                    report.reverted_blocks.push(block.hash.clone());
+                }
+                for tx in block.transactions.iter().filter(|tx| !tx.is_coinbase()) {
+                    if reverted_transactions.insert(tx.id.clone()) {
+                        report.reverted_transactions.push(tx.id.clone());
+                    }
+                }
+            }
+        }
+        report.reorged_nodes.sort_unstable();
+
+        // Kazanan zincire tekrar girmemiş işlemler (mempool'a dönen veya çakışma yüzünden düşen)
+        let confirmed: HashSet<&str> = self
+            .canonical_chain()
+            .iter()
+            .flat_map(|block| block.transactions.iter().map(|tx| tx.id.as_str()))
+            .collect();
+        report.unconfirmed_transactions = report
+            .reverted_transactions
+            .iter()
+            .filter(|id| !confirmed.contains(id.as_str()))
+            .cloned()
+            .collect();
+
+        let tips: HashSet<Option<&String>> = self.nodes.iter().map(|node| node.blockchain.last().map(|block| &block.hash)).collect();
+        report.converged = tips.len() <= 1;
+
+        Some(report)
+    }
+
+    // Her bağlantı bir kez (küçük ID önce), sabit sırada
+    fn edges(&self) -> Vec<(usize, usize)> {
+        let mut edges: Vec<(usize, usize)> = self.links.keys().filter(|(a, b)| a < b).copied().collect();
+        edges.sort_unstable();
+        edges
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use crate::block::Block;
+use crate::sim::SimTime;
+
+// Tek bir bloğun ağda yayılma kaydı
+#[derive(Debug, Clone)]
+pub struct BlockPropagation {
+    pub hash: String,
+    pub index: usize,
+    pub producer: usize,
+    pub mined_at: SimTime,
+    pub received_at: HashMap<usize, SimTime>, // Node ID -> bloğu ilk kabul ettiği zaman
+}
+
+impl BlockPropagation {
+    // Bloğun node'lara ulaşma gecikmeleri (üretici hariç), küçükten büyüğe
+    pub fn delays(&self) -> Vec<SimTime> {
+        let mut delays: Vec<SimTime> = self.received_at
+            .iter()
+            .filter(|(node_id, _)| **node_id != self.producer)
+            .map(|(_, time)| time - self.mined_at)
+            .collect();
+        delays.sort_unstable();
+        delays
+    }
+
+    // Bloğun node'ların belirli bir oranına (üretici dahil) ulaşması için geçen süre
+    pub fn time_to_reach(&self, fraction: f64, node_count: usize) -> Option<SimTime> {
+        let needed = ((fraction * node_count as f64).ceil() as usize).max(1);
+        let mut times: Vec<SimTime> = self.received_at.values().copied().collect();
+        times.sort_unstable();
+        times.get(needed - 1).map(|time| time - self.mined_at)
+    }
+}
+
+// Blokların yayılma gecikmesini ve eskimiş (stale) blokları takip eder
+#[derive(Debug, Clone, Default)]
+pub struct PropagationTracker {
+    pub blocks: Vec<BlockPropagation>,
+    positions: HashMap<String, usize>,
+}
+
+impl PropagationTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Yeni üretilen bir bloğu kaydet
+    pub fn record_mined(&mut self, block: &Block, producer: usize, time: SimTime) {
+        if self.positions.contains_key(&block.hash) {
+            return;
+        }
+
+        let mut received_at = HashMap::new();
+        received_at.insert(producer, time);
+
+        self.positions.insert(block.hash.clone(), self.blocks.len());
+        self.blocks.push(BlockPropagation {
+            hash: block.hash.clone(),
+            index: block.index,
+            producer,
+            mined_at: time,
+            received_at,
+        });
+    }
+
+    // Bir node'un bloğu kabul ettiği zamanı kaydet (sadece ilk kabul sayılır)
+    pub fn record_received(&mut self, hash: &str, node_id: usize, time: SimTime) {
+        if let Some(&position) = self.positions.get(hash) {
+            self.blocks[position].received_at.entry(node_id).or_insert(time);
+        }
+    }
+
+    pub fn get(&self, hash: &str) -> Option<&BlockPropagation> {
+        self.positions.get(hash).map(|&position| &self.blocks[position])
+    }
+
+    // Blokların node'ların belirli bir oranına ulaşma süresinin ortalaması (ms)
+    pub fn average_time_to_reach(&self, fraction: f64, node_count: usize) -> Option<f64> {
+        let times: Vec<SimTime> = self.blocks
+            .iter()
+            .filter_map(|block| block.time_to_reach(fraction, node_count))
+            .collect();
+
+        if times.is_empty() {
+            return None;
+        }
+        Some(times.iter().sum::<SimTime>() as f64 / times.len() as f64)
+    }
+
+    // Verilen kanonik zincirde yer almayan üretilmiş bloklar
+    pub fn stale_blocks<'a>(&'a self, canonical_chain: &[Block]) -> Vec<&'a BlockPropagation> {
+        let canonical: std::collections::HashSet<&str> = canonical_chain
+            .iter()
+            .map(|block| block.hash.as_str())
+            .collect();
+
+        self.blocks
+            .iter()
+            .filter(|block| !canonical.contains(block.hash.as_str()))
+            .collect()
+    }
+
+    // Eskimiş blok oranı (0.0 - 1.0)
+    pub fn stale_rate(&self, canonical_chain: &[Block]) -> f64 {
+        if self.blocks.is_empty() {
+            return 0.0;
+        }
+        self.stale_blocks(canonical_chain).len() as f64 / self.blocks.len() as f64
+    }
+}
@@ -0,0 +1,1098 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::str::FromStr;
+
+use toml::{Table, Value};
+
+use crate::byzantine::Misbehavior;
+use crate::churn::{ChurnModel, SessionLength};
+#[cfg(feature = "attacks")]
+use crate::eclipse::EclipseDefense;
+use crate::error::{NetworkError, ScenarioError};
+use crate::events::JsonlSink;
+use crate::gossip::RelayMode;
+use crate::link::{LatencyModel, LinkConfig};
+use crate::metrics::MetricsFormat;
+use crate::mining::MiningModel;
+use crate::network::BlockchainNetwork;
+use crate::sim::SimTime;
+use crate::stake::ConsensusMode;
+use crate::topology::Topology;
+use crate::workload::Workload;
+
+// Senaryo dosyalarında miktarlar coin cinsindendir
+const COIN: f64 = 100_000_000.0;
+
+// Senaryodaki ağın kurulumu; verilmeyen alanlar etkileşimli arayüzün varsayılanlarını kullanır
+#[derive(Debug, Clone)]
+pub struct NetworkSpec {
+    pub nodes: usize,
+    pub difficulty: usize,
+    pub block_time: u64, // Saniye
+    pub consensus: ConsensusMode,
+    pub mining: MiningModel,
+    pub topology: Topology,
+    pub relay: RelayMode,
+    pub link: LinkConfig,
+    pub hashrates: Vec<f64>,        // Node sırasıyla hash güçleri (listede olmayanlar 1)
+    pub finality: bool,             // Tüm node'ları validator yaparak BFT kesinlik katmanını aç
+    pub request_timeout: SimTime,
+}
+
+impl Default for NetworkSpec {
+    fn default() -> Self {
+        NetworkSpec {
+            nodes: 5,
+            difficulty: 2,
+            block_time: 60,
+            consensus: ConsensusMode::ProofOfWork,
+            mining: MiningModel::HashRace,
+            topology: Topology::FullMesh,
+            relay: RelayMode::Announce,
+            link: LinkConfig::default(),
+            hashrates: Vec::new(),
+            finality: false,
+            request_timeout: 5000,
+        }
+    }
+}
+
+// Zaman çizelgesindeki bir adım
+#[derive(Debug, Clone)]
+pub enum ScenarioAction {
+    Transfer { from: usize, to: usize, amount: u64 },
+    Partition { groups: Vec<Vec<usize>> },
+    Heal,
+    Hashrate { node: usize, value: f64 },
+    Join { peers: Vec<usize> },
+    Leave { node: usize },
+    Crash { node: usize },
+    Restart { node: usize },
+    Churn { nodes: Vec<usize>, model: ChurnModel },
+    StopChurn,
+    Misbehave { node: usize, behaviors: Vec<Misbehavior> },
+    StopMining,
+    Workload { config: Workload, senders: Vec<usize> },
+    StopWorkload,
+    #[cfg(feature = "attacks")]
+    SelfishMining { node: usize, hash_share: f64, gamma: f64 },
+    #[cfg(feature = "attacks")]
+    StopSelfishMining,
+    #[cfg(feature = "attacks")]
+    DoubleSpend { attacker: usize, merchant: usize, amount: u64, confirmations: usize, hash_share: f64 },
+    #[cfg(feature = "attacks")]
+    Eclipse { victim: usize, sybils: usize, hash_share: f64, defense: EclipseDefense },
+    #[cfg(feature = "attacks")]
+    StopEclipse,
+}
+
+impl ScenarioAction {
+    // Senaryo dosyasındaki adı
+    pub fn name(&self) -> &'static str {
+        match self {
+            ScenarioAction::Transfer { .. } => "transfer",
+            ScenarioAction::Partition { .. } => "partition",
+            ScenarioAction::Heal => "heal",
+            ScenarioAction::Hashrate { .. } => "hashrate",
+            ScenarioAction::Join { .. } => "join",
+            ScenarioAction::Leave { .. } => "leave",
+            ScenarioAction::Crash { .. } => "crash",
+            ScenarioAction::Restart { .. } => "restart",
+            ScenarioAction::Churn { .. } => "churn",
+            ScenarioAction::StopChurn => "stop_churn",
+            ScenarioAction::Misbehave { .. } => "misbehave",
+            ScenarioAction::StopMining => "stop_mining",
+            ScenarioAction::Workload { .. } => "workload",
+            ScenarioAction::StopWorkload => "stop_workload",
+            #[cfg(feature = "attacks")]
+            ScenarioAction::SelfishMining { .. } => "selfish",
+            #[cfg(feature = "attacks")]
+            ScenarioAction::StopSelfishMining => "stop_selfish",
+            #[cfg(feature = "attacks")]
+            ScenarioAction::DoubleSpend { .. } => "double_spend",
+            #[cfg(feature = "attacks")]
+            ScenarioAction::Eclipse { .. } => "eclipse",
+            #[cfg(feature = "attacks")]
+            ScenarioAction::StopEclipse => "stop_eclipse",
+        }
+    }
+
+    // Adımı ağa uygula
+    pub fn apply(&self, network: &mut BlockchainNetwork) -> Result<(), NetworkError> {
+        match self {
+            ScenarioAction::Transfer { from, to, amount } => {
+                if *to >= network.node_count() {
+                    return Err(NetworkError::NodeNotFound(*to));
+                }
+                let recipient = network.get_node_address(*to);
+                network.create_transaction(*from, &recipient, *amount)?;
+            }
+            ScenarioAction::Partition { groups } => {
+                network.partition(groups);
+            }
+            ScenarioAction::Heal => {
+                network.heal();
+            }
+            ScenarioAction::Hashrate { node, value } => network.set_hashrate(*node, *value)?,
+            ScenarioAction::Join { peers } => {
+                network.join_node(peers)?;
+            }
+            ScenarioAction::Leave { node } => network.leave_node(*node)?,
+            ScenarioAction::Crash { node } => network.crash_node(*node)?,
+            ScenarioAction::Restart { node } => network.restart_node(*node)?,
+            ScenarioAction::Churn { nodes, model } => network.set_churn(nodes, *model)?,
+            ScenarioAction::StopChurn => network.stop_churn(),
+            ScenarioAction::Misbehave { node, behaviors } => network.set_misbehavior(*node, behaviors.clone())?,
+            ScenarioAction::StopMining => network.stop_automatic_mining()?,
+            ScenarioAction::Workload { config, senders } => network.start_workload(*config, senders)?,
+            ScenarioAction::StopWorkload => {
+                network.stop_workload();
+            }
+            #[cfg(feature = "attacks")]
+            ScenarioAction::SelfishMining { node, hash_share, gamma } => network.start_selfish_mining(*node, *hash_share, *gamma)?,
+            #[cfg(feature = "attacks")]
+            ScenarioAction::StopSelfishMining => {
+                network.stop_selfish_mining();
+            }
+            #[cfg(feature = "attacks")]
+            ScenarioAction::DoubleSpend { attacker, merchant, amount, confirmations, hash_share } => {
+                network.start_double_spend(*attacker, *merchant, *amount, *confirmations, *hash_share)?;
+            }
+            #[cfg(feature = "attacks")]
+            ScenarioAction::Eclipse { victim, sybils, hash_share, defense } => {
+                network.start_eclipse(*victim, *sybils, *hash_share, *defense)?;
+            }
+            #[cfg(feature = "attacks")]
+            ScenarioAction::StopEclipse => {
+                network.stop_eclipse();
+            }
+        }
+        Ok(())
+    }
+}
+
+// Belirli bir sanal zamanda uygulanacak adım
+#[derive(Debug, Clone)]
+pub struct TimedAction {
+    pub at: SimTime, // ms
+    pub action: ScenarioAction,
+}
+
+// Çalıştırma sonunda toplanacak ölçümler
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    Chain,
+    Propagation,
+    Miners,
+    Reorgs,
+    Traffic,
+    Partition,
+    Churn,
+    Bans,
+    Byzantine,
+    Finality,
+    Attacks,
+    Workload,
+}
+
+impl Metric {
+    // Sonuç dosyasındaki bölüm adı
+    pub fn name(&self) -> &'static str {
+        match self {
+            Metric::Chain => "chain",
+            Metric::Propagation => "propagation",
+            Metric::Miners => "miners",
+            Metric::Reorgs => "reorgs",
+            Metric::Traffic => "traffic",
+            Metric::Partition => "partition",
+            Metric::Churn => "churn",
+            Metric::Bans => "bans",
+            Metric::Byzantine => "byzantine",
+            Metric::Finality => "finality",
+            Metric::Attacks => "attacks",
+            Metric::Workload => "workload",
+        }
+    }
+}
+
+impl FromStr for Metric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "chain" => Ok(Metric::Chain),
+            "propagation" => Ok(Metric::Propagation),
+            "miners" => Ok(Metric::Miners),
+            "reorgs" => Ok(Metric::Reorgs),
+            "traffic" => Ok(Metric::Traffic),
+            "partition" => Ok(Metric::Partition),
+            "churn" => Ok(Metric::Churn),
+            "bans" => Ok(Metric::Bans),
+            "byzantine" => Ok(Metric::Byzantine),
+            "finality" => Ok(Metric::Finality),
+            "attacks" => Ok(Metric::Attacks),
+            "workload" => Ok(Metric::Workload),
+            other => Err(format!(
+                "bilinmeyen metrik: {} (chain, propagation, miners, reorgs, traffic, partition, churn, bans, byzantine, finality, attacks, workload)",
+                other
+            )),
+        }
+    }
+}
+
+// Tekrarlanabilir bir deney: ağ kurulumu, zaman çizelgesi ve toplanacak ölçümler
+// Aynı seed ile aynı senaryo her çalıştırmada birebir aynı sonucu verir.
+#[derive(Debug, Clone)]
+pub struct Scenario {
+    pub name: String,
+    pub seed: Option<u64>,      // Verilmezse rastgele seçilir ve sonuçlara yazılır
+    pub duration: SimTime,      // Simülasyonun süreceği sanal zaman (ms)
+    pub network: NetworkSpec,
+    pub events: Vec<TimedAction>,
+    pub metrics: Vec<Metric>,
+    pub output: Option<String>, // Sonuçların yazılacağı dosya
+    pub series: Option<(String, MetricsFormat)>, // Blok ve node zaman serilerinin yazılacağı dosya öneki ve biçimi
+    pub event_log: Option<String>, // Ağ olaylarının JSON satırları olarak yazılacağı dosya
+}
+
+impl Scenario {
+    // Senaryoyu TOML dosyasından oku
+    pub fn from_file(path: &str) -> Result<Scenario, ScenarioError> {
+        let text = fs::read_to_string(path).map_err(|e| ScenarioError::Io(format!("{}: {}", path, e)))?;
+        text.parse()
+    }
+
+    // Senaryodaki ağı kur, madenciliği başlat ve genesis bloğunu üret
+    pub fn build_network(&self) -> Result<BlockchainNetwork, ScenarioError> {
+        let spec = &self.network;
+        let mut network = match self.seed {
+            Some(seed) => BlockchainNetwork::with_seed(seed),
+            None => BlockchainNetwork::with_seed(rand::random()),
+        };
+        network.set_difficulty(spec.difficulty);
+        network.set_block_time(spec.block_time);
+        network.relay_mode = spec.relay;
+        network.request_timeout = spec.request_timeout;
+        network.set_all_link_configs(spec.link.clone());
+        if let Some(path) = &self.event_log {
+            let sink = JsonlSink::create(path).map_err(|e| ScenarioError::Io(format!("{}: {}", path, e)))?;
+            network.events.subscribe(sink);
+        }
+
+        for _ in 0..spec.nodes {
+            network.add_node();
+        }
+        network.set_consensus(spec.consensus);
+        network.mining_model = spec.mining;
+        for (node_id, &rate) in spec.hashrates.iter().enumerate() {
+            network.set_hashrate(node_id, rate)?;
+        }
+        network.apply_topology(&spec.topology)?;
+        if spec.finality {
+            let validators: Vec<usize> = (0..spec.nodes).collect();
+            network.enable_finality(&validators)?;
+        }
+
+        if spec.consensus == ConsensusMode::ProofOfWork && spec.mining == MiningModel::RandomValidator {
+            network.select_random_validator()?;
+        }
+        network.start_automatic_mining()?;
+        network.mine_block()?;
+        Ok(network)
+    }
+
+    // Senaryoyu arayüz olmadan baştan sona çalıştır
+    // Uygulanamayan adımlar (ör. yetersiz bakiye) çalıştırmayı durdurmaz, sonuçlardaki hata listesine yazılır.
+    pub fn run(&self) -> Result<ScenarioResult, ScenarioError> {
+        let mut network = self.build_network()?;
+        let mut events = self.events.clone();
+        events.sort_by_key(|event| event.at);
+
+        let mut errors = Vec::new();
+        for event in events.iter().filter(|event| event.at <= self.duration) {
+            network.run_until(event.at);
+            if let Err(e) = event.action.apply(&mut network) {
+                errors.push(format!("{} ms, {}: {}", event.at, event.action.name(), e));
+            }
+        }
+        network.run_until(self.duration);
+        if let Some((prefix, format)) = &self.series {
+            network.export_metrics(prefix, *format).map_err(|e| ScenarioError::Io(format!("{}: {}", prefix, e)))?;
+        }
+
+        let mut metrics = Table::new();
+        for &metric in &self.metrics {
+            metrics.insert(metric.name().to_string(), Value::Table(collect_metric(&network, metric)));
+        }
+        Ok(ScenarioResult {
+            name: self.name.clone(),
+            seed: network.seed,
+            duration: self.duration,
+            metrics,
+            errors,
+        })
+    }
+}
+
+impl FromStr for Scenario {
+    type Err = ScenarioError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let table: Table = text.parse().map_err(|e: toml::de::Error| ScenarioError::Parse(e.message().to_string()))?;
+        let root = Fields::new(&table, "", &["name", "seed", "duration", "output", "network", "events", "metrics"])?;
+
+        let network = match root.table("network")? {
+            Some(table) => parse_network(table)?,
+            None => NetworkSpec::default(),
+        };
+        let mut events = Vec::new();
+        for (index, value) in root.array("events")?.unwrap_or_default().iter().enumerate() {
+            let path = format!("events[{}]", index);
+            let table = value.as_table().ok_or_else(|| invalid(&path, "tablo olmalı"))?;
+            events.push(parse_event(table, &path)?);
+        }
+        check_node_ids(&events, network.nodes)?;
+        let (metrics, series, event_log) = match root.table("metrics")? {
+            Some(table) => {
+                let fields = Fields::new(table, "metrics", &["collect", "series", "series_format", "events"])?;
+                let series = fields.string("series")?.map(str::to_string);
+                let format = fields.parsed::<MetricsFormat>("series_format")?.unwrap_or(MetricsFormat::Csv);
+                let event_log = fields.string("events")?.map(str::to_string);
+                (fields.parsed_list::<Metric>("collect")?.unwrap_or_default(), series.map(|prefix| (prefix, format)), event_log)
+            }
+            None => (vec![Metric::Chain, Metric::Propagation], None, None),
+        };
+
+        Ok(Scenario {
+            name: root.string("name")?.unwrap_or("senaryo").to_string(),
+            seed: root.integer("seed")?,
+            duration: root.integer("duration")?.ok_or_else(|| root.missing("duration"))?,
+            network,
+            events,
+            metrics,
+            output: root.string("output")?.map(str::to_string),
+            series,
+            event_log,
+        })
+    }
+}
+
+// Bir senaryo çalıştırmasının sonuçları
+#[derive(Debug, Clone)]
+pub struct ScenarioResult {
+    pub name: String,
+    pub seed: u64,           // Çalıştırmayı tekrarlamak için kullanılan seed
+    pub duration: SimTime,
+    pub metrics: Table,      // Metrik adı -> ölçülen değerler
+    pub errors: Vec<String>, // Uygulanamayan zaman çizelgesi adımları
+}
+
+impl ScenarioResult {
+    // Sonuçları TOML olarak yaz
+    pub fn to_toml(&self) -> String {
+        let mut summary = Table::new();
+        summary.insert("name".into(), Value::from(self.name.as_str()));
+        summary.insert("seed".into(), integer(self.seed));
+        summary.insert("duration".into(), integer(self.duration));
+        summary.insert("errors".into(), Value::from(self.errors.clone()));
+
+        // Özet bölümü metriklerden önce yazılır
+        let mut root = Table::new();
+        root.insert("scenario".into(), Value::Table(summary));
+        format!("{}\n{}", root, self.metrics)
+    }
+
+    // Sonuçları dosyaya yaz
+    pub fn write(&self, path: &str) -> Result<(), ScenarioError> {
+        fs::write(path, self.to_toml()).map_err(|e| ScenarioError::Io(format!("{}: {}", path, e)))
+    }
+}
+
+impl fmt::Display for ScenarioResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_toml())
+    }
+}
+
+// TOML tablosundan alan okuyan yardımcı; hata mesajlarında alanın tam yolu kullanılır
+struct Fields<'a> {
+    table: &'a Table,
+    path: String,
+}
+
+impl<'a> Fields<'a> {
+    // Tanınmayan alanlar (çoğunlukla yazım hataları) hata olarak bildirilir
+    fn new(table: &'a Table, path: &str, known: &[&str]) -> Result<Self, ScenarioError> {
+        let fields = Fields { table, path: path.to_string() };
+        if let Some(key) = table.keys().find(|key| !known.contains(&key.as_str())) {
+            return Err(invalid(&fields.field(key), "tanınmayan alan"));
+        }
+        Ok(fields)
+    }
+
+    fn field(&self, key: &str) -> String {
+        if self.path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", self.path, key)
+        }
+    }
+
+    fn missing(&self, key: &str) -> ScenarioError {
+        invalid(&self.field(key), "alan gerekli")
+    }
+
+    fn integer(&self, key: &str) -> Result<Option<u64>, ScenarioError> {
+        match self.table.get(key) {
+            None => Ok(None),
+            Some(Value::Integer(value)) if *value >= 0 => Ok(Some(*value as u64)),
+            Some(_) => Err(invalid(&self.field(key), "negatif olmayan tam sayı olmalı")),
+        }
+    }
+
+    fn node(&self, key: &str) -> Result<usize, ScenarioError> {
+        self.integer(key)?.map(|value| value as usize).ok_or_else(|| self.missing(key))
+    }
+
+    // Tam sayılar da ondalık sayı olarak kabul edilir
+    fn float(&self, key: &str) -> Result<Option<f64>, ScenarioError> {
+        match self.table.get(key) {
+            None => Ok(None),
+            Some(Value::Float(value)) => Ok(Some(*value)),
+            Some(Value::Integer(value)) => Ok(Some(*value as f64)),
+            Some(_) => Err(invalid(&self.field(key), "sayı olmalı")),
+        }
+    }
+
+    fn boolean(&self, key: &str) -> Result<Option<bool>, ScenarioError> {
+        match self.table.get(key) {
+            None => Ok(None),
+            Some(Value::Boolean(value)) => Ok(Some(*value)),
+            Some(_) => Err(invalid(&self.field(key), "true veya false olmalı")),
+        }
+    }
+
+    fn string(&self, key: &str) -> Result<Option<&'a str>, ScenarioError> {
+        match self.table.get(key) {
+            None => Ok(None),
+            Some(Value::String(value)) => Ok(Some(value.as_str())),
+            Some(_) => Err(invalid(&self.field(key), "metin olmalı")),
+        }
+    }
+
+    fn table(&self, key: &str) -> Result<Option<&'a Table>, ScenarioError> {
+        match self.table.get(key) {
+            None => Ok(None),
+            Some(Value::Table(value)) => Ok(Some(value)),
+            Some(_) => Err(invalid(&self.field(key), "tablo olmalı")),
+        }
+    }
+
+    fn array(&self, key: &str) -> Result<Option<Vec<Value>>, ScenarioError> {
+        match self.table.get(key) {
+            None => Ok(None),
+            Some(Value::Array(values)) => Ok(Some(values.clone())),
+            Some(_) => Err(invalid(&self.field(key), "liste olmalı")),
+        }
+    }
+
+    // Metin olarak yazılan ve FromStr ile çözümlenen değer
+    fn parsed<T>(&self, key: &str) -> Result<Option<T>, ScenarioError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        match self.string(key)? {
+            Some(text) => text.parse().map(Some).map_err(|e: T::Err| invalid(&self.field(key), &e.to_string())),
+            None => Ok(None),
+        }
+    }
+
+    fn parsed_list<T>(&self, key: &str) -> Result<Option<Vec<T>>, ScenarioError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let values = match self.array(key)? {
+            Some(values) => values,
+            None => return Ok(None),
+        };
+        let field = self.field(key);
+        values
+            .iter()
+            .map(|value| match value {
+                Value::String(text) => text.parse().map_err(|e: T::Err| invalid(&field, &e.to_string())),
+                _ => Err(invalid(&field, "metin listesi olmalı")),
+            })
+            .collect::<Result<Vec<T>, _>>()
+            .map(Some)
+    }
+
+    fn nodes(&self, key: &str) -> Result<Option<Vec<usize>>, ScenarioError> {
+        let values = match self.array(key)? {
+            Some(values) => values,
+            None => return Ok(None),
+        };
+        node_list(&values, &self.field(key)).map(Some)
+    }
+
+    fn floats(&self, key: &str) -> Result<Option<Vec<f64>>, ScenarioError> {
+        let values = match self.array(key)? {
+            Some(values) => values,
+            None => return Ok(None),
+        };
+        values
+            .iter()
+            .map(|value| match value {
+                Value::Float(number) => Ok(*number),
+                Value::Integer(number) => Ok(*number as f64),
+                _ => Err(invalid(&self.field(key), "sayı listesi olmalı")),
+            })
+            .collect::<Result<Vec<f64>, _>>()
+            .map(Some)
+    }
+}
+
+fn invalid(field: &str, message: &str) -> ScenarioError {
+    ScenarioError::InvalidField { field: field.to_string(), message: message.to_string() }
+}
+
+fn node_list(values: &[Value], field: &str) -> Result<Vec<usize>, ScenarioError> {
+    values
+        .iter()
+        .map(|value| match value {
+            Value::Integer(id) if *id >= 0 => Ok(*id as usize),
+            _ => Err(invalid(field, "node ID listesi olmalı")),
+        })
+        .collect()
+}
+
+// [network] ve [network.link] tabloları
+fn parse_network(table: &Table) -> Result<NetworkSpec, ScenarioError> {
+    let fields = Fields::new(
+        table,
+        "network",
+        &["nodes", "difficulty", "block_time", "consensus", "mining", "topology", "relay", "link", "hashrates", "finality", "request_timeout"],
+    )?;
+    let defaults = NetworkSpec::default();
+
+    let link = match fields.table("link")? {
+        Some(table) => {
+            let link = Fields::new(table, "network.link", &["latency", "loss_rate", "bandwidth"])?;
+            let loss_rate = link.float("loss_rate")?.unwrap_or(0.0);
+            if !(0.0..=1.0).contains(&loss_rate) {
+                return Err(invalid("network.link.loss_rate", "0 ile 1 arasında olmalı"));
+            }
+            LinkConfig {
+                latency: link.parsed::<LatencyModel>("latency")?.unwrap_or(LatencyModel::Constant(0)),
+                loss_rate,
+                bandwidth: link.integer("bandwidth")?.unwrap_or(0),
+            }
+        }
+        None => defaults.link,
+    };
+
+    Ok(NetworkSpec {
+        nodes: fields.integer("nodes")?.map(|nodes| nodes as usize).unwrap_or(defaults.nodes),
+        difficulty: fields.integer("difficulty")?.map(|difficulty| difficulty as usize).unwrap_or(defaults.difficulty),
+        block_time: fields.integer("block_time")?.unwrap_or(defaults.block_time),
+        consensus: fields.parsed("consensus")?.unwrap_or(defaults.consensus),
+        mining: fields.parsed("mining")?.unwrap_or(defaults.mining),
+        topology: fields.parsed("topology")?.unwrap_or(defaults.topology),
+        relay: fields.parsed("relay")?.unwrap_or(defaults.relay),
+        link,
+        hashrates: fields.floats("hashrates")?.unwrap_or_default(),
+        finality: fields.boolean("finality")?.unwrap_or(defaults.finality),
+        request_timeout: fields.integer("request_timeout")?.unwrap_or(defaults.request_timeout),
+    })
+}
+
+// [[events]] tablosundaki bir adım; her adımın "at" (ms) ve "action" alanı vardır
+fn parse_event(table: &Table, path: &str) -> Result<TimedAction, ScenarioError> {
+    let action = match table.get("action") {
+        Some(Value::String(action)) => action.as_str(),
+        Some(_) => return Err(invalid(&format!("{}.action", path), "metin olmalı")),
+        None => return Err(invalid(&format!("{}.action", path), "alan gerekli")),
+    };
+    let keys: &[&str] = match action {
+        "transfer" => &["from", "to", "amount"],
+        "partition" => &["groups"],
+        "hashrate" => &["node", "value"],
+        "join" => &["peers"],
+        "leave" | "crash" | "restart" => &["node"],
+        "churn" => &["nodes", "session", "downtime", "crash_probability"],
+        "misbehave" => &["node", "behaviors"],
+        "workload" => &["rate", "min_amount", "amount_shape", "hotspots", "hotspot_share", "fan_out", "batch_probability", "senders"],
+        "selfish" => &["node", "hash_share", "gamma"],
+        "double_spend" => &["attacker", "merchant", "amount", "confirmations", "hash_share"],
+        "eclipse" => &["victim", "sybils", "hash_share", "diverse_outbound", "anchors"],
+        _ => &[],
+    };
+    let known: Vec<&str> = ["at", "action"].iter().chain(keys.iter()).copied().collect();
+    let fields = Fields::new(table, path, &known)?;
+    let at = fields.integer("at")?.ok_or_else(|| fields.missing("at"))?;
+    let amount = |key: &str| -> Result<u64, ScenarioError> {
+        match fields.float(key)? {
+            Some(coins) if coins > 0.0 => Ok((coins * COIN).round() as u64),
+            Some(_) => Err(invalid(&fields.field(key), "pozitif olmalı")),
+            None => Err(fields.missing(key)),
+        }
+    };
+    let share = |key: &str| fields.float(key).and_then(|value| value.ok_or_else(|| fields.missing(key)));
+
+    let action = match action {
+        "transfer" => ScenarioAction::Transfer { from: fields.node("from")?, to: fields.node("to")?, amount: amount("amount")? },
+        "partition" => {
+            let groups = fields.array("groups")?.ok_or_else(|| fields.missing("groups"))?;
+            let field = fields.field("groups");
+            let groups = groups
+                .iter()
+                .map(|group| match group {
+                    Value::Array(ids) => node_list(ids, &field),
+                    _ => Err(invalid(&field, "node ID listelerinin listesi olmalı")),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            ScenarioAction::Partition { groups }
+        }
+        "heal" => ScenarioAction::Heal,
+        "hashrate" => ScenarioAction::Hashrate { node: fields.node("node")?, value: share("value")? },
+        "join" => ScenarioAction::Join { peers: fields.nodes("peers")?.unwrap_or_default() },
+        "leave" => ScenarioAction::Leave { node: fields.node("node")? },
+        "crash" => ScenarioAction::Crash { node: fields.node("node")? },
+        "restart" => ScenarioAction::Restart { node: fields.node("node")? },
+        "churn" => ScenarioAction::Churn {
+            nodes: fields.nodes("nodes")?.ok_or_else(|| fields.missing("nodes"))?,
+            model: ChurnModel {
+                session: fields.parsed::<SessionLength>("session")?.ok_or_else(|| fields.missing("session"))?,
+                downtime: fields.parsed::<SessionLength>("downtime")?.ok_or_else(|| fields.missing("downtime"))?,
+                crash_probability: fields.float("crash_probability")?.unwrap_or(0.0),
+            },
+        },
+        "stop_churn" => ScenarioAction::StopChurn,
+        "misbehave" => ScenarioAction::Misbehave {
+            node: fields.node("node")?,
+            behaviors: fields.parsed_list::<Misbehavior>("behaviors")?.unwrap_or_default(),
+        },
+        "stop_mining" => ScenarioAction::StopMining,
+        "workload" => {
+            let defaults = Workload::default();
+            ScenarioAction::Workload {
+                config: Workload {
+                    rate: fields.float("rate")?.ok_or_else(|| fields.missing("rate"))?,
+                    min_amount: match fields.float("min_amount")? {
+                        Some(_) => amount("min_amount")?,
+                        None => defaults.min_amount,
+                    },
+                    amount_shape: fields.float("amount_shape")?.unwrap_or(defaults.amount_shape),
+                    hotspots: fields.integer("hotspots")?.map(|count| count as usize).unwrap_or(defaults.hotspots),
+                    hotspot_share: fields.float("hotspot_share")?.unwrap_or(defaults.hotspot_share),
+                    fan_out: fields.integer("fan_out")?.map(|count| count as usize).unwrap_or(defaults.fan_out),
+                    batch_probability: fields.float("batch_probability")?.unwrap_or(defaults.batch_probability),
+                },
+                senders: fields.nodes("senders")?.unwrap_or_default(),
+            }
+        }
+        "stop_workload" => ScenarioAction::StopWorkload,
+        #[cfg(feature = "attacks")]
+        "selfish" => ScenarioAction::SelfishMining {
+            node: fields.node("node")?,
+            hash_share: share("hash_share")?,
+            gamma: fields.float("gamma")?.unwrap_or(0.0),
+        },
+        #[cfg(feature = "attacks")]
+        "stop_selfish" => ScenarioAction::StopSelfishMining,
+        #[cfg(feature = "attacks")]
+        "double_spend" => ScenarioAction::DoubleSpend {
+            attacker: fields.node("attacker")?,
+            merchant: fields.node("merchant")?,
+            amount: amount("amount")?,
+            confirmations: fields.integer("confirmations")?.map(|k| k as usize).unwrap_or(6),
+            hash_share: share("hash_share")?,
+        },
+        #[cfg(feature = "attacks")]
+        "eclipse" => ScenarioAction::Eclipse {
+            victim: fields.node("victim")?,
+            sybils: fields.node("sybils")?,
+            hash_share: fields.float("hash_share")?.unwrap_or(0.0),
+            defense: EclipseDefense {
+                diverse_outbound: fields.boolean("diverse_outbound")?.unwrap_or(false),
+                anchors: fields.integer("anchors")?.map(|anchors| anchors as usize).unwrap_or(0),
+            },
+        },
+        #[cfg(feature = "attacks")]
+        "stop_eclipse" => ScenarioAction::StopEclipse,
+        #[cfg(not(feature = "attacks"))]
+        "selfish" | "stop_selfish" | "double_spend" | "eclipse" | "stop_eclipse" => {
+            return Err(invalid(&fields.field("action"), "saldırı senaryoları için \"attacks\" özelliği gerekli"));
+        }
+        other => return Err(invalid(&fields.field("action"), &format!("bilinmeyen adım: {}", other))),
+    };
+    Ok(TimedAction { at, action })
+}
+
+// Adımların andığı node'lar, o ana kadar ağa katılanlar dahil var olmalı
+// Adımlar çalıştırmadaki gibi zamana göre (aynı anda olanlar dosya sırasıyla) sıralanır.
+fn check_node_ids(events: &[TimedAction], initial_nodes: usize) -> Result<(), ScenarioError> {
+    let mut order: Vec<usize> = (0..events.len()).collect();
+    order.sort_by_key(|&index| events[index].at);
+
+    let mut node_count = initial_nodes;
+    for index in order {
+        let action = &events[index].action;
+        let nodes: Vec<usize> = match action {
+            ScenarioAction::Transfer { from, to, .. } => vec![*from, *to],
+            ScenarioAction::Partition { groups } => groups.iter().flatten().copied().collect(),
+            ScenarioAction::Join { peers } => peers.clone(),
+            ScenarioAction::Churn { nodes, .. } => nodes.clone(),
+            ScenarioAction::Workload { senders, .. } => senders.clone(),
+            ScenarioAction::Hashrate { node, .. }
+            | ScenarioAction::Leave { node }
+            | ScenarioAction::Crash { node }
+            | ScenarioAction::Restart { node }
+            | ScenarioAction::Misbehave { node, .. } => vec![*node],
+            #[cfg(feature = "attacks")]
+            ScenarioAction::SelfishMining { node, .. } => vec![*node],
+            #[cfg(feature = "attacks")]
+            ScenarioAction::DoubleSpend { attacker, merchant, .. } => vec![*attacker, *merchant],
+            #[cfg(feature = "attacks")]
+            ScenarioAction::Eclipse { victim, .. } => vec![*victim],
+            _ => Vec::new(),
+        };
+        if let Some(missing) = nodes.into_iter().find(|&id| id >= node_count) {
+            return Err(invalid(
+                &format!("events[{}]", index),
+                &format!("Node {} yok ({} ms'de ağda {} node var)", missing, events[index].at, node_count),
+            ));
+        }
+
+        // Katılan node ve Sybil'ler sonraki adımlarda kullanılabilir
+        match action {
+            ScenarioAction::Join { .. } => node_count += 1,
+            #[cfg(feature = "attacks")]
+            ScenarioAction::Eclipse { sybils, .. } => node_count += sybils,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+// TOML tam sayıları i64'tür
+fn integer<T: TryInto<i64>>(value: T) -> Value {
+    Value::Integer(value.try_into().unwrap_or(i64::MAX))
+}
+
+// Bir metriğin çalıştırma sonundaki değerleri
+fn collect_metric(network: &BlockchainNetwork, metric: Metric) -> Table {
+    let mut table = Table::new();
+    let mut put = |key: &str, value: Value| {
+        table.insert(key.to_string(), value);
+    };
+
+    match metric {
+        Metric::Chain => {
+            let chain = network.canonical_chain();
+            let tips: HashSet<&str> = network
+                .nodes
+                .iter()
+                .filter(|node| node.online)
+                .filter_map(|node| node.blockchain.last().map(|block| block.hash.as_str()))
+                .collect();
+            let confirmed: usize = chain.iter().map(|block| block.transactions.iter().filter(|tx| !tx.is_coinbase()).count()).sum();
+            let seconds = network.now() as f64 / 1000.0;
+            put("height", integer(chain.len()));
+            put("tips", integer(tips.len()));
+            put("converged", Value::from(tips.len() == 1));
+            put("stale_blocks", integer(network.propagation.stale_blocks(chain).len()));
+            put("stale_rate", Value::from(network.stale_block_rate()));
+            put("confirmed_transactions", integer(confirmed));
+            put("pending_transactions", integer(network.mempool.len()));
+            if seconds > 0.0 {
+                put("throughput_tps", Value::from(confirmed as f64 / seconds));
+            }
+        }
+        Metric::Propagation => {
+            for (key, fraction) in [("p50_ms", 0.5), ("p90_ms", 0.9), ("full_ms", 1.0)] {
+                if let Some(delay) = network.average_propagation_delay(fraction) {
+                    put(key, Value::from(delay));
+                }
+            }
+        }
+        Metric::Miners => {
+            let miners: Vec<Value> = network
+                .miner_shares()
+                .iter()
+                .map(|share| {
+                    let mut miner = Table::new();
+                    miner.insert("node".into(), integer(share.node_id));
+                    miner.insert("hashrate".into(), Value::from(share.hashrate));
+                    miner.insert("hash_share".into(), Value::from(share.hash_share));
+                    miner.insert("blocks".into(), integer(share.blocks));
+                    miner.insert("block_share".into(), Value::from(share.block_share));
+                    Value::Table(miner)
+                })
+                .collect();
+            put("nodes", Value::Array(miners));
+        }
+        Metric::Reorgs => {
+            put("count", integer(network.reorgs.len()));
+            put("reverted_blocks", integer(network.reorgs.iter().map(|reorg| reorg.reverted.len()).sum::<usize>()));
+            put("max_depth", integer(network.reorgs.iter().map(|reorg| reorg.reverted.len()).max().unwrap_or(0)));
+        }
+        Metric::Traffic => {
+            let mut by_kind = Table::new();
+            for stats in &network.traffic {
+                for (kind, bytes) in &stats.bytes_sent_by_kind {
+                    let total = by_kind.get(*kind).and_then(Value::as_integer).unwrap_or(0);
+                    by_kind.insert(kind.to_string(), integer(total as u64 + bytes));
+                }
+            }
+            let bandwidth = network.bandwidth_per_node();
+            put("messages_sent", integer(network.traffic.iter().map(|stats| stats.messages_sent).sum::<u64>()));
+            put("bytes_sent", integer(network.traffic.iter().map(|stats| stats.bytes_sent).sum::<u64>()));
+            if !bandwidth.is_empty() {
+                put("average_bandwidth", Value::from(bandwidth.iter().sum::<f64>() / bandwidth.len() as f64));
+            }
+            put("bytes_by_kind", Value::Table(by_kind));
+        }
+        Metric::Partition => {
+            put("partitioned", Value::from(network.is_partitioned()));
+            if let Some(report) = network.heal_report() {
+                put("healed_at", integer(report.healed_at));
+                put("reorged_nodes", integer(report.reorged_nodes.len()));
+                put("reverted_blocks", integer(report.reverted_blocks.len()));
+                put("reverted_transactions", integer(report.reverted_transactions.len()));
+                put("unconfirmed_transactions", integer(report.unconfirmed_transactions.len()));
+                put("converged", Value::from(report.converged));
+            }
+        }
+        Metric::Churn => {
+            let report = network.churn_report();
+            put("online", integer(report.online));
+            put("offline", integer(report.offline.len()));
+            put("departures", integer(report.departures));
+            put("crashes", integer(report.crashes));
+            put("restarts", integer(report.restarts));
+            put("resynced", integer(report.resynced));
+            if let Some(time) = report.average_resync_time {
+                put("average_resync_ms", Value::from(time));
+            }
+            if let Some(blocks) = report.average_blocks_behind {
+                put("average_blocks_behind", Value::from(blocks));
+            }
+            put("tips", integer(report.tips));
+            put("pending_transactions", integer(report.pending_transactions));
+            if let Some(coverage) = report.mempool_coverage {
+                put("mempool_coverage", Value::from(coverage));
+            }
+        }
+        Metric::Bans => {
+            put("total", integer(network.bans.len()));
+            put("active", integer(network.active_bans(None).len()));
+            let bans: Vec<Value> = network
+                .bans
+                .iter()
+                .map(|record| {
+                    let mut ban = Table::new();
+                    ban.insert("node".into(), integer(record.node_id));
+                    ban.insert("peer".into(), integer(record.peer));
+                    ban.insert("reason".into(), Value::from(record.reason.to_string()));
+                    ban.insert("banned_at".into(), integer(record.banned_at));
+                    Value::Table(ban)
+                })
+                .collect();
+            put("records", Value::Array(bans));
+        }
+        Metric::Byzantine => {
+            let report = network.byzantine_report();
+            put("honest_height", integer(report.honest_height));
+            put("honest_tips", integer(report.honest_tips));
+            let nodes: Vec<Value> = report
+                .nodes
+                .iter()
+                .map(|(id, node)| {
+                    let effects = &node.effects;
+                    let mut entry = Table::new();
+                    entry.insert("node".into(), integer(*id));
+                    entry.insert("behaviors".into(), Value::from(node.behaviors.iter().map(|behavior| behavior.to_string()).collect::<Vec<_>>()));
+                    entry.insert("dropped_transactions".into(), integer(effects.dropped_transactions));
+                    entry.insert("withheld_blocks".into(), integer(effects.withheld_blocks));
+                    entry.insert("invalid_blocks_sent".into(), integer(effects.invalid_blocks_sent));
+                    entry.insert("spam_sent".into(), integer(effects.spam_sent));
+                    entry.insert("delayed_messages".into(), integer(effects.delayed_messages));
+                    entry.insert("false_announcements".into(), integer(effects.false_announcements));
+                    entry.insert("rejected_blocks".into(), integer(effects.rejected_blocks));
+                    entry.insert("rejected_transactions".into(), integer(effects.rejected_transactions));
+                    entry.insert("unanswered_requests".into(), integer(effects.unanswered_requests));
+                    Value::Table(entry)
+                })
+                .collect();
+            put("nodes", Value::Array(nodes));
+        }
+        Metric::Finality => {
+            let status = network.finality_status();
+            let finalized = status.iter().map(|status| status.finalized_height);
+            put("enabled", Value::from(!status.is_empty()));
+            put("min_finalized_height", integer(finalized.clone().min().unwrap_or(0)));
+            put("max_finalized_height", integer(finalized.max().unwrap_or(0)));
+            put("conflicts", integer(network.finality_conflicts().len()));
+        }
+        Metric::Workload => {
+            if let Some(report) = network.workload_report() {
+                put("submitted", integer(report.submitted));
+                put("payments", integer(report.payments));
+                put("insufficient_funds", integer(report.insufficient_funds));
+                put("failed", integer(report.failed));
+                put("offered_tps", Value::from(report.offered_tps));
+                put("confirmed", integer(report.confirmed));
+                put("confirmed_tps", Value::from(report.confirmed_tps));
+                put("pending", integer(report.pending));
+                if let Some(time) = report.average_confirmation_time {
+                    put("average_confirmation_ms", Value::from(time));
+                }
+                if let Some(time) = report.max_confirmation_time {
+                    put("max_confirmation_ms", integer(time));
+                }
+                put("peak_mempool", integer(report.peak_mempool));
+                put("average_mempool", Value::from(report.average_mempool));
+                put("blocks", integer(report.blocks));
+                put("full_blocks", integer(report.full_blocks));
+                put("hot_recipients", Value::from(report.hot_recipients.iter().map(|&id| integer(id)).collect::<Vec<_>>()));
+                put("hot_payments", integer(report.hot_payments));
+            }
+        }
+        Metric::Attacks => {
+            if let Some(report) = network.selfish_mining_report() {
+                let mut selfish = Table::new();
+                selfish.insert("node".into(), integer(report.node_id));
+                selfish.insert("hash_share".into(), Value::from(report.hash_share));
+                selfish.insert("revenue_share".into(), Value::from(report.revenue_share));
+                selfish.insert("expected_share".into(), Value::from(report.expected_share));
+                selfish.insert("orphaned_honest".into(), integer(report.orphaned_honest));
+                put("selfish", Value::Table(selfish));
+            }
+            if let Some(report) = network.double_spend_report() {
+                let mut double_spend = Table::new();
+                double_spend.insert("attacker".into(), integer(report.attacker));
+                double_spend.insert("status".into(), Value::from(report.status.to_string()));
+                double_spend.insert("reversed".into(), Value::from(report.reversed));
+                double_spend.insert("private_blocks".into(), integer(report.private_blocks));
+                double_spend.insert("honest_blocks".into(), integer(report.honest_blocks));
+                double_spend.insert("success_probability".into(), Value::from(report.success_probability));
+                put("double_spend", Value::Table(double_spend));
+            }
+            if let Some(report) = network.eclipse_report() {
+                let mut eclipse = Table::new();
+                eclipse.insert("victim".into(), integer(report.victim));
+                eclipse.insert("eclipsed".into(), Value::from(report.eclipsed));
+                eclipse.insert("missed_blocks".into(), integer(report.missed_blocks));
+                eclipse.insert("fake_blocks".into(), integer(report.fake_blocks));
+                put("eclipse", Value::Table(eclipse));
+            }
+        }
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Result<Scenario, ScenarioError> {
+        text.parse()
+    }
+
+    fn invalid_field(text: &str) -> String {
+        match parse(text) {
+            Err(ScenarioError::InvalidField { field, .. }) => field,
+            other => panic!("alan hatası bekleniyordu: {:?}", other.map(|scenario| scenario.name)),
+        }
+    }
+
+    #[test]
+    fn valid_scenario_is_parsed() {
+        let scenario = parse(
+            r#"
+            duration = 60000
+            [network]
+            nodes = 3
+            [network.link]
+            latency = "uniform:50:200"
+            [[events]]
+            at = 1000
+            action = "join"
+            peers = [0]
+            [[events]]
+            at = 2000
+            action = "crash"
+            node = 3
+            "#,
+        )
+        .unwrap();
+        assert_eq!(scenario.network.nodes, 3);
+        assert_eq!(scenario.network.link.latency, LatencyModel::Uniform { min: 50, max: 200 });
+        assert_eq!(scenario.events.len(), 2);
+    }
+
+    #[test]
+    fn unknown_action_is_rejected() {
+        let text = r#"
+            duration = 1000
+            [[events]]
+            at = 0
+            action = "explode"
+        "#;
+        assert_eq!(invalid_field(text), "events[0].action");
+    }
+
+    #[test]
+    fn node_index_out_of_range_is_rejected() {
+        let text = r#"
+            duration = 1000
+            [network]
+            nodes = 3
+            [[events]]
+            at = 500
+            action = "crash"
+            node = 3
+        "#;
+        assert_eq!(invalid_field(text), "events[0]");
+
+        // Node ancak katıldıktan sonra kullanılabilir
+        let text = r#"
+            duration = 1000
+            [network]
+            nodes = 3
+            [[events]]
+            at = 500
+            action = "crash"
+            node = 3
+            [[events]]
+            at = 100
+            action = "join"
+            peers = [0]
+            [[events]]
+            at = 900
+            action = "transfer"
+            from = 0
+            to = 4
+            amount = 1
+        "#;
+        assert_eq!(invalid_field(text), "events[2]");
+    }
+
+    #[test]
+    fn bad_latency_spec_is_rejected() {
+        for latency in ["uniform:50", "uniform:abc:100", "const:-5", "gauss:10"] {
+            let text = format!("duration = 1000\n[network.link]\nlatency = \"{}\"\n", latency);
+            assert_eq!(invalid_field(&text), "network.link.latency", "{}", latency);
+        }
+    }
+
+    #[test]
+    fn unknown_field_and_missing_duration_are_rejected() {
+        assert_eq!(invalid_field("duration = 1000\n[network]\nnode = 3\n"), "network.node");
+        assert_eq!(invalid_field("[network]\nnodes = 3\n"), "duration");
+        assert!(matches!(parse("duration = "), Err(ScenarioError::Parse(_))));
+    }
+}
@@ -0,0 +1,290 @@
+use std::fmt;
+
+use rand::Rng;
+
+use crate::block::Block;
+#[cfg(feature = "attacks")]
+use crate::error::NetworkError;
+use crate::message::Message;
+use crate::network::BlockchainNetwork;
+
+// Bencil madencilik (Eyal & Sirer, 2013) saldırısının durumu
+// Saldırgan bulduğu blokları gizli bir dalda biriktirir ve dürüst madenciler blok buldukça
+// önündeki farka göre bloklarını stratejik olarak yayınlar. Saldırganın dürüst blokları
+// bulundukları anda öğrendiği (ağı dinlediği) kabul edilir.
+#[derive(Debug, Clone)]
+pub struct SelfishMiner {
+    pub node_id: usize,
+    pub gamma: f64,              // Eşit uzunlukta iki dal yarışırken saldırganın dalında madencilik yapan dürüst hash gücü payı
+    pub public_length: usize,    // Dürüst node'ların bildiği en uzun zincirin uzunluğu
+    pub published: usize,        // Saldırganın zincirinin yayınlanmış kısmının uzunluğu
+    pub private_branch: usize,   // Dürüst zincirden ayrıldıktan sonra saldırganın bulduğu blok sayısı
+    pub start_height: usize,     // Saldırı başladığında dürüst zincirin uzunluğu
+    pub attacker_mined: usize,   // Saldırı boyunca saldırganın bulduğu bloklar
+    pub honest_mined: usize,     // Saldırı boyunca dürüst madencilerin bulduğu bloklar
+    pub races: usize,            // Eşit uzunluktaki dalların yarıştığı durum sayısı
+}
+
+// Saldırganın gelir payının hash gücü payıyla karşılaştırması
+#[derive(Debug, Clone)]
+pub struct SelfishMiningReport {
+    pub node_id: usize,
+    pub hash_share: f64,
+    pub gamma: f64,
+    pub attacker_blocks: usize, // Saldırı başladıktan sonra dürüst node'ların zincirine giren saldırgan blokları
+    pub honest_blocks: usize,   // Aynı zincire giren dürüst bloklar
+    pub revenue_share: f64,     // attacker_blocks / (attacker_blocks + honest_blocks)
+    pub expected_share: f64,    // Eyal & Sirer modelinin öngördüğü gelir payı
+    pub threshold: f64,         // Bu gamma için bencil madenciliğin kârlı olduğu en küçük hash gücü payı
+    pub orphaned_honest: usize, // Zincire giremeyen dürüst bloklar
+    pub withheld: usize,        // Hâlâ yayınlanmamış saldırgan blokları
+    pub races: usize,
+}
+
+impl fmt::Display for SelfishMiningReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Bencil madenci Node {} (hash gücü payı %{:.1}, gamma {:.2}):", self.node_id, self.hash_share * 100.0, self.gamma)?;
+        writeln!(f, "  Zincirdeki bloklar: {} saldırgan, {} dürüst", self.attacker_blocks, self.honest_blocks)?;
+        writeln!(f, "  Gelir payı: %{:.1} (model: %{:.1})", self.revenue_share * 100.0, self.expected_share * 100.0)?;
+        writeln!(f, "  Kârlılık eşiği: %{:.1} hash gücü", self.threshold * 100.0)?;
+        writeln!(f, "  Zincire giremeyen dürüst bloklar: {}", self.orphaned_honest)?;
+        write!(f, "  Eşit dal yarışları: {}, yayınlanmamış bloklar: {}", self.races, self.withheld)
+    }
+}
+
+// Eyal & Sirer modelinde hash gücü payı alpha ve bağlantı avantajı gamma olan bencil madencinin gelir payı
+pub fn expected_revenue_share(alpha: f64, gamma: f64) -> f64 {
+    let numerator = alpha * (1.0 - alpha).powi(2) * (4.0 * alpha + gamma * (1.0 - 2.0 * alpha)) - alpha.powi(3);
+    let denominator = 1.0 - alpha * (1.0 + (2.0 - alpha) * alpha);
+    numerator / denominator
+}
+
+// Bencil madenciliğin dürüst madencilikten kârlı olduğu en küçük hash gücü payı
+pub fn profitability_threshold(gamma: f64) -> f64 {
+    (1.0 - gamma) / (3.0 - 2.0 * gamma)
+}
+
+impl BlockchainNetwork {
+    // Saldırı senaryosu: node'u bencil madenci yap
+    // Node'un hash gücü, toplam hash gücünün hash_share kadarı olacak şekilde ayarlanır.
+    // gamma, eşit uzunlukta iki dal yarışırken saldırganın bloğunu önce gören dürüst hash gücü payıdır (ağdaki bağlantı avantajı).
+    #[cfg(feature = "attacks")]
+    pub fn start_selfish_mining(&mut self, node_id: usize, hash_share: f64, gamma: f64) -> Result<(), NetworkError> {
+        if self.consensus != crate::stake::ConsensusMode::ProofOfWork || self.mining_model != crate::mining::MiningModel::HashRace {
+            return Err(NetworkError::HashRaceRequired);
+        }
+        if self.attack_in_progress() {
+            return Err(NetworkError::AttackInProgress);
+        }
+        if !(hash_share > 0.0 && hash_share < 1.0) {
+            return Err(NetworkError::InvalidShare { name: "hash gücü payı", value: hash_share.to_string() });
+        }
+        if !(0.0..=1.0).contains(&gamma) {
+            return Err(NetworkError::InvalidShare { name: "gamma", value: gamma.to_string() });
+        }
+        let node = self.nodes.get(node_id).ok_or(NetworkError::NodeNotFound(node_id))?;
+
+        // Saldırganın hash gücü dürüst hash gücüne göre ayarlanır
+        let honest = self.total_hashrate() - node.hashrate;
+        if honest <= 0.0 {
+            return Err(NetworkError::NoHashPower);
+        }
+        let length = node.blockchain.len();
+        self.set_hashrate(node_id, honest * hash_share / (1.0 - hash_share))?;
+
+        let public_length = self.honest_chain(node_id).len();
+        self.selfish = Some(SelfishMiner {
+            node_id,
+            gamma,
+            public_length,
+            published: length,
+            private_branch: 0,
+            start_height: public_length,
+            attacker_mined: 0,
+            honest_mined: 0,
+            races: 0,
+        });
+        Ok(())
+    }
+
+    // Bencil madenciliği bitir: son raporu döndür ve gizli blokları yayınla
+    #[cfg(feature = "attacks")]
+    pub fn stop_selfish_mining(&mut self) -> Option<SelfishMiningReport> {
+        let report = self.selfish_mining_report()?;
+        let length = self.nodes[report.node_id].blockchain.len();
+        self.publish_private_blocks(length);
+        self.selfish = None;
+        self.process_due_events();
+        Some(report)
+    }
+
+    // Saldırganın dürüst node'ların zincirindeki gelir payı
+    pub fn selfish_mining_report(&self) -> Option<SelfishMiningReport> {
+        let miner = self.selfish.as_ref()?;
+        let attacker = &self.nodes[miner.node_id];
+        let address = attacker.get_address();
+
+        let chain = self.honest_chain(miner.node_id);
+        let mut attacker_blocks = 0;
+        let mut honest_blocks = 0;
+        for block in chain.iter().skip(miner.start_height) {
+            let coinbase = block.transactions.first().filter(|tx| tx.is_coinbase());
+            if coinbase.map(|tx| tx.outputs[0].recipient_address == address).unwrap_or(false) {
+                attacker_blocks += 1;
+            } else {
+                honest_blocks += 1;
+            }
+        }
+
+        let total = self.total_hashrate();
+        let hash_share = if total > 0.0 { attacker.hashrate / total } else { 0.0 };
+        let blocks = attacker_blocks + honest_blocks;
+        Some(SelfishMiningReport {
+            node_id: miner.node_id,
+            hash_share,
+            gamma: miner.gamma,
+            attacker_blocks,
+            honest_blocks,
+            revenue_share: if blocks > 0 { attacker_blocks as f64 / blocks as f64 } else { 0.0 },
+            expected_share: expected_revenue_share(hash_share, miner.gamma),
+            threshold: profitability_threshold(miner.gamma),
+            orphaned_honest: miner.honest_mined.saturating_sub(honest_blocks),
+            withheld: attacker.blockchain.len().saturating_sub(miner.published),
+            races: miner.races,
+        })
+    }
+
+    // Node bencil madenci mi (bulduğu bloklar hemen yayılmaz)
+    pub(crate) fn is_selfish_miner(&self, node_id: usize) -> bool {
+        self.selfish.as_ref().map(|miner| miner.node_id == node_id).unwrap_or(false)
+    }
+
+    // Saldırganın (bencil madenci veya çifte harcayan) zincirinin diğer node'lara gösterilebilecek uzunluğu
+    pub(crate) fn published_length(&self, node_id: usize) -> Option<usize> {
+        if self.is_double_spender(node_id) {
+            return self.double_spend.as_ref().map(|attack| attack.fork_height);
+        }
+        self.selfish.as_ref().filter(|miner| miner.node_id == node_id).map(|miner| miner.published)
+    }
+
+    // Saldırgan gizli dalına yeni bir blok ekledi
+    // Eşit uzunlukta dallar yarışırken ikinci bloğu bulursa tüm dalı yayınlar ve yarışı kazanır
+    pub(crate) fn selfish_private_block(&mut self) {
+        let miner = match self.selfish.as_mut() {
+            Some(miner) => miner,
+            None => return,
+        };
+        let length = self.nodes[miner.node_id].blockchain.len();
+        let lead_before = length as isize - 1 - miner.public_length as isize;
+        miner.private_branch += 1;
+        miner.attacker_mined += 1;
+
+        if lead_before == 0 && miner.private_branch == 2 {
+            miner.private_branch = 0;
+            self.publish_private_blocks(length);
+        }
+    }
+
+    // Dürüst bir madenci blok buldu; saldırgan önündeki farka göre tepki verir
+    pub(crate) fn selfish_public_block(&mut self, producer: usize, block: &Block) {
+        let miner = match self.selfish.as_mut() {
+            Some(miner) => miner,
+            None => return,
+        };
+        miner.honest_mined += 1;
+
+        // Dürüst zinciri uzatmayan bloklar (eski uçlar üzerine bulunanlar) durumu değiştirmez
+        let public_length = block.index + 1;
+        if public_length <= miner.public_length {
+            return;
+        }
+        let attacker = miner.node_id;
+        let length = self.nodes[attacker].blockchain.len();
+        let lead_before = length as isize - miner.public_length as isize;
+        miner.public_length = public_length;
+
+        match lead_before {
+            // Önde değil: dürüst zinciri benimse ve onun üzerinde madenciliğe devam et
+            lead if lead <= 0 => self.selfish_adopt(producer),
+            // Tek blok önde: son bloğu yayınla ve eşit uzunluktaki dallar yarışsın
+            1 => {
+                if let Some(miner) = self.selfish.as_mut() {
+                    miner.races += 1;
+                }
+                self.publish_private_blocks(length);
+                self.selfish_rush(producer, length - 1);
+            }
+            // İki blok önde: tüm dalı yayınla, daha uzun dal dürüst bloğu geçersiz kılar
+            2 => {
+                if let Some(miner) = self.selfish.as_mut() {
+                    miner.private_branch = 0;
+                }
+                self.publish_private_blocks(length);
+            }
+            // Daha fazla önde: sadece dürüst bloğun yüksekliğindeki bloğu yayınla
+            _ => self.publish_private_blocks(public_length),
+        }
+    }
+
+    // Saldırganın zincirini verilen uzunluğa kadar yayınla
+    fn publish_private_blocks(&mut self, length: usize) {
+        let attacker = match self.selfish.as_ref() {
+            Some(miner) => miner.node_id,
+            None => return,
+        };
+        let from = self.selfish.as_ref().map(|miner| miner.published).unwrap_or(length);
+        let now = self.now();
+        for index in from..length {
+            let block = self.nodes[attacker].blockchain[index].clone();
+            self.propagation.record_mined(&block, attacker, now);
+            self.broadcast_block(attacker, &block);
+        }
+
+        if let Some(miner) = self.selfish.as_mut() {
+            miner.published = miner.published.max(length);
+            miner.public_length = miner.public_length.max(length);
+        }
+    }
+
+    // Eşit dal yarışında saldırganın bağlantı avantajı: dürüst hash gücünün gamma kadarı saldırganın
+    // bloğunu dürüst bloktan önce görür. Dürüst bloğu bulan madenci kendi bloğunda kalacağı için
+    // diğer dürüst node'lar, toplamda gamma payına ulaşacak olasılıkla seçilir.
+    fn selfish_rush(&mut self, producer: usize, index: usize) {
+        let (attacker, gamma) = match self.selfish.as_ref() {
+            Some(miner) => (miner.node_id, miner.gamma),
+            None => return,
+        };
+        let honest = self.total_hashrate() - self.nodes[attacker].hashrate;
+        let others = honest - self.nodes[producer].hashrate;
+        if others <= 0.0 {
+            return;
+        }
+        let probability = (gamma * honest / others).min(1.0);
+
+        let block = self.nodes[attacker].blockchain[index].clone();
+        for node in 0..self.nodes.len() {
+            if node == attacker || node == producer {
+                continue;
+            }
+            if self.rng.random::<f64>() < probability {
+                self.deliver_message(attacker, node, Message::Block(block.clone()));
+            }
+        }
+    }
+
+    // Saldırgan dürüst zinciri benimser: bilmediği dürüst blokları sırayla alır
+    fn selfish_adopt(&mut self, producer: usize) {
+        let attacker = match self.selfish.as_ref() {
+            Some(miner) => miner.node_id,
+            None => return,
+        };
+        self.catch_up(attacker, producer);
+
+        let length = self.nodes[attacker].blockchain.len();
+        if let Some(miner) = self.selfish.as_mut() {
+            miner.private_branch = 0;
+            miner.published = length;
+            miner.public_length = miner.public_length.max(length);
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+// Sanal zaman (milisaniye cinsinden)
+// Simülasyon duvar saatini (SystemTime) kullanmaz; zaman sadece olaylar işlendikçe ilerler
+pub type SimTime = u64;
+
+// Kuyruktaki zamanlanmış bir olay
+#[derive(Debug)]
+struct Scheduled<E> {
+    time: SimTime,
+    seq: u64, // Aynı zamanlı olayların eklenme sırasıyla işlenmesi için sıra numarası
+    event: E,
+}
+
+impl<E> PartialEq for Scheduled<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.time == other.time && self.seq == other.seq
+    }
+}
+
+impl<E> Eq for Scheduled<E> {}
+
+impl<E> PartialOrd for Scheduled<E> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<E> Ord for Scheduled<E> {
+    // BinaryHeap en büyük elemanı verdiği için sıralama ters çevrilir (en erken olay önce)
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.time.cmp(&self.time).then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+// Ayrık olay zamanlayıcısı (discrete-event scheduler)
+// Olaylar zaman damgalarına göre sırayla işlenir, aynı zamandaki olaylar eklenme sırasını korur.
+// Bu sayede aynı girdilerle yapılan iki çalıştırma her zaman aynı sırayla ilerler.
+#[derive(Debug)]
+pub struct Scheduler<E> {
+    now: SimTime,
+    next_seq: u64,
+    queue: BinaryHeap<Scheduled<E>>,
+}
+
+impl<E> Default for Scheduler<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E> Scheduler<E> {
+    pub fn new() -> Self {
+        Scheduler {
+            now: 0,
+            next_seq: 0,
+            queue: BinaryHeap::new(),
+        }
+    }
+
+    // Şu anki sanal zaman
+    pub fn now(&self) -> SimTime {
+        self.now
+    }
+
+    // Olayı belirli bir zamana planla (geçmişe planlanan olaylar şu ana çekilir)
+    pub fn schedule_at(&mut self, time: SimTime, event: E) {
+        let time = time.max(self.now);
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.queue.push(Scheduled { time, seq, event });
+    }
+
+    // Olayı şu andan belirli bir süre sonraya planla
+    pub fn schedule_in(&mut self, delay: SimTime, event: E) {
+        self.schedule_at(self.now.saturating_add(delay), event);
+    }
+
+    // Sıradaki olayın zamanı
+    pub fn peek_time(&self) -> Option<SimTime> {
+        self.queue.peek().map(|scheduled| scheduled.time)
+    }
+
+    // Sıradaki olayı al ve saati o olayın zamanına ilerlet
+    pub fn pop(&mut self) -> Option<(SimTime, E)> {
+        let scheduled = self.queue.pop()?;
+        self.now = scheduled.time;
+        Some((scheduled.time, scheduled.event))
+    }
+
+    // Sıradaki olay verilen zamandan önce (veya o anda) ise al
+    pub fn pop_until(&mut self, until: SimTime) -> Option<(SimTime, E)> {
+        match self.peek_time() {
+            Some(time) if time <= until => self.pop(),
+            _ => None,
+        }
+    }
+
+    // Saati ileri sar (geri almaya izin verilmez)
+    pub fn advance_to(&mut self, time: SimTime) {
+        self.now = self.now.max(time);
+    }
+
+    // Koşulu sağlamayan bekleyen olayları iptal et
+    pub fn retain<F: FnMut(&E) -> bool>(&mut self, mut keep: F) {
+        self.queue.retain(|scheduled| keep(&scheduled.event));
+    }
+
+    // Bekleyen olay sayısı
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
@@ -0,0 +1,434 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use sha2::{Digest, Sha256};
+
+use crate::ban::Offense;
+use crate::block::{Block, BlockHeader};
+use crate::error::{EvidenceError, NetworkError, ValidationError};
+use crate::message::Message;
+use crate::network::BlockchainNetwork;
+use crate::stake;
+use crate::transaction::{get_utxo_id, Transaction, TxInput, TxOutput, UTXO};
+
+// Çifte imza kanıtlanan validator'un kilitli stake'inden (teminatından) kesilen yüzde
+pub const SLASH_PERCENT: u64 = 50;
+
+// Kesilen miktardan kanıtı bloğuna ekleyen üreticiye ödenen yüzde; geri kalanı yakılır
+// Ödül kanıtta yazan bir adrese değil bloğun üreticisine gider, böylece kanıtı kendi adresiyle
+// yeniden paketleyen bir üretici ödülü başkasının elinden alamaz
+pub const REPORTER_PERCENT: u64 = 10;
+
+// Aynı yükseklikte iki farklı blok imzalayan validator'un kanıtı
+// Her iki başlık da önericinin imzasını taşıdığı için herkes kanıtı bağımsız olarak doğrulayabilir
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EquivocationEvidence {
+    pub first: BlockHeader,
+    pub second: BlockHeader,
+}
+
+impl EquivocationEvidence {
+    // Başlıklar hash'e göre sıralanır, böylece aynı çift her zaman aynı kanıtı oluşturur
+    pub fn new(a: BlockHeader, b: BlockHeader) -> Self {
+        let (first, second) = if a.hash <= b.hash { (a, b) } else { (b, a) };
+        EquivocationEvidence { first, second }
+    }
+
+    // Suç kimliği: önerici ve yükseklik; aynı yükseklikteki çifte imza sadece bir kez cezalandırılır
+    pub fn id(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.first.proposer.as_bytes());
+        hasher.update((self.first.index as u64).to_le_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+
+    // Kanıtın tüm içeriğinin hash'i (merkle köküne bu girer)
+    pub fn hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.first.hash.as_bytes());
+        hasher.update(self.second.hash.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+
+    // Kanıtı doğrula ve suçlu önericinin adresini döndür
+    pub fn verify(&self) -> Result<String, EvidenceError> {
+        if self.first.hash == self.second.hash {
+            return Err(EvidenceError::SameBlock);
+        }
+        if self.first.index != self.second.index {
+            return Err(EvidenceError::DifferentHeights { first: self.first.index, second: self.second.index });
+        }
+        if self.first.proposer != self.second.proposer {
+            return Err(EvidenceError::DifferentProposers);
+        }
+
+        for header in [&self.first, &self.second] {
+            if header.hash != header.calculate_hash() || stake::verify_seal(header).is_err() || header.index == 0 {
+                return Err(EvidenceError::InvalidSignature { hash: header.hash.clone() });
+            }
+        }
+
+        stake::proposer_address(&self.first).ok_or(EvidenceError::InvalidSignature { hash: self.first.hash.clone() })
+    }
+
+    // Kanıtın yaklaşık boyutu (byte)
+    pub fn size(&self) -> usize {
+        self.first.size() + self.second.size()
+    }
+}
+
+// Kanıta karşılık gelen ceza işlemi: suçlunun tüm stake çıktıları harcanır,
+// kalan teminat tekrar kilitlenir, kanıtı içeren bloğun üreticisine (producer) ödül verilir ve farkı yakılır.
+// İşlem verilen UTXO setinden belirlenimci olarak türetilir, böylece her node aynı işlemi bekler.
+pub fn slashing_transaction(evidence: &EquivocationEvidence, utxo_set: &[UTXO], producer: &str) -> Result<Transaction, EvidenceError> {
+    let offender = evidence.verify()?;
+    let locked = stake::stake_address(&offender);
+
+    let bonds: Vec<&UTXO> = utxo_set.iter().filter(|utxo| utxo.recipient_address == locked).collect();
+    let bond: u64 = bonds.iter().map(|utxo| utxo.amount).sum();
+    if bond == 0 {
+        return Err(EvidenceError::NothingToSlash { offender });
+    }
+
+    let slashed = bond * SLASH_PERCENT / 100;
+    let reward = slashed * REPORTER_PERCENT / 100;
+
+    let inputs = bonds
+        .iter()
+        .map(|utxo| TxInput {
+            utxo_id: get_utxo_id(&utxo.transaction_id, utxo.output_index),
+            utxo_output_index: utxo.output_index,
+            signature: Vec::new(),
+            sender_address: locked.clone(),
+        })
+        .collect();
+
+    let mut outputs = Vec::new();
+    if bond > slashed {
+        outputs.push(TxOutput { amount: bond - slashed, recipient_address: locked });
+    }
+    if reward > 0 {
+        outputs.push(TxOutput { amount: reward, recipient_address: producer.to_string() });
+    }
+
+    Ok(Transaction::new_at(inputs, outputs, evidence.first.timestamp))
+}
+
+// Zincirde bu suç için daha önce ceza uygulanmış mı
+pub fn already_slashed(chain: &[Block], evidence_id: &str) -> bool {
+    chain.iter().any(|block| block.evidence.iter().any(|evidence| evidence.id() == evidence_id))
+}
+
+// Bloktaki kanıtların kendi içinde tekrarlanmadığını ve zincirde daha önce kullanılmadığını kontrol et
+pub fn validate_block_evidence(block: &Block, chain: &[Block]) -> Result<(), ValidationError> {
+    let mut seen = HashSet::new();
+    for evidence in &block.evidence {
+        let id = evidence.id();
+        if !seen.insert(id.clone()) || already_slashed(chain, &id) {
+            return Err(ValidationError::Evidence { index: block.index, error: EvidenceError::AlreadySlashed });
+        }
+    }
+    Ok(())
+}
+
+// Kanonik zincirde uygulanmış bir ceza
+#[derive(Debug, Clone)]
+pub struct SlashRecord {
+    pub height: usize,               // Kanıtın girdiği blok
+    pub offense_height: usize,       // Çifte imzanın yapıldığı yükseklik
+    pub offender: String,            // Cezalandırılan validator'un adresi
+    pub offender_node: Option<usize>,
+    pub reporter: String,            // Ödülü alan: kanıtı bloğuna ekleyen üretici
+    pub bond: u64,                   // Ceza öncesi teminat
+    pub slashed: u64,                // Teminattan kesilen
+    pub reward: u64,                 // Bildirene ödenen
+    pub burned: u64,                 // Yakılan
+}
+
+impl fmt::Display for SlashRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let offender = match self.offender_node {
+            Some(id) => format!("Node {}", id),
+            None => self.offender.clone(),
+        };
+        write!(
+            f,
+            "Blok #{}: {} #{} yüksekliğinde çifte imza attı; teminat {} coin, kesilen {} coin (ödül {}, yakılan {})",
+            self.height,
+            offender,
+            self.offense_height,
+            self.bond as f64 / 100_000_000.0,
+            self.slashed as f64 / 100_000_000.0,
+            self.reward as f64 / 100_000_000.0,
+            self.burned as f64 / 100_000_000.0
+        )
+    }
+}
+
+impl BlockchainNetwork {
+    // Bir node adına çifte imza kanıtı gönder; node kanıtı doğrular ve komşularına yayar
+    // Kanıt, bir sonraki önericinin bloğuna ceza işlemiyle birlikte girer
+    pub fn submit_evidence(&mut self, node_id: usize, a: BlockHeader, b: BlockHeader) -> Result<EquivocationEvidence, NetworkError> {
+        let node = self.nodes.get_mut(node_id).ok_or(NetworkError::NodeNotFound(node_id))?;
+        let evidence = EquivocationEvidence::new(a, b);
+        node.add_evidence(evidence.clone())?;
+
+        self.relay_evidence(node_id, &evidence);
+        self.process_due_events();
+        Ok(evidence)
+    }
+
+    // Kanıtı henüz bilmeyen komşulara ilet
+    pub(crate) fn relay_evidence(&mut self, origin: usize, evidence: &EquivocationEvidence) {
+        let id = evidence.id();
+        let peers = match self.nodes.get(origin) {
+            Some(node) => node.connections.clone(),
+            None => return,
+        };
+
+        for peer in peers {
+            if self.nodes[origin].peer_knows(peer, &id) {
+                continue;
+            }
+            if self.send_message(origin, peer, Message::Evidence(Box::new(evidence.clone()))) {
+                self.nodes[origin].mark_known(peer, &id);
+            }
+        }
+    }
+
+    // Komşudan gelen kanıtı işle; yeni ve geçerliyse yay
+    pub(crate) fn handle_evidence(&mut self, from: usize, to: usize, evidence: EquivocationEvidence) {
+        let node = match self.nodes.get_mut(to) {
+            Some(node) => node,
+            None => return,
+        };
+        node.mark_known(from, &evidence.id());
+
+        match node.add_evidence(evidence.clone()) {
+            Ok(true) => self.relay_evidence(to, &evidence),
+            Ok(false) => {}
+            Err(_) => self.penalize(to, from, Some(Offense::InvalidEvidence)),
+        }
+    }
+
+    // Kanonik zincirde uygulanmış cezalar
+    pub fn slashings(&self) -> Vec<SlashRecord> {
+        let chain = self.canonical_chain();
+        let mut records = Vec::new();
+
+        for block in chain {
+            let producer = block.transactions.first().and_then(|tx| tx.outputs.first()).map(|output| output.recipient_address.clone()).unwrap_or_default();
+            let slash_start = block.transactions.len().saturating_sub(block.evidence.len());
+            for (evidence, tx) in block.evidence.iter().zip(&block.transactions[slash_start..]) {
+                let offender = match stake::proposer_address(&evidence.first) {
+                    Some(offender) => offender,
+                    None => continue,
+                };
+
+                // Harcanan teminat çıktılarının miktarları zincirdeki işlemlerden bulunur
+                let bond: u64 = tx
+                    .inputs
+                    .iter()
+                    .filter_map(|input| {
+                        chain.iter().flat_map(|block| &block.transactions).find_map(|source| {
+                            source.outputs.iter().enumerate().find_map(|(index, output)| {
+                                (get_utxo_id(&source.id, index) == input.utxo_id).then_some(output.amount)
+                            })
+                        })
+                    })
+                    .sum();
+                let remaining: u64 = tx.outputs.iter().filter(|output| stake::stake_owner(&output.recipient_address).is_some()).map(|output| output.amount).sum();
+                let reward: u64 = tx.outputs.iter().filter(|output| output.recipient_address == producer).map(|output| output.amount).sum();
+                let slashed = bond.saturating_sub(remaining);
+
+                records.push(SlashRecord {
+                    height: block.index,
+                    offense_height: evidence.first.index,
+                    offender_node: self.nodes.iter().position(|node| node.get_address() == offender),
+                    offender,
+                    reporter: producer.clone(),
+                    bond,
+                    slashed,
+                    reward,
+                    burned: slashed.saturating_sub(reward),
+                });
+            }
+        }
+
+        records
+    }
+
+    // Saldırı senaryosu: bu slotun önericisi aynı yükseklikte iki farklı blok imzalar
+    // Bloklardan biri komşuların yarısına, diğeri diğer yarısına gönderilir
+    #[cfg(feature = "attacks")]
+    pub fn equivocate(&mut self, node_id: usize) -> Result<(Block, Block), NetworkError> {
+        if !self.engine.signed_blocks() {
+            return Err(NetworkError::NotValidator(node_id));
+        }
+
+        let timestamp = self.timestamp();
+        let difficulty = self.difficulty;
+        let node = self.nodes.get_mut(node_id).ok_or(NetworkError::NodeNotFound(node_id))?;
+        if node.slot_proposer(timestamp).as_deref() != Some(node.get_address()) {
+            return Err(NetworkError::NotValidator(node_id));
+        }
+
+        // Dürüst node'ların çifte imza korumasını atlayarak, zincire eklemeden aynı yükseklikte iki blok imzala
+        node.is_validator = true;
+        let first = node.create_block_at(difficulty, timestamp)?;
+        let second = node.create_block_at(difficulty, timestamp)?;
+        node.is_validator = false;
+
+        node.receive_block(first.clone(), difficulty)?;
+        self.propagation.record_mined(&first, node_id, self.now());
+        self.propagation.record_mined(&second, node_id, self.now());
+
+        let peers = self.nodes[node_id].connections.clone();
+        let half = peers.len().div_ceil(2);
+        for (position, &peer) in peers.iter().enumerate() {
+            let block = if position < half { &first } else { &second };
+            self.nodes[node_id].mark_known(peer, &block.hash);
+            self.send_message(node_id, peer, Message::Block(block.clone()));
+        }
+
+        self.process_due_events();
+        Ok((first, second))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node::Node;
+    use crate::wallet::Wallet;
+
+    const BOND: u64 = 10_0000_0000;
+
+    // Cüzdanın verilen yükseklikte imzaladığı bir blok başlığı
+    fn signed_header(wallet: &Wallet, index: usize, timestamp: u64) -> BlockHeader {
+        let coinbase = Transaction::new_coinbase_at(wallet.get_address().to_string(), 50, timestamp, 0);
+        let mut block = Block::new(index, timestamp, vec![coinbase], "0".repeat(64));
+        block.sign(wallet);
+        block.header()
+    }
+
+    fn equivocation(wallet: &Wallet) -> EquivocationEvidence {
+        EquivocationEvidence::new(signed_header(wallet, 5, 100), signed_header(wallet, 5, 101))
+    }
+
+    fn bond(owner: &str) -> UTXO {
+        UTXO { transaction_id: "a".repeat(64), output_index: 0, amount: BOND, recipient_address: stake::stake_address(owner) }
+    }
+
+    #[test]
+    fn valid_evidence_names_the_offender() {
+        let wallet = Wallet::new();
+        let evidence = equivocation(&wallet);
+        assert_eq!(evidence.verify(), Ok(wallet.get_address().to_string()));
+
+        // Başlıkların sırası kanıtı değiştirmez
+        let swapped = EquivocationEvidence::new(evidence.second.clone(), evidence.first.clone());
+        assert_eq!(swapped, evidence);
+        assert_eq!(swapped.id(), evidence.id());
+    }
+
+    #[test]
+    fn mismatched_or_forged_evidence_is_rejected() {
+        let wallet = Wallet::new();
+        let header = signed_header(&wallet, 5, 100);
+
+        assert_eq!(EquivocationEvidence::new(header.clone(), header.clone()).verify(), Err(EvidenceError::SameBlock));
+        // Başlıklar hash'e göre sıralandığı için yüksekliklerin sırası hatada değişebilir
+        let heights = EquivocationEvidence::new(header.clone(), signed_header(&wallet, 6, 101));
+        assert_eq!(heights.verify(), Err(EvidenceError::DifferentHeights { first: heights.first.index, second: heights.second.index }));
+        assert_ne!(heights.first.index, heights.second.index);
+        assert_eq!(
+            EquivocationEvidence::new(header.clone(), signed_header(&Wallet::new(), 5, 101)).verify(),
+            Err(EvidenceError::DifferentProposers)
+        );
+
+        // İmza başka bir anahtarla atılmış
+        let mut forged = signed_header(&wallet, 5, 101);
+        forged.signature = Wallet::new().sign(forged.hash.as_bytes());
+        let hash = forged.hash.clone();
+        assert_eq!(EquivocationEvidence::new(header.clone(), forged).verify(), Err(EvidenceError::InvalidSignature { hash }));
+
+        // İmzadan sonra içeriği değiştirilmiş başlık
+        let mut tampered = signed_header(&wallet, 5, 101);
+        tampered.timestamp += 1;
+        let hash = tampered.hash.clone();
+        assert_eq!(EquivocationEvidence::new(header, tampered).verify(), Err(EvidenceError::InvalidSignature { hash }));
+    }
+    #[test]
+    fn slashing_transaction_splits_the_bond() {
+        let wallet = Wallet::new();
+        let offender = wallet.get_address().to_string();
+        let evidence = equivocation(&wallet);
+        let utxo_set = vec![bond(&offender), UTXO { transaction_id: "b".repeat(64), output_index: 0, amount: 7, recipient_address: offender.clone() }];
+
+        let tx = slashing_transaction(&evidence, &utxo_set, "üretici").unwrap();
+        let slashed = BOND * SLASH_PERCENT / 100;
+        let reward = slashed * REPORTER_PERCENT / 100;
+        assert_eq!(tx.inputs.len(), 1);
+        assert_eq!(tx.inputs[0].sender_address, stake::stake_address(&offender));
+        assert_eq!(
+            tx.outputs,
+            vec![
+                TxOutput { amount: BOND - slashed, recipient_address: stake::stake_address(&offender) },
+                TxOutput { amount: reward, recipient_address: "üretici".to_string() },
+            ]
+        );
+        // Kesilen miktarın ödül dışındaki kısmı yakılır
+        let paid: u64 = tx.outputs.iter().map(|output| output.amount).sum();
+        assert_eq!(BOND - paid, slashed - reward);
+
+        // Aynı UTXO setinden her node aynı işlemi türetir
+        assert_eq!(slashing_transaction(&evidence, &utxo_set, "üretici"), Ok(tx));
+        assert_eq!(slashing_transaction(&evidence, &utxo_set[1..], "üretici"), Err(EvidenceError::NothingToSlash { offender }));
+    }
+
+    #[test]
+    fn same_offense_is_slashed_once() {
+        let wallet = Wallet::new();
+        let evidence = equivocation(&wallet);
+        // Aynı önerici ve yükseklik için farklı bir blok çifti de aynı suçtur
+        let again = EquivocationEvidence::new(signed_header(&wallet, 5, 102), signed_header(&wallet, 5, 103));
+        assert_eq!(again.id(), evidence.id());
+
+        let genesis = Block::new(0, 0, Vec::new(), "0".to_string());
+        let slashed = Block::with_evidence(1, 200, Vec::new(), vec![evidence.clone()], genesis.hash.clone());
+        assert!(!already_slashed(std::slice::from_ref(&genesis), &evidence.id()));
+        let chain = vec![genesis, slashed];
+        assert!(already_slashed(&chain, &evidence.id()));
+
+        let next = Block::with_evidence(2, 300, Vec::new(), vec![again.clone()], chain[1].hash.clone());
+        assert_eq!(
+            validate_block_evidence(&next, &chain),
+            Err(ValidationError::Evidence { index: 2, error: EvidenceError::AlreadySlashed })
+        );
+        let twice = Block::with_evidence(1, 200, Vec::new(), vec![evidence, again], chain[0].hash.clone());
+        assert_eq!(
+            validate_block_evidence(&twice, &chain[..1]),
+            Err(ValidationError::Evidence { index: 1, error: EvidenceError::AlreadySlashed })
+        );
+    }
+
+    #[test]
+    fn slashing_reward_must_go_to_the_coinbase_recipient() {
+        let wallet = Wallet::new();
+        let offender = wallet.get_address().to_string();
+        let evidence = equivocation(&wallet);
+        let utxo_set = vec![bond(&offender)];
+        let block_paying = |recipient: &str| {
+            let coinbase = Transaction::new_coinbase_at("üretici".to_string(), 50, 200, 0);
+            let slash = slashing_transaction(&evidence, &utxo_set, recipient).unwrap();
+            Block::with_evidence(6, 200, vec![coinbase, slash], vec![evidence.clone()], "0".repeat(64))
+        };
+
+        assert_eq!(Node::apply_block_transactions(&mut utxo_set.clone(), &block_paying("üretici")), Ok(()));
+        assert_eq!(
+            Node::apply_block_transactions(&mut utxo_set.clone(), &block_paying("başkası")),
+            Err(ValidationError::InvalidSlashing { index: 6 })
+        );
+    }
+}
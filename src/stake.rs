@@ -0,0 +1,191 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use rand::Rng;
+use secp256k1::PublicKey;
+use sha2::{Digest, Sha256};
+
+use crate::block::{Block, BlockHeader};
+use crate::error::{NetworkError, ValidationError};
+use crate::network::BlockchainNetwork;
+use crate::transaction::{Transaction, UTXO};
+use crate::wallet::Wallet;
+
+// Blok zaman damgasının node'un saatinin ne kadar ilerisinde olabileceği (saniye)
+// Slot zaman damgasından türetildiği için önerici gelecekteki slotları ancak bu kadar deneyebilir
+pub const MAX_CLOCK_DRIFT: u64 = 1;
+
+// Stake çıktılarının alıcı adresi önekleri: "stake:<sahibin adresi>"
+// Bu çıktılar sahibinin cüzdanında görünmez ve harcanamaz, yani kilitlidir
+pub const STAKE_PREFIX: &str = "stake:";
+
+// Blokların nasıl mühürlendiği ve kimin blok üretebileceği
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsensusMode {
+    // Bloklar nonce aranarak mühürlenir (zorluk seviyesi kadar 0 ile başlayan hash)
+    ProofOfWork,
+    // Her slotta stake ağırlıklı seçilen önerici bloğu kendi anahtarıyla imzalar
+    ProofOfStake,
+}
+
+impl FromStr for ConsensusMode {
+    type Err = String;
+
+    // "pow" veya "pos"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pow" => Ok(ConsensusMode::ProofOfWork),
+            "pos" => Ok(ConsensusMode::ProofOfStake),
+            other => Err(format!("bilinmeyen uzlaşma modu: {} (pow veya pos)", other)),
+        }
+    }
+}
+
+// Bir adrese ait stake çıktılarının alıcı adresi
+pub fn stake_address(owner: &str) -> String {
+    format!("{}{}", STAKE_PREFIX, owner)
+}
+
+// Alıcı adresi bir stake çıktısıysa stake sahibinin adresi
+pub fn stake_owner(recipient_address: &str) -> Option<&str> {
+    recipient_address.strip_prefix(STAKE_PREFIX)
+}
+
+// UTXO setindeki kilitli stake'ler: sahip adresi -> toplam miktar (adrese göre sıralı)
+pub fn stakes(utxo_set: &[UTXO]) -> BTreeMap<String, u64> {
+    let mut stakes = BTreeMap::new();
+    for utxo in utxo_set {
+        if let Some(owner) = stake_owner(&utxo.recipient_address) {
+            *stakes.entry(owner.to_string()).or_insert(0) += utxo.amount;
+        }
+    }
+    stakes
+}
+
+// Slotun doğrulanabilir rastgele tohumu: önceki blok hash'i ve slot numarasının SHA-256'sı
+// Herkes aynı ebeveyn ve slot için aynı tohumu hesaplar, böylece önerici seçimi tekrar doğrulanabilir
+pub fn slot_seed(previous_hash: &str, slot: u64) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(previous_hash.as_bytes());
+    hasher.update(slot.to_le_bytes());
+    hasher.finalize().into()
+}
+
+// Tohuma göre stake ağırlıklı önerici seç; hiç stake yoksa None
+// Tohumun ilk 16 byte'ı toplam stake'e göre mod alınır ve sıralı stake listesinde karşılık gelen sahip bulunur
+pub fn select_proposer(stakes: &BTreeMap<String, u64>, seed: &[u8; 32]) -> Option<String> {
+    let total: u128 = stakes.values().map(|&amount| amount as u128).sum();
+    if total == 0 {
+        return None;
+    }
+
+    let mut target = u128::from_le_bytes(seed[..16].try_into().unwrap()) % total;
+    for (owner, &amount) in stakes {
+        if target < amount as u128 {
+            return Some(owner.clone());
+        }
+        target -= amount as u128;
+    }
+    None
+}
+
+// Başlıktaki önerici public key'inin adresi
+pub fn proposer_address(header: &BlockHeader) -> Option<String> {
+    let bytes = hex::decode(&header.proposer).ok()?;
+    let public_key = PublicKey::from_slice(&bytes).ok()?;
+    Some(Wallet::generate_address(&public_key))
+}
+
+// PoS mührünü doğrula: genesis dışındaki bloklar önericinin blok hash'i üzerindeki imzasını taşımalı
+pub fn verify_seal(header: &BlockHeader) -> Result<(), ValidationError> {
+    if header.index == 0 {
+        return Ok(());
+    }
+
+    let public_key = hex::decode(&header.proposer)
+        .ok()
+        .and_then(|bytes| PublicKey::from_slice(&bytes).ok())
+        .ok_or(ValidationError::MissingProposer { index: header.index })?;
+
+    Wallet::verify_with_key(&public_key, header.hash.as_bytes(), &header.signature)
+        .map_err(|_| ValidationError::InvalidProposerSignature { index: header.index })
+}
+
+// Bloğun, ebeveyninden sonraki durumdaki stake'lere göre o slot için seçilen önerici tarafından
+// üretildiğini kontrol et. Slot, bloğun zaman damgasıdır (saniye).
+// Her slotta en fazla bir blok olabilir, bu yüzden zaman damgası ebeveyninkinden büyük olmalıdır;
+// üst sınır (saatin en fazla MAX_CLOCK_DRIFT ilerisi) blok ağdan alınırken kontrol edilir.
+pub fn validate_proposer(header: &BlockHeader, parent_timestamp: u64, utxo_set: &[UTXO]) -> Result<(), ValidationError> {
+    if header.timestamp <= parent_timestamp {
+        return Err(ValidationError::TimestampTooEarly { index: header.index });
+    }
+
+    let seed = slot_seed(&header.previous_hash, header.timestamp);
+    let expected = select_proposer(&stakes(utxo_set), &seed).ok_or(ValidationError::NoStake { index: header.index })?;
+    let found = proposer_address(header).ok_or(ValidationError::MissingProposer { index: header.index })?;
+
+    if found != expected {
+        return Err(ValidationError::WrongProposer { index: header.index, expected, found });
+    }
+    Ok(())
+}
+
+impl BlockchainNetwork {
+    // Uzlaşma modunu değiştir ve yerleşik motorunu tak; tüm node'lar bundan sonra blokları bu moda göre mühürler ve doğrular
+    pub fn set_consensus(&mut self, mode: ConsensusMode) {
+        self.consensus = mode;
+        self.set_consensus_engine(mode.engine());
+    }
+
+    // Node'un coinlerinin bir kısmını stake olarak kilitle
+    // Kilitleme normal bir transfer gibi yayılır; blokta onaylandıktan sonra önerici seçiminde sayılır
+    pub fn stake(&mut self, node_id: usize, amount: u64) -> Result<Transaction, NetworkError> {
+        let node = self.nodes.get(node_id).ok_or(NetworkError::NodeNotFound(node_id))?;
+        let address = stake_address(node.get_address());
+        self.create_transaction(node_id, &address, amount)
+    }
+
+    // Kanonik zincire göre node'ların kilitli stake'leri (node ID, miktar)
+    pub fn stake_table(&self) -> Vec<(usize, u64)> {
+        let best = self.nodes.iter().max_by_key(|node| node.blockchain.len());
+        let stakes = best.map(|node| stakes(&node.utxo_set)).unwrap_or_default();
+
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(id, node)| (id, stakes.get(node.get_address()).copied().unwrap_or(0)))
+            .collect()
+    }
+
+    // PoS genesis bloğu: ilk node'a coinbase ödülü ve her node'a genesis_stake kadar kilitli stake
+    pub(crate) fn create_stake_genesis(&mut self) -> Result<Block, NetworkError> {
+        if self.nodes.is_empty() {
+            return Err(NetworkError::NoNodes);
+        }
+
+        let timestamp = self.timestamp();
+        let producer = self.current_validator_id.unwrap_or(0);
+        let node = &mut self.nodes[producer];
+        let mut transactions = vec![Transaction::new_coinbase_at(
+            node.get_address().to_string(),
+            node.mining_reward,
+            timestamp,
+            node.rng.random(),
+        )];
+
+        let addresses: Vec<String> = self.nodes.iter().map(|node| node.get_address().to_string()).collect();
+        for address in addresses {
+            let nonce = self.nodes[producer].rng.random();
+            transactions.push(Transaction::new_coinbase_at(stake_address(&address), self.genesis_stake, timestamp, nonce));
+        }
+
+        let genesis = Block::new(0, timestamp, transactions, "0".to_string());
+        self.nodes[producer].receive_block(genesis.clone(), self.difficulty)?;
+        self.last_block_time = timestamp;
+
+        self.propagation.record_mined(&genesis, producer, self.now());
+        self.broadcast_block(producer, &genesis);
+
+        Ok(genesis)
+    }
+}
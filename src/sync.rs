@@ -0,0 +1,606 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::ban::Offense;
+use crate::block::BlockHeader;
+use crate::byzantine::Misbehavior;
+use crate::error::{NetworkError, ValidationError};
+use crate::events::Event;
+use crate::message::{InvItem, Message};
+use crate::network::{BlockchainNetwork, SimEvent};
+use crate::node::Node;
+use crate::sim::SimTime;
+use crate::consensus::ConsensusEngine;
+
+// Tek bir headers mesajındaki en fazla başlık sayısı; dolu gelen cevap devamının istenmesini tetikler
+pub const MAX_HEADERS: usize = 2000;
+
+// Senkronizasyon sırasında bir komşudan aynı anda istenebilecek en fazla blok gövdesi
+pub const MAX_BLOCKS_IN_FLIGHT: usize = 16;
+
+// Ağa sonradan katılan bir node'un ilk senkronizasyon durumu
+// Önce komşulardan başlıklar alınıp PoW zinciri doğrulanır, sonra gövdeler birden fazla komşudan paralel indirilir
+#[derive(Debug, Clone)]
+pub struct SyncState {
+    pub started_at: SimTime,
+    pub completed_at: Option<SimTime>,
+    pub headers: HashMap<String, BlockHeader>, // Doğrulanmış başlıklar: hash -> başlık
+    pub best_header: Option<(String, usize)>,  // En fazla işi olan başlık zincirinin ucu: (hash, indeks)
+    pub best_work: u128,                       // Bu başlık zincirinin genesis'ten itibaren toplam işi
+    pub peers: Vec<usize>,                     // Başlık gönderen komşular (gövdeler bunlardan istenir)
+    pub pending: Vec<usize>,                   // Başlık istenen ama son cevabını henüz vermemiş komşular
+    last_reported: usize,                      // En son raporlanan ilerleme yüzdesi
+}
+
+impl SyncState {
+    pub fn new(started_at: SimTime) -> Self {
+        SyncState {
+            started_at,
+            completed_at: None,
+            headers: HashMap::new(),
+            best_header: None,
+            best_work: 0,
+            peers: Vec::new(),
+            pending: Vec::new(),
+            last_reported: 0,
+        }
+    }
+
+    // Ulaşılması gereken zincir uzunluğu
+    pub fn target_height(&self) -> usize {
+        self.best_header.as_ref().map(|(_, index)| index + 1).unwrap_or(0)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.completed_at.is_some()
+    }
+}
+
+// Bir node'un senkronizasyon ilerlemesi
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncProgress {
+    pub node_id: usize,
+    pub headers: usize, // Doğrulanmış başlık sayısı
+    pub height: usize,  // Node'un aktif zincir uzunluğu
+    pub target: usize,  // Komşuların en uzun zincir uzunluğu
+    pub elapsed: SimTime,
+    pub complete: bool,
+}
+
+impl SyncProgress {
+    // İndirilen blokların hedefe oranı (0.0 - 1.0)
+    pub fn fraction(&self) -> f64 {
+        if self.target == 0 {
+            return if self.complete { 1.0 } else { 0.0 };
+        }
+        (self.height as f64 / self.target as f64).min(1.0)
+    }
+}
+
+impl fmt::Display for SyncProgress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Node {}: {}/{} blok (%{:.1}), {} başlık, {} ms{}",
+            self.node_id,
+            self.height,
+            self.target,
+            self.fraction() * 100.0,
+            self.headers,
+            self.elapsed,
+            if self.complete { ", tamamlandı" } else { "" }
+        )
+    }
+}
+
+// Ardışık başlıkların hash, mühür (PoW veya önerici imzası) ve bağlantı kontrolü
+// PoS önericisinin doğru olup olmadığı stake durumuna bağlı olduğundan gövdeler gelince kontrol edilir
+pub fn validate_headers(headers: &[BlockHeader], difficulty: usize, consensus: &dyn ConsensusEngine) -> Result<(), ValidationError> {
+    for (position, header) in headers.iter().enumerate() {
+        if header.hash != header.calculate_hash() {
+            return Err(ValidationError::InvalidHash { index: header.index });
+        }
+        consensus.verify_seal(header, difficulty)?;
+
+        if position > 0 {
+            let previous = &headers[position - 1];
+            if header.index != previous.index + 1 {
+                return Err(ValidationError::InvalidIndex { expected: previous.index + 1, found: header.index });
+            }
+            if header.previous_hash != previous.hash {
+                return Err(ValidationError::InvalidPreviousHash {
+                    expected: previous.hash.clone(),
+                    found: header.previous_hash.clone(),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+impl Node {
+    // Başlık zincirinin uzlaşma motoruna göre toplam işi: başlıklar üzerinden geriye,
+    // node'un bildiği bir bloğa kadar yürünür ve o bloğa kadarki dalın işi eklenir
+    fn header_chain_work(&self, tip: &str) -> u128 {
+        let engine = self.consensus.as_ref();
+        let headers = self.sync.as_ref().map(|sync| &sync.headers);
+        let mut work = 0;
+        let mut cursor = tip;
+        while let Some(header) = headers.and_then(|headers| headers.get(cursor)) {
+            if self.knows_block(cursor) {
+                break;
+            }
+            work += engine.block_work(&header.hash);
+            cursor = &header.previous_hash;
+        }
+        work + self.branch_to(cursor).iter().map(|block| engine.block_work(&block.hash)).sum::<u128>()
+    }
+}
+
+impl BlockchainNetwork {
+    // Ağa yeni bir node ekle, verilen komşulara bağla ve ilk senkronizasyonu başlat
+    pub fn join_node(&mut self, peers: &[usize]) -> Result<usize, NetworkError> {
+        if let Some(&peer) = peers.iter().find(|&&peer| peer >= self.nodes.len()) {
+            return Err(NetworkError::NodeNotFound(peer));
+        }
+        let node_id = self.add_node();
+        // Genesis bloğu istemciye gömülü kabul edilir; başlıklar bu bloğa bağlanmak zorundadır
+        let difficulty = self.difficulty;
+        if let Some(genesis) = self.canonical_chain().first().cloned() {
+            self.nodes[node_id].receive_block(genesis, difficulty)?;
+        }
+        for &peer in peers {
+            self.connect_nodes(node_id, peer)?;
+        }
+
+        self.start_sync(node_id)?;
+
+        // Gecikmesiz bağlantılardaki teslimatları hemen tamamla
+        self.process_due_events();
+
+        Ok(node_id)
+    }
+
+    // Node için başlık-öncelikli senkronizasyonu başlat: tüm komşulardan başlıkları iste
+    // Cevap vermeyen komşular request_timeout sonunda beklenmekten vazgeçilir
+    pub fn start_sync(&mut self, node_id: usize) -> Result<(), NetworkError> {
+        let now = self.now();
+        let node = self.nodes.get_mut(node_id).ok_or(NetworkError::NodeNotFound(node_id))?;
+        let mut sync = SyncState::new(now);
+        sync.pending = node.connections.clone();
+        node.sync = Some(sync);
+
+        let locator = node.block_locator();
+        for peer in node.connections.clone() {
+            self.send_message(node_id, peer, Message::GetHeaders { locator: locator.clone() });
+        }
+        let deadline = now.saturating_add(self.request_timeout);
+        self.scheduler.schedule_at(deadline, SimEvent::SyncTimeout { node: node_id, started_at: now });
+
+        Ok(())
+    }
+
+    // Başlık cevabı için bekleme süresi doldu: cevap vermeyen komşular beklenmez
+    pub(crate) fn handle_sync_timeout(&mut self, node_id: usize, started_at: SimTime) {
+        let sync = match self.nodes.get_mut(node_id).and_then(|node| node.sync.as_mut()) {
+            // Bu arada yeniden başlatılan senkronizasyonun zaman aşımı eski başlangıca aittir
+            Some(sync) if sync.started_at == started_at && !sync.is_complete() => sync,
+            _ => return,
+        };
+        sync.pending.clear();
+        self.advance_sync(node_id);
+    }
+
+    // Node'un senkronizasyon ilerlemesi (hiç senkronizasyon başlatılmadıysa None)
+    pub fn sync_progress(&self, node_id: usize) -> Option<SyncProgress> {
+        let node = self.nodes.get(node_id)?;
+        let sync = node.sync.as_ref()?;
+
+        Some(SyncProgress {
+            node_id,
+            headers: sync.headers.len(),
+            height: node.blockchain.len(),
+            target: sync.target_height(),
+            elapsed: sync.completed_at.unwrap_or(self.now()) - sync.started_at,
+            complete: sync.is_complete(),
+        })
+    }
+
+    // Komşunun locator'ından sonraki başlıkları gönder
+    pub(crate) fn handle_getheaders(&mut self, from: usize, to: usize, locator: Vec<String>) {
+        let mut headers = match self.nodes.get(to) {
+            Some(node) => node.headers_after(&locator, MAX_HEADERS),
+            None => return,
+        };
+        // Bencil madencinin gizli blokları başlıklarla da sızdırılmaz
+        if let Some(published) = self.published_length(to) {
+            headers.retain(|header| header.index < published);
+        }
+        // Blokları saklayan node başlıkları da paylaşmaz, yalancı node zincirinin ucuna sahte bir başlık ekler
+        if self.misbehaves(to, |b| *b == Misbehavior::WithholdBlocks) {
+            headers.clear();
+        }
+        self.append_fake_headers(to, &mut headers);
+
+        // Boş cevap da gönderilir; böylece senkronize olan node komşunun daha uzun zinciri olmadığını öğrenir
+        self.send_message(to, from, Message::Headers(headers));
+    }
+
+    // Komşudan gelen başlıkları doğrula ve en fazla işi olan başlık zincirini güncelle
+    pub(crate) fn handle_headers(&mut self, from: usize, to: usize, headers: Vec<BlockHeader>) {
+        let difficulty = self.difficulty;
+        let node = match self.nodes.get_mut(to) {
+            Some(node) => node,
+            None => return,
+        };
+
+        // İlk senkronizasyonu sürmeyen node'lar başlıkları sadece eksik blokları bulmak için kullanır
+        // (örneğin bölünmüş ağ birleştiğinde karşı tarafın zinciri)
+        let syncing = node.sync.as_ref().map(|sync| !sync.is_complete()).unwrap_or(false);
+        // Genesis başlığı node'un kendi genesis'i olmalı, diğerleri bilinen ebeveynin hemen ardından gelmeli
+        let connects = match headers.first() {
+            Some(first) if first.index == 0 => node.blockchain.first().map(|genesis| genesis.hash == first.hash).unwrap_or(false),
+            Some(first) => {
+                let parent_index = node
+                    .find_block(&first.previous_hash)
+                    .map(|parent| parent.index)
+                    .or_else(|| node.sync.as_ref().and_then(|sync| sync.headers.get(&first.previous_hash)).map(|parent| parent.index));
+                parent_index.map(|index| first.index == index + 1).unwrap_or(false)
+            }
+            None => true,
+        };
+
+        // Geçersiz veya bilinen zincire bağlanmayan başlıklar yok sayılır
+        if !connects || validate_headers(&headers, difficulty, node.consensus.as_ref()).is_err() {
+            self.record_byzantine(from, |effects| effects.rejected_headers += 1);
+            // Bağlanmayan başlıklar eski bir locator'a verilmiş dürüst bir cevap olabilir, sadece geçersiz olanlar cezalandırılır
+            if connects {
+                self.penalize(to, from, Some(Offense::InvalidHeaders));
+            }
+            return;
+        }
+
+        for header in &headers {
+            node.mark_known(from, &header.hash);
+        }
+
+        // Dolu gelen cevap, komşunun daha fazla başlığı olduğunu gösterir
+        let continuation = match headers.last() {
+            Some(last) if headers.len() == MAX_HEADERS => Some(last.hash.clone()),
+            _ => None,
+        };
+
+        if !syncing {
+            let wanted: Vec<InvItem> = headers
+                .into_iter()
+                .map(|header| InvItem::Block(header.hash))
+                .filter(|item| !node.has_item(item) && !node.requested.contains_key(item.id()))
+                .collect();
+            self.request_items(to, from, wanted);
+            if let Some(last_hash) = continuation {
+                self.send_message(to, from, Message::GetHeaders { locator: vec![last_hash] });
+            }
+            return;
+        }
+
+        let sync = node.sync.as_mut().expect("senkronizasyon durumu yukarıda kontrol edildi");
+        if !sync.peers.contains(&from) {
+            sync.peers.push(from);
+        }
+        // Dolu olmayan cevap komşunun tüm başlıklarını gönderdiğini gösterir
+        if continuation.is_none() {
+            sync.pending.retain(|&peer| peer != from);
+        }
+        let tip = headers.last().map(|last| (last.hash.clone(), last.index));
+        for header in headers {
+            sync.headers.insert(header.hash.clone(), header);
+        }
+
+        // Başlık zincirleri, blokların çatal seçimiyle aynı şekilde uzlaşma motorunun toplam işine göre karşılaştırılır
+        if let Some((hash, index)) = tip {
+            let work = node.header_chain_work(&hash);
+            let sync = node.sync.as_mut().expect("senkronizasyon durumu yukarıda kontrol edildi");
+            if sync.best_header.is_none() || work > sync.best_work {
+                sync.best_header = Some((hash, index));
+                sync.best_work = work;
+            }
+        }
+
+        if let Some(last_hash) = continuation {
+            self.send_message(to, from, Message::GetHeaders { locator: vec![last_hash] });
+        }
+
+        self.advance_sync(to);
+    }
+
+    // Senkronizasyonu ilerlet: tamamlandıysa işaretle, değilse boş indirme yuvalarını doldur
+    pub(crate) fn advance_sync(&mut self, node_id: usize) {
+        let now = self.now();
+        let node = match self.nodes.get_mut(node_id) {
+            Some(node) => node,
+            None => return,
+        };
+        let height = node.blockchain.len();
+        let sync = match node.sync.as_mut() {
+            Some(sync) if !sync.is_complete() => sync,
+            _ => return,
+        };
+
+        // Henüz hiçbir komşu başlık göndermediyse beklemeye devam et
+        if sync.peers.is_empty() && !sync.pending.is_empty() {
+            return;
+        }
+
+        // Başlık istenen tüm komşular cevap vermeden (veya zaman aşımı dolmadan) hedef kesinleşmez
+        let target = sync.target_height();
+        if sync.pending.is_empty() && height >= target {
+            sync.completed_at = Some(now);
+        }
+
+        // İlerleme her %25'te bir raporlanır
+        let percent = (height * 100).checked_div(target).unwrap_or(100).min(100);
+        let report = percent / 25 > sync.last_reported / 25 || sync.is_complete();
+        if report {
+            sync.last_reported = percent;
+        }
+        let complete = sync.is_complete();
+        if complete {
+            self.record_resync(node_id, now);
+        }
+
+        if report {
+            if let Some(progress) = self.sync_progress(node_id) {
+                self.emit(Event::SyncProgress(progress));
+            }
+        }
+
+        if !complete {
+            self.fill_download_slots(node_id);
+        }
+    }
+
+    // Eksik blok gövdelerini, o blokları bilen komşular arasında paylaştırarak iste
+    fn fill_download_slots(&mut self, node_id: usize) {
+        let node = match self.nodes.get(node_id) {
+            Some(node) => node,
+            None => return,
+        };
+        let sync = match node.sync.as_ref() {
+            Some(sync) => sync,
+            None => return,
+        };
+
+        // En iyi başlık zincirinde geriye doğru yürüyerek henüz sahip olunmayan blokları bul
+        let mut missing = Vec::new();
+        let mut cursor = sync.best_header.as_ref().map(|(hash, _)| hash.clone());
+        while let Some(hash) = cursor {
+            if node.knows_block(&hash) {
+                break;
+            }
+            cursor = sync.headers.get(&hash).map(|header| header.previous_hash.clone());
+            // Sırasız gelip yetim havuzunda ebeveynini bekleyen bloklar tekrar istenmez
+            let item = InvItem::Block(hash);
+            if !node.has_item(&item) {
+                missing.push(item);
+            }
+        }
+        missing.reverse();
+
+        // Her komşunun şu an cevap bekleyen istek sayısı
+        let mut in_flight: HashMap<usize, usize> = sync.peers.iter().map(|&peer| (peer, 0)).collect();
+        for (peer, _) in node.requested.values() {
+            if let Some(count) = in_flight.get_mut(peer) {
+                *count += 1;
+            }
+        }
+
+        // Düşük indeksli bloklar önce, en az yüklü komşudan istenir
+        let mut batches: HashMap<usize, Vec<InvItem>> = HashMap::new();
+        for item in missing {
+            if node.requested.contains_key(item.id()) {
+                continue;
+            }
+
+            let peer = sync
+                .peers
+                .iter()
+                .copied()
+                .filter(|&peer| node.peer_knows(peer, item.id()) && in_flight[&peer] < MAX_BLOCKS_IN_FLIGHT)
+                .min_by_key(|peer| in_flight[peer]);
+
+            if let Some(peer) = peer {
+                *in_flight.get_mut(&peer).unwrap() += 1;
+                batches.entry(peer).or_default().push(item);
+            }
+        }
+
+        // İstekleri sabit sırada gönder (HashMap sırası çalıştırmadan çalıştırmaya değişebilir)
+        let mut batches: Vec<(usize, Vec<InvItem>)> = batches.into_iter().collect();
+        batches.sort_by_key(|(peer, _)| *peer);
+        for (peer, items) in batches {
+            self.request_items(node_id, peer, items);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::link::{LatencyModel, LinkConfig};
+    use crate::stake::ConsensusMode;
+    use crate::topology::Topology;
+
+    // Verilen sayıda blok üretip duran, tam bağlı 4 node'lu PoW ağı
+    fn network(seed: u64, blocks: usize) -> BlockchainNetwork {
+        let mut network = BlockchainNetwork::with_seed(seed);
+        network.set_difficulty(1);
+        network.set_block_time(2);
+        network.default_link = LinkConfig { latency: LatencyModel::Uniform { min: 50, max: 150 }, loss_rate: 0.0, bandwidth: 50_000 };
+        for _ in 0..4 {
+            network.add_node();
+        }
+        network.apply_topology(&Topology::FullMesh).unwrap();
+        network.select_random_validator().unwrap();
+        network.start_automatic_mining().unwrap();
+        network.run_blocks(blocks);
+        network.stop_automatic_mining().unwrap();
+        network.run_for(10_000);
+        network
+    }
+
+    fn tip(network: &BlockchainNetwork, node_id: usize) -> String {
+        network.nodes[node_id].blockchain.last().unwrap().hash.clone()
+    }
+
+    // Senkronizasyon tamamlanana kadar küçük adımlarla ilerle; her adımda kontrol çalıştırılır
+    fn run_sync(network: &mut BlockchainNetwork, node_id: usize, mut check: impl FnMut(&BlockchainNetwork)) -> SyncProgress {
+        for _ in 0..2000 {
+            check(network);
+            let progress = network.sync_progress(node_id).unwrap();
+            if progress.complete {
+                return progress;
+            }
+            network.run_for(25);
+        }
+        panic!("senkronizasyon tamamlanmadı: {}", network.sync_progress(node_id).unwrap());
+    }
+
+    #[test]
+    fn late_joiner_reaches_the_network_tip() {
+        let mut network = network(1, 40);
+        let expected = network.canonical_chain().len();
+        let node_id = network.join_node(&[0, 1, 2]).unwrap();
+
+        let progress = run_sync(&mut network, node_id, |_| {});
+        assert_eq!(progress.target, expected);
+        assert_eq!(progress.height, expected);
+        assert_eq!(tip(&network, node_id), tip(&network, 0));
+    }
+
+    #[test]
+    fn downloads_are_spread_across_peers_within_the_limit() {
+        let mut network = network(2, 80);
+        let node_id = network.join_node(&[0, 1, 2, 3]).unwrap();
+
+        let mut used = HashSet::new();
+        run_sync(&mut network, node_id, |network| {
+            let mut in_flight: HashMap<usize, usize> = HashMap::new();
+            for (peer, _) in network.nodes[node_id].requested.values() {
+                *in_flight.entry(*peer).or_default() += 1;
+            }
+            for (peer, count) in in_flight {
+                assert!(count <= MAX_BLOCKS_IN_FLIGHT, "Node {}'den {} blok bekleniyor", peer, count);
+                used.insert(peer);
+            }
+        });
+        assert!(used.len() >= 2, "gövdeler tek komşudan indirildi: {:?}", used);
+        assert_eq!(tip(&network, node_id), tip(&network, 0));
+    }
+
+    #[test]
+    fn headers_must_be_continuous_and_sealed() {
+        let network = network(3, 6);
+        let engine = ConsensusMode::ProofOfWork.engine();
+        let headers: Vec<BlockHeader> = network.canonical_chain().iter().map(|block| block.header()).collect();
+        assert_eq!(validate_headers(&headers, 1, engine.as_ref()), Ok(()));
+
+        // Aradan bir başlık eksik
+        let mut gap = headers.clone();
+        gap.remove(3);
+        assert_eq!(
+            validate_headers(&gap, 1, engine.as_ref()),
+            Err(ValidationError::InvalidIndex { expected: 3, found: 4 })
+        );
+
+        // Başka bir zincirin başlığı araya girmiş
+        let mut foreign = headers.clone();
+        foreign[3].previous_hash = "0".repeat(64);
+        foreign[3].hash = foreign[3].calculate_hash();
+        while !foreign[3].hash.starts_with('0') {
+            foreign[3].nonce += 1;
+            foreign[3].hash = foreign[3].calculate_hash();
+        }
+        assert!(matches!(validate_headers(&foreign, 1, engine.as_ref()), Err(ValidationError::InvalidPreviousHash { .. })));
+
+        // Hash'i doğru ama iş kanıtı yetersiz
+        let mut unsealed = headers.clone();
+        while unsealed[2].hash.starts_with('0') {
+            unsealed[2].nonce += 1;
+            unsealed[2].hash = unsealed[2].calculate_hash();
+        }
+        assert_eq!(
+            validate_headers(&unsealed[..3], 1, engine.as_ref()),
+            Err(ValidationError::InsufficientProofOfWork { index: 2, difficulty: 1 })
+        );
+
+        // Hash'i içeriğiyle uyuşmuyor
+        let mut tampered = headers;
+        tampered[2].timestamp += 1;
+        assert_eq!(validate_headers(&tampered, 1, engine.as_ref()), Err(ValidationError::InvalidHash { index: 2 }));
+    }
+
+    #[test]
+    fn timed_out_block_request_moves_to_another_peer() {
+        let mut network = network(4, 60);
+        let node_id = network.join_node(&[0, 1, 2]).unwrap();
+
+        // İlk gövde istekleri gidene kadar ilerle, sonra isteklerin gittiği komşulardan birini çökert
+        while network.nodes[node_id].requested.is_empty() {
+            network.run_for(10);
+        }
+        let (stalled, peer) = network.nodes[node_id]
+            .requested
+            .iter()
+            .map(|(hash, (peer, _))| (hash.clone(), *peer))
+            .min()
+            .unwrap();
+        let waiting: Vec<String> = network.nodes[node_id]
+            .requested
+            .iter()
+            .filter(|(_, (requested_from, _))| *requested_from == peer)
+            .map(|(hash, _)| hash.clone())
+            .collect();
+        network.crash_node(peer).unwrap();
+
+        // Zaman aşımından hemen sonra cevapsız istekler başka komşulara verilmiş olmalı
+        network.run_for(network.request_timeout + 1);
+        let node = &network.nodes[node_id];
+        for hash in &waiting {
+            let moved = node.requested.get(hash).map(|(requested_from, _)| *requested_from != peer).unwrap_or(false);
+            assert!(moved || node.knows_block(hash), "{} hâlâ çöken komşudan bekleniyor", hash);
+        }
+        assert!(waiting.contains(&stalled));
+
+        run_sync(&mut network, node_id, |_| {});
+        let live = (0..3).find(|&id| id != peer).unwrap();
+        assert_eq!(tip(&network, node_id), tip(&network, live));
+    }
+
+    #[test]
+    fn unresponsive_header_peer_is_given_up_after_the_timeout() {
+        let mut network = network(5, 20);
+        network.crash_node(2).unwrap();
+        let node_id = network.join_node(&[2, 3]).unwrap();
+
+        let progress = run_sync(&mut network, node_id, |_| {});
+        assert!(progress.elapsed >= network.request_timeout, "{}", progress);
+        assert_eq!(tip(&network, node_id), tip(&network, 3));
+    }
+
+    #[test]
+    fn best_header_follows_the_engine_work() {
+        let mut network = network(6, 10);
+        let node_id = network.join_node(&[0]).unwrap();
+        run_sync(&mut network, node_id, |_| {});
+
+        let node = &network.nodes[node_id];
+        let sync = node.sync.as_ref().unwrap();
+        let (hash, index) = sync.best_header.clone().unwrap();
+        assert_eq!(index + 1, node.blockchain.len());
+        assert_eq!(sync.best_work, node.header_chain_work(&hash));
+        assert_eq!(sync.best_work, node.blockchain.iter().map(|block| node.consensus.block_work(&block.hash)).sum::<u128>());
+    }
+}
@@ -0,0 +1,530 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::block::Block;
+use crate::error::{NetworkError, TransactionError};
+use crate::message::{InvItem, Message};
+use crate::node::{BlockStatus, Node};
+use crate::sync::{validate_headers, MAX_HEADERS};
+use crate::transaction::Transaction;
+use crate::wire::{read_packet, write_packet, Packet, PROTOCOL_VERSION};
+
+// El sıkışmanın tamamlanması için beklenecek en uzun süre
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Gerçek TCP modunda çalışan bir node'un ayarları
+#[derive(Debug, Clone)]
+pub struct TcpNodeConfig {
+    pub node_id: usize,
+    pub port: u16,                         // Dinlenecek port (0: işletim sistemi seçer)
+    pub peers: Vec<SocketAddr>,            // Bağlanılacak komşular; bağlantı koparsa tekrar denenir
+    pub difficulty: usize,
+    pub mine_interval: Option<Duration>,   // Verilirse node bu aralıkla kendi zincirinin ucuna blok çıkarır
+    pub create_genesis: bool,              // Genesis bloğunu bu node mu oluşturacak
+    pub seed: Option<u64>,                 // Cüzdan ve coinbase nonce'ları için seed
+    pub reconnect_interval: Duration,      // Kopan komşulara yeniden bağlanma denemeleri arasındaki süre
+    pub verbose: bool,                     // Bağlantı ve blok olaylarını ekrana yaz
+}
+
+impl Default for TcpNodeConfig {
+    fn default() -> Self {
+        TcpNodeConfig {
+            node_id: 0,
+            port: 0,
+            peers: Vec::new(),
+            difficulty: 2,
+            mine_interval: None,
+            create_genesis: false,
+            seed: None,
+            reconnect_interval: Duration::from_secs(2),
+            verbose: false,
+        }
+    }
+}
+
+// El sıkışması tamamlanmış bir komşu bağlantısı
+struct Peer {
+    stream: TcpStream,       // Yazma ucu (okuma ayrı bir thread'de yapılır)
+    addr: SocketAddr,        // Bağlantının karşı ucu
+    listen_addr: SocketAddr, // Komşunun dinlediği adres (sürüm mesajından)
+    address: String,         // Komşunun cüzdan adresi
+}
+
+// Thread'ler arasında paylaşılan durum
+struct Shared {
+    config: TcpNodeConfig,
+    listen_port: u16,
+    node: Mutex<Node>,
+    peers: Mutex<HashMap<usize, Peer>>, // Komşu node ID -> bağlantı
+    dial_targets: Mutex<Vec<SocketAddr>>,
+    running: AtomicBool,
+}
+
+// Kendi thread'lerinde çalışan ve 127.0.0.1 üzerinde TCP ile haberleşen node
+// Her bağlantı için bir okuma thread'i, ayrıca dinleme, yeniden bağlanma ve (isteğe bağlı) madencilik thread'leri vardır
+pub struct TcpNode {
+    pub node_id: usize,
+    pub local_addr: SocketAddr,
+    shared: Arc<Shared>,
+    handles: Vec<JoinHandle<()>>,
+}
+
+impl TcpNode {
+    // Node'u başlat: portu dinle, komşulara bağlan
+    pub fn spawn(config: TcpNodeConfig) -> io::Result<TcpNode> {
+        let listener = TcpListener::bind(("127.0.0.1", config.port))?;
+        let local_addr = listener.local_addr()?;
+
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_rng(&mut rand::rng()),
+        };
+        let mut node = Node::with_rng(config.node_id, None, rng);
+
+        // Genesis bloğunu üreten node onu hemen kendi zincirine ekler, diğerleri başlıklarla indirir
+        if config.create_genesis {
+            node.is_validator = true;
+            node.mine_block_at(config.difficulty, unix_time()).map_err(io::Error::other)?;
+        }
+
+        let shared = Arc::new(Shared {
+            listen_port: local_addr.port(),
+            node: Mutex::new(node),
+            peers: Mutex::new(HashMap::new()),
+            dial_targets: Mutex::new(config.peers.clone()),
+            running: AtomicBool::new(true),
+            config,
+        });
+
+        let mut handles = Vec::new();
+
+        let accept_shared = Arc::clone(&shared);
+        handles.push(thread::spawn(move || accept_loop(accept_shared, listener)));
+
+        let dial_shared = Arc::clone(&shared);
+        handles.push(thread::spawn(move || dial_loop(dial_shared)));
+
+        if let Some(interval) = shared.config.mine_interval {
+            let mining_shared = Arc::clone(&shared);
+            handles.push(thread::spawn(move || mining_loop(mining_shared, interval)));
+        }
+
+        Ok(TcpNode {
+            node_id: shared.config.node_id,
+            local_addr,
+            shared,
+            handles,
+        })
+    }
+
+    // Yeni bir komşu adresi ekle; bağlantı bir sonraki denemede kurulur
+    pub fn connect(&self, addr: SocketAddr) {
+        let mut targets = self.shared.dial_targets.lock().unwrap();
+        if !targets.contains(&addr) {
+            targets.push(addr);
+        }
+    }
+
+    // El sıkışması tamamlanmış komşuların ID'leri
+    pub fn peer_ids(&self) -> Vec<usize> {
+        let mut ids: Vec<usize> = self.shared.peers.lock().unwrap().keys().copied().collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    // Komşunun cüzdan adresi
+    pub fn peer_address(&self, peer_id: usize) -> Option<String> {
+        self.shared.peers.lock().unwrap().get(&peer_id).map(|peer| peer.address.clone())
+    }
+
+    // Node'un durumuna kilitli erişim
+    pub fn with_node<R>(&self, f: impl FnOnce(&Node) -> R) -> R {
+        f(&self.shared.node.lock().unwrap())
+    }
+
+    // Aktif zincirin uzunluğu
+    pub fn height(&self) -> usize {
+        self.with_node(|node| node.blockchain.len())
+    }
+
+    // Yeni bir işlem oluştur ve komşulara duyur
+    pub fn create_transaction(&self, recipient_address: &str, amount: u64) -> Result<Transaction, TransactionError> {
+        let tx = self
+            .shared
+            .node
+            .lock()
+            .unwrap()
+            .create_transaction_at(recipient_address, amount, unix_time())?;
+
+        relay(&self.shared, None, InvItem::Transaction(tx.id.clone()));
+        Ok(tx)
+    }
+
+    // Kendi zincirinin ucuna hemen bir blok çıkar ve duyur
+    pub fn mine_block(&self) -> Result<Block, NetworkError> {
+        mine(&self.shared)
+    }
+
+    // Node'u durdur: tüm bağlantıları kapat ve thread'lerin bitmesini bekle
+    // Bellekteki durum kaybolur; aynı portla yeniden başlatılan node komşularından senkronize olur
+    pub fn shutdown(mut self) {
+        self.stop();
+    }
+
+    fn stop(&mut self) {
+        if !self.shared.running.swap(false, Ordering::SeqCst) {
+            return;
+        }
+
+        for (_, peer) in self.shared.peers.lock().unwrap().drain() {
+            let _ = peer.stream.shutdown(Shutdown::Both);
+        }
+
+        // Bekleyen accept çağrısını uyandırmak için kendimize bağlan
+        let _ = TcpStream::connect(self.local_addr);
+
+        for handle in self.handles.drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for TcpNode {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+// Gelen bağlantıları kabul et
+fn accept_loop(shared: Arc<Shared>, listener: TcpListener) {
+    for stream in listener.incoming() {
+        if !shared.running.load(Ordering::SeqCst) {
+            break;
+        }
+
+        if let Ok(stream) = stream {
+            let shared = Arc::clone(&shared);
+            thread::spawn(move || {
+                let _ = run_connection(shared, stream);
+            });
+        }
+    }
+}
+
+// Bağlı olunmayan komşu adreslerine düzenli aralıklarla bağlanmayı dene
+fn dial_loop(shared: Arc<Shared>) {
+    while shared.running.load(Ordering::SeqCst) {
+        let targets = shared.dial_targets.lock().unwrap().clone();
+        let connected: HashSet<SocketAddr> = shared
+            .peers
+            .lock()
+            .unwrap()
+            .values()
+            .map(|peer| peer.listen_addr)
+            .collect();
+
+        for addr in targets {
+            if connected.contains(&addr) {
+                continue;
+            }
+            if let Ok(stream) = TcpStream::connect_timeout(&addr, HANDSHAKE_TIMEOUT) {
+                let shared = Arc::clone(&shared);
+                thread::spawn(move || {
+                    let _ = run_connection(shared, stream);
+                });
+            }
+        }
+
+        sleep_while_running(&shared, shared.config.reconnect_interval);
+    }
+}
+
+// Belirli aralıklarla blok üret
+fn mining_loop(shared: Arc<Shared>, interval: Duration) {
+    loop {
+        sleep_while_running(&shared, interval);
+        if !shared.running.load(Ordering::SeqCst) {
+            break;
+        }
+
+        // Genesis henüz indirilmediyse ayrı bir zincir başlatılmaz
+        if shared.node.lock().unwrap().blockchain.is_empty() {
+            continue;
+        }
+        let _ = mine(&shared);
+    }
+}
+
+// Node'un ucuna yeni blok çıkar, kendi zincirine ekle ve duyur
+fn mine(shared: &Shared) -> Result<Block, NetworkError> {
+    let difficulty = shared.config.difficulty;
+    let block = {
+        let mut node = shared.node.lock().unwrap();
+        node.is_validator = true;
+        node.mine_block_at(difficulty, unix_time())?
+    };
+
+    if shared.config.verbose {
+        println!("[node {}] Blok #{} üretildi: {}", shared.config.node_id, block.index, block.hash);
+    }
+    relay(shared, None, InvItem::Block(block.hash.clone()));
+    Ok(block)
+}
+
+// Bağlantıda el sıkış, komşuyu kaydet ve kopana kadar gelen paketleri işle
+fn run_connection(shared: Arc<Shared>, mut stream: TcpStream) -> io::Result<()> {
+    stream.set_nodelay(true)?;
+    let addr = stream.peer_addr()?;
+    let (peer_id, peer_height, listen_port, address) = handshake(&shared, &mut stream)?;
+    let listen_addr = SocketAddr::new(addr.ip(), listen_port);
+
+    {
+        let mut peers = shared.peers.lock().unwrap();
+        // Kendimize veya zaten bağlı olduğumuz bir komşuya ikinci bağlantı açılmaz
+        if peer_id == shared.config.node_id || peers.contains_key(&peer_id) || !shared.running.load(Ordering::SeqCst) {
+            let _ = stream.shutdown(Shutdown::Both);
+            return Ok(());
+        }
+        peers.insert(peer_id, Peer { stream: stream.try_clone()?, addr, listen_addr, address });
+    }
+    if shared.config.verbose {
+        println!("[node {}] Komşu bağlandı: node {} ({})", shared.config.node_id, peer_id, addr);
+    }
+
+    // Komşunun zinciri daha uzunsa başlıkları iste
+    let (height, locator) = {
+        let node = shared.node.lock().unwrap();
+        (node.blockchain.len(), node.block_locator())
+    };
+    if peer_height > height {
+        send(&shared, peer_id, Message::GetHeaders { locator });
+    }
+
+    let result = loop {
+        match read_packet(&mut stream) {
+            Ok(Packet::Message(message)) => handle_message(&shared, peer_id, message),
+            // El sıkışmadan sonra tekrar gelen sürüm mesajları protokol ihlalidir
+            Ok(_) => break Err(io::Error::new(io::ErrorKind::InvalidData, "beklenmeyen el sıkışma paketi")),
+            Err(e) => break Err(e),
+        }
+    };
+
+    // Bağlantı koptu; aynı ID ile yeni bir bağlantı kurulmuşsa ona dokunma
+    let removed = {
+        let mut peers = shared.peers.lock().unwrap();
+        let current = peers.get(&peer_id).map(|peer| peer.addr == addr).unwrap_or(false);
+        if current {
+            peers.remove(&peer_id);
+        }
+        current
+    };
+    if removed {
+        // Bu komşudan beklenen cevaplar artık gelmeyecek, başka komşulardan istenebilsin
+        shared.node.lock().unwrap().requested.retain(|_, (peer, _)| *peer != peer_id);
+        if shared.config.verbose && shared.running.load(Ordering::SeqCst) {
+            println!("[node {}] Komşu ayrıldı: node {}", shared.config.node_id, peer_id);
+        }
+    }
+    result
+}
+
+// Sürüm mesajlarını karşılıklı gönder ve onayla
+// Dönüş: (komşu ID, komşunun zincir uzunluğu, komşunun dinlediği port, komşunun cüzdan adresi)
+fn handshake(shared: &Shared, stream: &mut TcpStream) -> io::Result<(usize, usize, u16, String)> {
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+
+    let (height, address) = {
+        let node = shared.node.lock().unwrap();
+        (node.blockchain.len(), node.get_address().to_string())
+    };
+    write_packet(stream, &Packet::Version {
+        version: PROTOCOL_VERSION,
+        node_id: shared.config.node_id,
+        height,
+        listen_port: shared.listen_port,
+        address,
+    })?;
+
+    let (peer_id, peer_height, peer_port, peer_address) = match read_packet(stream)? {
+        Packet::Version { version, node_id, height, listen_port, address } if version == PROTOCOL_VERSION => {
+            (node_id, height, listen_port, address)
+        }
+        Packet::Version { version, .. } => {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("desteklenmeyen protokol sürümü: {}", version)));
+        }
+        _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "sürüm mesajı bekleniyordu")),
+    };
+
+    write_packet(stream, &Packet::VerAck)?;
+    match read_packet(stream)? {
+        Packet::VerAck => {}
+        _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "verack bekleniyordu")),
+    }
+
+    stream.set_read_timeout(None)?;
+    Ok((peer_id, peer_height, peer_port, peer_address))
+}
+
+// Komşudan gelen protokol mesajını işle
+// Cevaplar node kilidi bırakıldıktan sonra gönderilir
+fn handle_message(shared: &Shared, from: usize, message: Message) {
+    let difficulty = shared.config.difficulty;
+    let mut replies: Vec<Message> = Vec::new();
+    let mut announce: Vec<InvItem> = Vec::new();
+
+    {
+        let mut node = shared.node.lock().unwrap();
+        match message {
+            Message::Block(block) => {
+                node.requested.remove(&block.hash);
+                node.mark_known(from, &block.hash);
+                let hash = block.hash.clone();
+                let index = block.index;
+
+                match node.receive_block(block, difficulty) {
+                    // Ebeveyni bilinmiyorsa aradaki blokları başlıklarla iste
+                    Ok(BlockStatus::Orphan) => replies.push(Message::GetHeaders { locator: node.block_locator() }),
+                    Ok(status) if status.is_accepted() => {
+                        if shared.config.verbose {
+                            println!("[node {}] Blok #{} node {} üzerinden alındı", shared.config.node_id, index, from);
+                        }
+                        announce.push(InvItem::Block(hash));
+                        for (orphan, _) in node.process_orphans(difficulty) {
+                            announce.push(InvItem::Block(orphan.hash));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Message::Transaction(tx) => {
+                node.requested.remove(&tx.id);
+                node.mark_known(from, &tx.id);
+                let id = tx.id.clone();
+                if let Ok(true) = node.receive_transaction(tx) {
+                    announce.push(InvItem::Transaction(id));
+                }
+            }
+            Message::Inv(items) => {
+                let mut wanted = Vec::new();
+                for item in items {
+                    node.mark_known(from, item.id());
+                    if !node.has_item(&item) && !node.requested.contains_key(item.id()) {
+                        node.requested.insert(item.id().to_string(), (from, 0));
+                        wanted.push(item);
+                    }
+                }
+                if !wanted.is_empty() {
+                    replies.push(Message::GetData(wanted));
+                }
+            }
+            Message::GetData(items) => {
+                for item in items {
+                    match item {
+                        InvItem::Block(hash) => {
+                            if let Some(block) = node.find_block(&hash) {
+                                replies.push(Message::Block(block.clone()));
+                            }
+                        }
+                        InvItem::Transaction(id) => {
+                            if let Some(tx) = node.mempool.iter().find(|tx| tx.id == id) {
+                                replies.push(Message::Transaction(tx.clone()));
+                            }
+                        }
+                    }
+                }
+            }
+            Message::GetHeaders { locator } => {
+                replies.push(Message::Headers(node.headers_after(&locator, MAX_HEADERS)));
+            }
+            Message::Headers(headers) => {
+                let connects = headers
+                    .first()
+                    .map(|first| first.index == 0 || node.knows_block(&first.previous_hash))
+                    .unwrap_or(false);
+                if connects && validate_headers(&headers, difficulty, node.consensus.as_ref()).is_ok() {
+                    let wanted: Vec<InvItem> = headers
+                        .iter()
+                        .filter(|header| !node.knows_block(&header.hash))
+                        .map(|header| InvItem::Block(header.hash.clone()))
+                        .collect();
+                    for header in &headers {
+                        node.mark_known(from, &header.hash);
+                    }
+                    if !wanted.is_empty() {
+                        replies.push(Message::GetData(wanted));
+                    }
+                    // Dolu gelen cevap, komşunun daha fazla başlığı olduğunu gösterir
+                    if headers.len() == MAX_HEADERS {
+                        let last = headers.last().unwrap().hash.clone();
+                        replies.push(Message::GetHeaders { locator: vec![last] });
+                    }
+                }
+            }
+            // TCP modu PoW ile çalışır; kanıtlar sadece doğrulanıp havuza alınır
+            Message::Evidence(evidence) => {
+                node.mark_known(from, &evidence.id());
+                let _ = node.add_evidence(*evidence);
+            }
+            // TCP modunda BFT kesinlik katmanı çalışmaz; öneri ve oylar yok sayılır
+            Message::Proposal(_) | Message::Vote(_) => {}
+        }
+    }
+
+    for reply in replies {
+        send(shared, from, reply);
+    }
+    for item in announce {
+        relay(shared, Some(from), item);
+    }
+}
+
+// Öğeyi, bilmeyen tüm komşulara inv ile duyur
+fn relay(shared: &Shared, except: Option<usize>, item: InvItem) {
+    let targets: Vec<usize> = {
+        let mut node = shared.node.lock().unwrap();
+        let peer_ids: Vec<usize> = shared.peers.lock().unwrap().keys().copied().collect();
+        let targets: Vec<usize> = peer_ids
+            .into_iter()
+            .filter(|&peer| Some(peer) != except && !node.peer_knows(peer, item.id()))
+            .collect();
+        for &peer in &targets {
+            node.mark_known(peer, item.id());
+        }
+        targets
+    };
+
+    for peer in targets {
+        send(shared, peer, Message::Inv(vec![item.clone()]));
+    }
+}
+
+// Komşuya mesaj gönder; yazma hatasında bağlantıyı kapat (okuma thread'i komşuyu listeden çıkarır)
+fn send(shared: &Shared, peer_id: usize, message: Message) {
+    let mut peers = shared.peers.lock().unwrap();
+    if let Some(peer) = peers.get_mut(&peer_id) {
+        if write_packet(&mut peer.stream, &Packet::Message(message)).is_err() {
+            let _ = peer.stream.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+// Durdurulma isteğine bakarak kısa aralıklarla uyu
+fn sleep_while_running(shared: &Shared, duration: Duration) {
+    let step = Duration::from_millis(50);
+    let mut slept = Duration::ZERO;
+    while slept < duration && shared.running.load(Ordering::SeqCst) {
+        thread::sleep(step.min(duration - slept));
+        slept += step;
+    }
+}
+
+fn unix_time() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).expect("Zaman alınamadı").as_secs()
+}
@@ -0,0 +1,536 @@
+use rand::Rng;
+use std::collections::{HashSet, VecDeque};
+use std::str::FromStr;
+
+use crate::error::{NetworkError, TopologyError};
+use crate::network::BlockchainNetwork;
+
+// Konfigürasyon modelinde geçerli bir eşleşme bulmak için yapılacak en fazla deneme
+const REGULAR_ATTEMPTS: usize = 1000;
+
+// Node'lar arasındaki bağlantı grafiğinin şekli
+#[derive(Debug, Clone, PartialEq)]
+pub enum Topology {
+    // Her node diğer tüm node'lara bağlı
+    FullMesh,
+    // Her node iki komşusuna bağlı bir halka
+    Ring,
+    // Tüm node'lar tek bir merkeze bağlı
+    Star { hub: usize },
+    // Her node'un tam olarak `degree` komşusu olduğu rastgele graf
+    RandomRegular { degree: usize },
+    // Erdős–Rényi: her node çifti birbirinden bağımsız `probability` olasılıkla bağlı
+    ErdosRenyi { probability: f64 },
+    // Watts–Strogatz küçük dünya: her node halkada `neighbors` yakın komşusuna bağlı,
+    // her bağlantının ucu `rewire` olasılıkla rastgele bir node'a taşınır
+    WattsStrogatz { neighbors: usize, rewire: f64 },
+    // Barabási–Albert ölçekten bağımsız ağ: her yeni node, derecesiyle orantılı olasılıkla `edges` node'a bağlanır
+    BarabasiAlbert { edges: usize },
+    // Dosyadan veya elle verilen bağlantı listesi
+    EdgeList(Vec<(usize, usize)>),
+}
+
+impl Topology {
+    // Kenar listesi dosyasını oku
+    // Her satırda boşluk veya virgülle ayrılmış iki node ID'si; boş satırlar ve # ile başlayan yorumlar atlanır
+    pub fn from_edge_list_file(path: &str) -> Result<Topology, TopologyError> {
+        let text = std::fs::read_to_string(path).map_err(|e| TopologyError::Io(format!("{}: {}", path, e)))?;
+        Ok(Topology::EdgeList(parse_edge_list(&text)?))
+    }
+
+    // Topolojinin verilen node sayısı için kenarlarını üret (her kenar bir kez, küçük ID önce)
+    pub fn edges<R: Rng + ?Sized>(&self, node_count: usize, rng: &mut R) -> Result<Vec<(usize, usize)>, TopologyError> {
+        let mut edges = Vec::new();
+
+        match self {
+            Topology::FullMesh => {
+                for i in 0..node_count {
+                    for j in (i + 1)..node_count {
+                        edges.push((i, j));
+                    }
+                }
+            }
+            Topology::Ring => {
+                if node_count == 2 {
+                    edges.push((0, 1));
+                } else if node_count > 2 {
+                    for i in 0..node_count {
+                        edges.push(ordered(i, (i + 1) % node_count));
+                    }
+                }
+            }
+            Topology::Star { hub } => {
+                if node_count > 0 && *hub >= node_count {
+                    return Err(TopologyError::NodeOutOfRange { node: *hub, node_count });
+                }
+                for i in (0..node_count).filter(|i| i != hub) {
+                    edges.push(ordered(*hub, i));
+                }
+            }
+            Topology::RandomRegular { degree } => {
+                edges = random_regular(node_count, *degree, rng)?;
+            }
+            Topology::ErdosRenyi { probability } => {
+                check_probability(*probability)?;
+                for i in 0..node_count {
+                    for j in (i + 1)..node_count {
+                        if rng.random_bool(*probability) {
+                            edges.push((i, j));
+                        }
+                    }
+                }
+            }
+            Topology::WattsStrogatz { neighbors, rewire } => {
+                edges = watts_strogatz(node_count, *neighbors, *rewire, rng)?;
+            }
+            Topology::BarabasiAlbert { edges: per_node } => {
+                edges = barabasi_albert(node_count, *per_node, rng)?;
+            }
+            Topology::EdgeList(list) => {
+                let mut seen = HashSet::new();
+                for &(a, b) in list {
+                    for node in [a, b] {
+                        if node >= node_count {
+                            return Err(TopologyError::NodeOutOfRange { node, node_count });
+                        }
+                    }
+                    if a != b && seen.insert(ordered(a, b)) {
+                        edges.push(ordered(a, b));
+                    }
+                }
+            }
+        }
+
+        Ok(edges)
+    }
+}
+
+// Komut satırı için kısa gösterim:
+// mesh, ring, star[:merkez], regular:<k>, er:<p>, ws:<k>:<p>, ba:<m>, file:<yol>
+impl FromStr for Topology {
+    type Err = TopologyError;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = spec.splitn(2, ':').collect();
+        let argument = parts.get(1).copied();
+
+        let topology = match (parts[0], argument) {
+            ("mesh", None) => Topology::FullMesh,
+            ("ring", None) => Topology::Ring,
+            ("star", None) => Topology::Star { hub: 0 },
+            ("star", Some(hub)) => Topology::Star { hub: parse_parameter(spec, hub)? },
+            ("regular", Some(degree)) => Topology::RandomRegular { degree: parse_parameter(spec, degree)? },
+            ("er", Some(probability)) => Topology::ErdosRenyi { probability: parse_parameter(spec, probability)? },
+            ("ws", Some(arguments)) => {
+                let (neighbors, rewire) = arguments
+                    .split_once(':')
+                    .ok_or_else(|| TopologyError::InvalidParameter(spec.to_string()))?;
+                Topology::WattsStrogatz {
+                    neighbors: parse_parameter(spec, neighbors)?,
+                    rewire: parse_parameter(spec, rewire)?,
+                }
+            }
+            ("ba", Some(edges)) => Topology::BarabasiAlbert { edges: parse_parameter(spec, edges)? },
+            ("file", Some(path)) => Topology::from_edge_list_file(path)?,
+            _ => return Err(TopologyError::UnknownTopology(spec.to_string())),
+        };
+
+        Ok(topology)
+    }
+}
+
+// Bağlantı grafiğinin özet istatistikleri
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphStats {
+    pub nodes: usize,
+    pub edges: usize,
+    pub average_degree: f64,
+    pub max_degree: usize,
+    pub connected: bool,
+    pub diameter: Option<usize>, // En uzun en kısa yol (graf bağlı değilse None)
+}
+
+impl BlockchainNetwork {
+    // Mevcut node'ları topolojiye göre birbirine bağla
+    // Rastgele topolojiler ağın seed'li üretecini kullanır; eklenen kenar sayısı döner
+    // Birbirini yasaklamış node'lar arasındaki kenarlar atlanır
+    pub fn apply_topology(&mut self, topology: &Topology) -> Result<usize, NetworkError> {
+        let node_count = self.nodes.len();
+        let edges = topology.edges(node_count, &mut self.rng)?;
+
+        let mut connected = 0;
+        for &(a, b) in &edges {
+            match self.connect_nodes(a, b) {
+                Ok(()) => connected += 1,
+                Err(NetworkError::Banned { .. }) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(connected)
+    }
+
+    // Bağlantı grafiğinin derece, bağlılık ve çap istatistikleri
+    pub fn graph_stats(&self) -> GraphStats {
+        let node_count = self.nodes.len();
+        let degrees: Vec<usize> = self.nodes.iter().map(|node| node.connections.len()).collect();
+        let degree_sum: usize = degrees.iter().sum();
+
+        // Her node'dan BFS ile en uzak node'a olan mesafe
+        let mut diameter = 0;
+        let mut connected = true;
+        for start in 0..node_count {
+            let mut distance = vec![usize::MAX; node_count];
+            let mut queue = VecDeque::from([start]);
+            distance[start] = 0;
+
+            while let Some(current) = queue.pop_front() {
+                for &next in &self.nodes[current].connections {
+                    if distance[next] == usize::MAX {
+                        distance[next] = distance[current] + 1;
+                        queue.push_back(next);
+                    }
+                }
+            }
+
+            if distance.contains(&usize::MAX) {
+                connected = false;
+                break;
+            }
+            diameter = diameter.max(distance.into_iter().max().unwrap_or(0));
+        }
+
+        GraphStats {
+            nodes: node_count,
+            edges: degree_sum / 2,
+            average_degree: if node_count == 0 { 0.0 } else { degree_sum as f64 / node_count as f64 },
+            max_degree: degrees.into_iter().max().unwrap_or(0),
+            connected,
+            diameter: if connected { Some(diameter) } else { None },
+        }
+    }
+}
+
+// Kenar listesi metnini çözümle
+pub fn parse_edge_list(text: &str) -> Result<Vec<(usize, usize)>, TopologyError> {
+    let mut edges = Vec::new();
+
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let ids: Vec<&str> = line.split(|c: char| c == ',' || c.is_whitespace()).filter(|part| !part.is_empty()).collect();
+        match ids.as_slice() {
+            [a, b] => match (a.parse::<usize>(), b.parse::<usize>()) {
+                (Ok(a), Ok(b)) => edges.push((a, b)),
+                _ => return Err(TopologyError::Parse { line: number + 1, content: line.to_string() }),
+            },
+            _ => return Err(TopologyError::Parse { line: number + 1, content: line.to_string() }),
+        }
+    }
+
+    Ok(edges)
+}
+
+fn ordered(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+fn parse_parameter<T: FromStr>(spec: &str, value: &str) -> Result<T, TopologyError> {
+    value.parse().map_err(|_| TopologyError::InvalidParameter(spec.to_string()))
+}
+
+fn check_probability(probability: f64) -> Result<(), TopologyError> {
+    if (0.0..=1.0).contains(&probability) {
+        Ok(())
+    } else {
+        Err(TopologyError::InvalidParameter(format!("olasılık 0 ile 1 arasında olmalı: {}", probability)))
+    }
+}
+
+// Konfigürasyon modeli: her node için `degree` uç oluşturulur ve uygun uç çiftleri rastgele eşlenir
+// (Steger–Wormald). Kendine bağlantı veya çift kenar oluşturmayan çift bulunamazsa eşleşme baştan denenir.
+fn random_regular<R: Rng + ?Sized>(node_count: usize, degree: usize, rng: &mut R) -> Result<Vec<(usize, usize)>, TopologyError> {
+    if node_count == 0 || degree == 0 {
+        return Ok(Vec::new());
+    }
+    if degree >= node_count || !(node_count * degree).is_multiple_of(2) {
+        return Err(TopologyError::InvalidParameter(format!(
+            "{} node için {}-düzenli graf kurulamaz (derece node sayısından küçük, node sayısı × derece çift olmalı)",
+            node_count, degree
+        )));
+    }
+
+    let stubs: Vec<usize> = (0..node_count).flat_map(|node| std::iter::repeat_n(node, degree)).collect();
+
+    'attempt: for _ in 0..REGULAR_ATTEMPTS {
+        let mut remaining = stubs.clone();
+        let mut edges = HashSet::new();
+
+        while !remaining.is_empty() {
+            let mut paired = false;
+            for _ in 0..remaining.len() * remaining.len() {
+                let i = rng.random_range(0..remaining.len());
+                let j = rng.random_range(0..remaining.len());
+                let (a, b) = (remaining[i], remaining[j]);
+                if a == b || edges.contains(&ordered(a, b)) {
+                    continue;
+                }
+
+                edges.insert(ordered(a, b));
+                remaining.swap_remove(i.max(j));
+                remaining.swap_remove(i.min(j));
+                paired = true;
+                break;
+            }
+
+            if !paired {
+                continue 'attempt;
+            }
+        }
+
+        let mut edges: Vec<(usize, usize)> = edges.into_iter().collect();
+        edges.sort_unstable();
+        return Ok(edges);
+    }
+
+    Err(TopologyError::InvalidParameter(format!(
+        "{} denemede {}-düzenli graf bulunamadı",
+        REGULAR_ATTEMPTS, degree
+    )))
+}
+
+// Halka kafes kurulur, sonra her kenarın bir ucu `rewire` olasılıkla rastgele bir node'a taşınır
+fn watts_strogatz<R: Rng + ?Sized>(
+    node_count: usize,
+    neighbors: usize,
+    rewire: f64,
+    rng: &mut R,
+) -> Result<Vec<(usize, usize)>, TopologyError> {
+    check_probability(rewire)?;
+    if !neighbors.is_multiple_of(2) || (node_count > 0 && neighbors >= node_count) {
+        return Err(TopologyError::InvalidParameter(format!(
+            "komşu sayısı çift ve node sayısından ({}) küçük olmalı: {}",
+            node_count, neighbors
+        )));
+    }
+
+    let mut lattice = Vec::new();
+    for i in 0..node_count {
+        for offset in 1..=neighbors / 2 {
+            lattice.push((i, (i + offset) % node_count));
+        }
+    }
+
+    let mut edges: HashSet<(usize, usize)> = lattice.iter().map(|&(a, b)| ordered(a, b)).collect();
+    for (source, target) in lattice {
+        if !rng.random_bool(rewire) {
+            continue;
+        }
+
+        // Kaynak zaten herkese bağlıysa taşınacak yer yok
+        let degree = edges.iter().filter(|&&(a, b)| a == source || b == source).count();
+        if degree >= node_count - 1 {
+            continue;
+        }
+
+        let new_target = loop {
+            let candidate = rng.random_range(0..node_count);
+            if candidate != source && !edges.contains(&ordered(source, candidate)) {
+                break candidate;
+            }
+        };
+        edges.remove(&ordered(source, target));
+        edges.insert(ordered(source, new_target));
+    }
+
+    let mut edges: Vec<(usize, usize)> = edges.into_iter().collect();
+    edges.sort_unstable();
+    Ok(edges)
+}
+
+// Tercihli bağlanma: ilk `edges + 1` node tam bağlı başlar, sonraki her node
+// mevcut node'lardan dereceleriyle orantılı olasılıkla `edges` farklı node seçer
+fn barabasi_albert<R: Rng + ?Sized>(node_count: usize, per_node: usize, rng: &mut R) -> Result<Vec<(usize, usize)>, TopologyError> {
+    if per_node == 0 {
+        return Err(TopologyError::InvalidParameter("her yeni node en az bir bağlantı kurmalı".to_string()));
+    }
+
+    let mut edges = Vec::new();
+    // Her node, derecesi kadar tekrar eder; buradan düzgün seçim dereceyle orantılı seçimdir
+    let mut endpoints = Vec::new();
+
+    let seed_count = (per_node + 1).min(node_count);
+    for i in 0..seed_count {
+        for j in (i + 1)..seed_count {
+            edges.push((i, j));
+            endpoints.push(i);
+            endpoints.push(j);
+        }
+    }
+
+    for node in seed_count..node_count {
+        let mut targets = HashSet::new();
+        while targets.len() < per_node {
+            targets.insert(endpoints[rng.random_range(0..endpoints.len())]);
+        }
+
+        let mut targets: Vec<usize> = targets.into_iter().collect();
+        targets.sort_unstable();
+        for target in targets {
+            edges.push((target, node));
+            endpoints.push(target);
+            endpoints.push(node);
+        }
+    }
+
+    Ok(edges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn generate(topology: &Topology, node_count: usize, seed: u64) -> Vec<(usize, usize)> {
+        topology.edges(node_count, &mut StdRng::seed_from_u64(seed)).expect("topoloji kurulamadı")
+    }
+
+    fn degrees(edges: &[(usize, usize)], node_count: usize) -> Vec<usize> {
+        let mut degrees = vec![0; node_count];
+        for &(a, b) in edges {
+            degrees[a] += 1;
+            degrees[b] += 1;
+        }
+        degrees
+    }
+
+    // Kendine bağlantı, çift kenar veya aralık dışı node yok; her kenar küçük ID önce
+    fn assert_simple(edges: &[(usize, usize)], node_count: usize) {
+        let mut seen = HashSet::new();
+        for &(a, b) in edges {
+            assert!(a < b, "kenar sıralı değil veya kendine bağlı: ({}, {})", a, b);
+            assert!(b < node_count, "node aralık dışında: {}", b);
+            assert!(seen.insert((a, b)), "çift kenar: ({}, {})", a, b);
+        }
+    }
+
+    #[test]
+    fn deterministic_topology_edge_counts() {
+        for node_count in [3, 5, 12] {
+            let ring = generate(&Topology::Ring, node_count, 0);
+            assert_simple(&ring, node_count);
+            assert_eq!(ring.len(), node_count);
+            assert!(degrees(&ring, node_count).iter().all(|&degree| degree == 2));
+
+            let star = generate(&Topology::Star { hub: 2 }, node_count, 0);
+            assert_simple(&star, node_count);
+            assert_eq!(star.len(), node_count - 1);
+            assert!(star.iter().all(|&(a, b)| a == 2 || b == 2));
+
+            let mesh = generate(&Topology::FullMesh, node_count, 0);
+            assert_simple(&mesh, node_count);
+            assert_eq!(mesh.len(), node_count * (node_count - 1) / 2);
+        }
+        assert_eq!(generate(&Topology::Ring, 2, 0), vec![(0, 1)]);
+        assert!(generate(&Topology::Ring, 1, 0).is_empty());
+        assert_eq!(
+            Topology::Star { hub: 4 }.edges(3, &mut StdRng::seed_from_u64(0)),
+            Err(TopologyError::NodeOutOfRange { node: 4, node_count: 3 })
+        );
+    }
+
+    #[test]
+    fn random_regular_has_equal_degrees() {
+        for (node_count, degree) in [(10, 3), (20, 4), (7, 6), (16, 1)] {
+            for seed in 0..5 {
+                let edges = generate(&Topology::RandomRegular { degree }, node_count, seed);
+                assert_simple(&edges, node_count);
+                assert_eq!(edges.len(), node_count * degree / 2);
+                assert!(degrees(&edges, node_count).iter().all(|&d| d == degree), "{}-düzenli değil (seed {})", degree, seed);
+            }
+        }
+
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!(Topology::RandomRegular { degree: 3 }.edges(5, &mut rng).is_err());
+        assert!(Topology::RandomRegular { degree: 5 }.edges(5, &mut rng).is_err());
+    }
+
+    #[test]
+    fn watts_strogatz_keeps_edge_count() {
+        for rewire in [0.0, 0.3, 1.0] {
+            for seed in 0..5 {
+                let edges = generate(&Topology::WattsStrogatz { neighbors: 4, rewire }, 20, seed);
+                assert_simple(&edges, 20);
+                assert_eq!(edges.len(), 20 * 4 / 2);
+            }
+        }
+
+        // Taşıma yoksa graf halka kafesin kendisidir
+        let lattice = generate(&Topology::WattsStrogatz { neighbors: 2, rewire: 0.0 }, 8, 0);
+        let mut ring = generate(&Topology::Ring, 8, 0);
+        ring.sort_unstable();
+        assert_eq!(lattice, ring);
+
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!(Topology::WattsStrogatz { neighbors: 3, rewire: 0.1 }.edges(10, &mut rng).is_err());
+        assert!(Topology::WattsStrogatz { neighbors: 4, rewire: 1.5 }.edges(10, &mut rng).is_err());
+    }
+
+    #[test]
+    fn barabasi_albert_edge_count() {
+        for (node_count, per_node) in [(10, 1), (30, 2), (50, 3)] {
+            for seed in 0..5 {
+                let edges = generate(&Topology::BarabasiAlbert { edges: per_node }, node_count, seed);
+                assert_simple(&edges, node_count);
+                // Başlangıçtaki tam bağlı çekirdek + sonraki her node için `per_node` kenar
+                let seed_count = per_node + 1;
+                assert_eq!(edges.len(), seed_count * (seed_count - 1) / 2 + (node_count - seed_count) * per_node);
+                assert!(degrees(&edges, node_count).iter().all(|&degree| degree >= per_node));
+            }
+        }
+        assert!(Topology::BarabasiAlbert { edges: 0 }.edges(10, &mut StdRng::seed_from_u64(0)).is_err());
+    }
+
+    #[test]
+    fn random_topologies_are_reproducible_with_seed() {
+        let topologies = [
+            Topology::RandomRegular { degree: 4 },
+            Topology::ErdosRenyi { probability: 0.3 },
+            Topology::WattsStrogatz { neighbors: 4, rewire: 0.3 },
+            Topology::BarabasiAlbert { edges: 2 },
+        ];
+        for topology in &topologies {
+            assert_eq!(generate(topology, 24, 7), generate(topology, 24, 7), "{:?}", topology);
+            assert_ne!(generate(topology, 24, 7), generate(topology, 24, 8), "{:?}", topology);
+        }
+    }
+
+    #[test]
+    fn edge_list_parsing() {
+        let text = "# yorum\n0 1\n\n1,2\n  2\t3  \n3 , 0\n";
+        assert_eq!(parse_edge_list(text), Ok(vec![(0, 1), (1, 2), (2, 3), (3, 0)]));
+
+        assert_eq!(parse_edge_list("0 1\n1 x\n"), Err(TopologyError::Parse { line: 2, content: "1 x".to_string() }));
+        assert_eq!(parse_edge_list("0 1 2\n"), Err(TopologyError::Parse { line: 1, content: "0 1 2".to_string() }));
+
+        // Kendine bağlantılar ve tekrar eden kenarlar atlanır, aralık dışı node reddedilir
+        let list = Topology::EdgeList(vec![(1, 0), (0, 1), (2, 2), (1, 2)]);
+        assert_eq!(generate(&list, 3, 0), vec![(0, 1), (1, 2)]);
+        assert_eq!(list.edges(2, &mut StdRng::seed_from_u64(0)), Err(TopologyError::NodeOutOfRange { node: 2, node_count: 2 }));
+    }
+
+    #[test]
+    fn topology_specs_parse() {
+        assert_eq!("mesh".parse(), Ok(Topology::FullMesh));
+        assert_eq!("star:3".parse(), Ok(Topology::Star { hub: 3 }));
+        assert_eq!("regular:4".parse(), Ok(Topology::RandomRegular { degree: 4 }));
+        assert_eq!("ws:4:0.2".parse(), Ok(Topology::WattsStrogatz { neighbors: 4, rewire: 0.2 }));
+        assert_eq!("ba:2".parse(), Ok(Topology::BarabasiAlbert { edges: 2 }));
+        assert!("ws:4".parse::<Topology>().is_err());
+        assert!("tree".parse::<Topology>().is_err());
+    }
+}
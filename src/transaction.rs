@@ -1,9 +1,12 @@
 use sha2::{Sha256, Digest};
 use std::fmt;
 use std::time::{SystemTime, UNIX_EPOCH};
-use hex;
+
+use crate::error::TransactionError;
+use crate::stake;
 
 // UTXO (Unspent Transaction Output) yapısı
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct UTXO {
     pub transaction_id: String,  // Bu UTXO'nun ait olduğu işlemin ID'si
@@ -45,6 +48,11 @@ impl Transaction {
             .unwrap()
             .as_secs();
         
+        Self::new_at(inputs, outputs, timestamp)
+    }
+    
+    // Verilen zaman damgasıyla transaction oluştur (simülasyonun sanal saati için)
+    pub fn new_at(inputs: Vec<TxInput>, outputs: Vec<TxOutput>, timestamp: u64) -> Self {
         let mut tx = Transaction {
             id: String::new(),
             inputs,
@@ -68,6 +76,12 @@ impl Transaction {
         // Rasgele bir nonce ekleyerek her coinbase işleminin benzersiz olmasını sağla
         let nonce = rand::random::<u64>();
         
+        Self::new_coinbase_at(recipient_address, amount, timestamp, nonce)
+    }
+    
+    // Verilen zaman damgası ve nonce ile coinbase transaction oluştur
+    // Simülasyon nonce'u node'un seed'li üretecinden çeker, böylece ID'ler tekrarlanabilir olur
+    pub fn new_coinbase_at(recipient_address: String, amount: u64, timestamp: u64, nonce: u64) -> Self {
         // Coinbase işleminde girdi yoktur, sadece çıktı vardır
         let outputs = vec![TxOutput {
             amount,
@@ -142,9 +156,28 @@ impl Transaction {
     
     // İşlemin geçerli olup olmadığını kontrol et
     pub fn is_valid(&self, utxo_set: &[UTXO]) -> bool {
+        self.validate(utxo_set).is_ok()
+    }
+    
+    // İşlemi doğrula ve geçersizse nedenini döndür
+    pub fn validate(&self, utxo_set: &[UTXO]) -> Result<(), TransactionError> {
         // Coinbase işlemi her zaman geçerlidir
-        if self.inputs.is_empty() && !self.outputs.is_empty() {
-            return true;
+        if self.is_coinbase() {
+            return Ok(());
+        }
+        
+        // Her girdinin harcadığı UTXO sette olmalı ve stake olarak kilitli olmamalı
+        for input in &self.inputs {
+            let utxo = utxo_set.iter().find(|utxo| {
+                get_utxo_id(&utxo.transaction_id, utxo.output_index) == input.utxo_id
+            });
+            match utxo {
+                None => return Err(TransactionError::MissingUtxo { utxo_id: input.utxo_id.clone() }),
+                Some(utxo) if stake::stake_owner(&utxo.recipient_address).is_some() => {
+                    return Err(TransactionError::StakeLocked { utxo_id: input.utxo_id.clone() });
+                }
+                Some(_) => {}
+            }
         }
         
         // Toplam girdi ve çıktı miktarlarını kontrol et
@@ -153,27 +186,49 @@ impl Transaction {
         
         // Çıktı miktarı girdi miktarından büyük olamaz
         if total_output > total_input {
-            // Geçersiz işlem: Çıktı miktarı girdi miktarından büyük
-            return false;
+            return Err(TransactionError::OutputsExceedInputs {
+                input: total_input,
+                output: total_output,
+            });
         }
         
-        true
+        Ok(())
+    }
+    
+    // İşlemin yaklaşık boyutu (byte)
+    pub fn size(&self) -> usize {
+        let inputs: usize = self.inputs
+            .iter()
+            .map(|input| input.utxo_id.len() + 8 + input.signature.len() + input.sender_address.len())
+            .sum();
+        let outputs: usize = self.outputs
+            .iter()
+            .map(|output| 8 + output.recipient_address.len())
+            .sum();
+        
+        // ID + zaman damgası + girdiler + çıktılar
+        self.id.len() + 8 + inputs + outputs
+    }
+    
+    // Girdisi olmayan ve en az bir çıktısı olan işlemler coinbase işlemidir
+    pub fn is_coinbase(&self) -> bool {
+        self.inputs.is_empty() && !self.outputs.is_empty()
     }
 }
 
 impl fmt::Display for Transaction {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Transaction ID: {}\n", self.id)?;
+        writeln!(f, "Transaction ID: {}", self.id)?;
         
-        write!(f, "Inputs:\n")?;
+        writeln!(f, "Inputs:")?;
         for (i, input) in self.inputs.iter().enumerate() {
-            write!(f, "  [{}] UTXO: {}, Gönderen: {}\n", 
+            writeln!(f, "  [{}] UTXO: {}, Gönderen: {}", 
                 i, input.utxo_id, input.sender_address)?;
         }
         
-        write!(f, "Outputs:\n")?;
+        writeln!(f, "Outputs:")?;
         for (i, output) in self.outputs.iter().enumerate() {
-            write!(f, "  [{}] Miktar: {} coin, Alıcı: {}\n", 
+            writeln!(f, "  [{}] Miktar: {} coin, Alıcı: {}", 
                 i, output.amount as f64 / 100_000_000.0, output.recipient_address)?;
         }
         
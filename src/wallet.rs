@@ -1,9 +1,10 @@
 use rand::Rng;
 use secp256k1::{Secp256k1, PublicKey, SecretKey};
 use sha2::{Sha256, Digest};
-use bs58;
-use hex;
+use std::collections::HashSet;
+use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::error::WalletError;
 use crate::transaction::{Transaction, UTXO, TxInput, TxOutput, get_utxo_id};
 
 #[derive(Clone, Debug)]
@@ -15,13 +16,24 @@ pub struct Wallet {
     utxos: Vec<UTXO>,      // Bu cüzdana ait harcanmamış çıktılar
 }
 
+impl Default for Wallet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Wallet {
     pub fn new() -> Self {
+        Self::from_rng(&mut rand::rng())
+    }
+    
+    // Verilen rastgele sayı üretecinden cüzdan oluştur
+    // Seed'li bir üreteç verildiğinde aynı seed her zaman aynı anahtarları üretir
+    pub fn from_rng<R: Rng + ?Sized>(rng: &mut R) -> Self {
         // 1. Özel anahtar oluştur (256 bit rastgele sayı)
         let secp = Secp256k1::new();
-        let mut rng = rand::thread_rng();
         // 32 byte'lık rastgele bir sayı oluşturup SecretKey'e dönüştür
-        let random_bytes: [u8; 32] = core::array::from_fn(|_| rng.gen());
+        let random_bytes: [u8; 32] = core::array::from_fn(|_| rng.random());
         let secret_key = SecretKey::from_slice(&random_bytes).expect("32 bytes secret key");
         
         // 2. Genel anahtarı elde et (ECDSA kullanılır)
@@ -47,13 +59,14 @@ impl Wallet {
         &self.public_key
     }
     
-    fn generate_address(public_key: &PublicKey) -> String {
+    // Public key'den adres üret (başka bir node'un imzasını adresiyle eşleştirmek için de kullanılır)
+    pub fn generate_address(public_key: &PublicKey) -> String {
         // Public key'i hash'le (Bitcoin'den farklı olarak sadece tek bir SHA-256 kullan)
         let public_key_bytes = public_key.serialize();
         
         // SHA-256
         let mut hasher = Sha256::new();
-        hasher.update(&public_key_bytes);
+        hasher.update(public_key_bytes);
         let hash_result = hasher.finalize();
         
         // Version byte ekle (0x00)
@@ -66,7 +79,7 @@ impl Wallet {
         let checksum_result1 = checksum_hasher1.finalize();
         
         let mut checksum_hasher2 = Sha256::new();
-        checksum_hasher2.update(&checksum_result1);
+        checksum_hasher2.update(checksum_result1);
         let checksum_result2 = checksum_hasher2.finalize();
         
         // İlk 4 byte'ı al
@@ -98,7 +111,12 @@ impl Wallet {
         signature.serialize_der().to_vec()
     }
     
-    pub fn verify(&self, data: &[u8], signature: &[u8]) -> bool {
+    pub fn verify(&self, data: &[u8], signature: &[u8]) -> Result<(), WalletError> {
+        Self::verify_with_key(&self.public_key, data, signature)
+    }
+    
+    // Verilen public key ile imzayı doğrula (cüzdanın kendisi olmadan, örn. blok önerenin imzası)
+    pub fn verify_with_key(public_key: &PublicKey, data: &[u8], signature: &[u8]) -> Result<(), WalletError> {
         let secp = Secp256k1::new();
         
         // İlk olarak verinin hash'ini al
@@ -109,10 +127,13 @@ impl Wallet {
         // Hash'i bir message tipine dönüştür
         let message = secp256k1::Message::from_digest_slice(&message_hash).expect("32 bytes");
         
-        // İmzayı doğrula
-        let signature = secp256k1::ecdsa::Signature::from_der(signature).expect("Geçerli imza");
+        // İmzayı çözümle (bozuk DER verisi panik yerine hata döndürür)
+        let signature = secp256k1::ecdsa::Signature::from_der(signature)
+            .map_err(|_| WalletError::MalformedSignature)?;
         
-        secp.verify_ecdsa(&message, &signature, &self.public_key).is_ok()
+        // İmzayı doğrula
+        secp.verify_ecdsa(&message, &signature, public_key)
+            .map_err(|_| WalletError::SignatureMismatch)
     }
     
     // Cüzdana UTXO ekle
@@ -135,24 +156,58 @@ impl Wallet {
         }
     }
     
+    // Anahtarları koruyarak tüm UTXO'ları ve bakiyeyi sıfırla (zincir yeniden kurulurken kullanılır)
+    pub fn reset_utxos(&mut self) {
+        self.utxos.clear();
+        self.balance = 0;
+    }
+    
     // Cüzdanın bakiyesini döndür
     pub fn get_balance(&self) -> u64 {
         self.balance
     }
     
     // Yeni bir işlem oluştur
-    pub fn create_transaction(&self, recipient_address: &str, amount: u64) -> Option<Transaction> {
-        // Bakiye kontrolü
-        if amount > self.balance {
-            // Yetersiz bakiye
-            return None;
+    pub fn create_transaction(&self, recipient_address: &str, amount: u64) -> Result<Transaction, WalletError> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        
+        self.create_transaction_at(recipient_address, amount, timestamp)
+    }
+    
+    // Verilen zaman damgasıyla yeni bir işlem oluştur
+    pub fn create_transaction_at(&self, recipient_address: &str, amount: u64, timestamp: u64) -> Result<Transaction, WalletError> {
+        self.create_payment_at(&[(recipient_address.to_string(), amount)], timestamp, &HashSet::new())
+    }
+    
+    // Birden fazla alıcıya tek işlemle ödeme yap (alıcı adresi, miktar)
+    // excluded verilen UTXO'lar (ör. mempool'da bekleyen işlemlerin harcadıkları) girdi olarak seçilmez.
+    pub fn create_payment_at(&self, payments: &[(String, u64)], timestamp: u64, excluded: &HashSet<String>) -> Result<Transaction, WalletError> {
+        let amount: u64 = payments.iter().map(|(_, amount)| amount).sum();
+        if payments.is_empty() || payments.iter().any(|(_, amount)| *amount == 0) {
+            return Err(WalletError::ZeroAmount);
+        }
+        
+        // Bakiye kontrolü (başka işlemlerin harcamakta olduğu UTXO'lar sayılmaz)
+        let spendable: Vec<&UTXO> = self.utxos
+            .iter()
+            .filter(|utxo| !excluded.contains(&get_utxo_id(&utxo.transaction_id, utxo.output_index)))
+            .collect();
+        let available: u64 = spendable.iter().map(|utxo| utxo.amount).sum();
+        if amount > available {
+            return Err(WalletError::InsufficientFunds {
+                available,
+                requested: amount,
+            });
         }
         
         // Girdi olarak kullanılacak UTXO'ları seç
         let mut selected_utxos = Vec::new();
         let mut selected_amount = 0;
         
-        for utxo in &self.utxos {
+        for utxo in spendable {
             selected_utxos.push(utxo.clone());
             selected_amount += utxo.amount;
             
@@ -179,13 +234,14 @@ impl Wallet {
         }
         
         // Çıktıları oluştur
-        let mut outputs = Vec::new();
-        
-        // Alıcıya gönderilecek miktar
-        outputs.push(TxOutput {
-            amount,
-            recipient_address: recipient_address.to_string(),
-        });
+        // Alıcılara gönderilecek miktarlar
+        let mut outputs: Vec<TxOutput> = payments
+            .iter()
+            .map(|(recipient_address, amount)| TxOutput {
+                amount: *amount,
+                recipient_address: recipient_address.clone(),
+            })
+            .collect();
         
         // Para üstü (eğer varsa)
         let change = selected_amount - amount;
@@ -197,7 +253,7 @@ impl Wallet {
         }
         
         // İşlemi oluştur
-        Some(Transaction::new(inputs, outputs))
+        Ok(Transaction::new_at(inputs, outputs, timestamp))
     }
     
     // Cüzdanın UTXO'larını güncelle (yeni bloklar geldiğinde)
@@ -206,21 +262,17 @@ impl Wallet {
             // Bu cüzdana ait harcanan UTXO'ları çıkar
             for input in &tx.inputs {
                 if input.sender_address == self.address {
-                    // UTXO ID'sinden transaction_id'yi çıkar
-                    // Örnek: utxo_id = "abc123def0" -> tx_id = "abc123def", output_index = 0
-                    let last_char = input.utxo_id.chars().last().unwrap_or('0');
-                    let output_index = last_char.to_digit(10).unwrap_or(0) as usize;
-                    let tx_id = &input.utxo_id[0..input.utxo_id.len()-1];
-                    
                     // UTXO'nun hala cüzdanda olup olmadığını kontrol et
                     // Eğer zaten harcanmışsa (işlem oluşturulduğunda çıkarılmışsa) tekrar çıkarma
-                    let utxo_exists = self.utxos.iter().any(|utxo| {
-                        utxo.transaction_id == *tx_id && utxo.output_index == output_index
-                    });
+                    // (ID, 10 ve üstü çıktı sıralarında da doğru eşleşsin diye son haneden çözümlenmez, UTXO'larla karşılaştırılır)
+                    let spent = self.utxos
+                        .iter()
+                        .find(|utxo| get_utxo_id(&utxo.transaction_id, utxo.output_index) == input.utxo_id)
+                        .map(|utxo| (utxo.transaction_id.clone(), utxo.output_index));
                     
-                    if utxo_exists {
+                    if let Some((tx_id, output_index)) = spent {
                         // UTXO hala cüzdanda, çıkar
-                        self.remove_utxo(tx_id, output_index);
+                        self.remove_utxo(&tx_id, output_index);
                     }
                 }
             }
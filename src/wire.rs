@@ -0,0 +1,570 @@
+use std::io::{self, Read, Write};
+
+use crate::block::{Block, BlockHeader};
+use crate::error::WireError;
+use crate::finality::{Proposal, Vote, VoteKind};
+use crate::message::{InvItem, Message};
+use crate::slashing::EquivocationEvidence;
+use crate::transaction::{Transaction, TxInput, TxOutput};
+
+// Protokol sürümü; el sıkışmada karşı tarafla aynı olmalı
+pub const PROTOCOL_VERSION: u32 = 4;
+
+// Tek bir çerçevenin en fazla boyutu (byte); daha büyük uzunluk bildiren bağlantı kesilir
+pub const MAX_FRAME_SIZE: usize = 32 * 1024 * 1024;
+
+// TCP üzerinden gönderilen paketler
+#[derive(Debug, Clone)]
+pub enum Packet {
+    // Bağlantı kurulunca iki taraf da kendini tanıtır
+    Version { version: u32, node_id: usize, height: usize, listen_port: u16, address: String },
+    // Karşı tarafın sürüm mesajı kabul edildi
+    VerAck,
+    // El sıkışmadan sonra gönderilen protokol mesajları
+    Message(Message),
+}
+
+// Paketi byte dizisine çevir
+// Tamsayılar little-endian, metinler ve listeler 4 byte uzunluk önekiyle yazılır
+pub fn encode_packet(packet: &Packet) -> Vec<u8> {
+    let mut encoder = Encoder::default();
+    match packet {
+        Packet::Version { version, node_id, height, listen_port, address } => {
+            encoder.put_u8(0);
+            encoder.put_u32(*version);
+            encoder.put_u64(*node_id as u64);
+            encoder.put_u64(*height as u64);
+            encoder.put_u32(*listen_port as u32);
+            encoder.put_str(address);
+        }
+        Packet::VerAck => encoder.put_u8(1),
+        Packet::Message(message) => {
+            encoder.put_u8(2);
+            encoder.put_message(message);
+        }
+    }
+    encoder.buf
+}
+
+// Byte dizisinden paketi çöz
+pub fn decode_packet(data: &[u8]) -> Result<Packet, WireError> {
+    let mut decoder = Decoder { data, pos: 0 };
+    let packet = match decoder.u8()? {
+        0 => Packet::Version {
+            version: decoder.u32()?,
+            node_id: decoder.u64()? as usize,
+            height: decoder.u64()? as usize,
+            listen_port: decoder.u32()? as u16,
+            address: decoder.string()?,
+        },
+        1 => Packet::VerAck,
+        2 => Packet::Message(decoder.message()?),
+        tag => return Err(WireError::UnknownTag(tag)),
+    };
+
+    if decoder.pos != data.len() {
+        return Err(WireError::TrailingBytes(data.len() - decoder.pos));
+    }
+    Ok(packet)
+}
+
+// Uzunluk önekli bir çerçeve yaz: 4 byte big-endian uzunluk + içerik
+pub fn write_frame<W: Write>(writer: &mut W, payload: &[u8]) -> io::Result<()> {
+    writer.write_all(&(payload.len() as u32).to_be_bytes())?;
+    writer.write_all(payload)?;
+    writer.flush()
+}
+
+// Uzunluk önekli bir çerçeve oku
+pub fn read_frame<R: Read>(reader: &mut R) -> io::Result<Vec<u8>> {
+    let mut length = [0u8; 4];
+    reader.read_exact(&mut length)?;
+    let length = u32::from_be_bytes(length) as usize;
+    if length > MAX_FRAME_SIZE {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, WireError::FrameTooLarge(length)));
+    }
+
+    let mut payload = vec![0u8; length];
+    reader.read_exact(&mut payload)?;
+    Ok(payload)
+}
+
+// Paketi çerçeve olarak yaz
+pub fn write_packet<W: Write>(writer: &mut W, packet: &Packet) -> io::Result<()> {
+    write_frame(writer, &encode_packet(packet))
+}
+
+// Bir çerçeve okuyup paketi çöz
+pub fn read_packet<R: Read>(reader: &mut R) -> io::Result<Packet> {
+    let frame = read_frame(reader)?;
+    decode_packet(&frame).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+#[derive(Default)]
+struct Encoder {
+    buf: Vec<u8>,
+}
+
+impl Encoder {
+    fn put_u8(&mut self, value: u8) {
+        self.buf.push(value);
+    }
+
+    fn put_u32(&mut self, value: u32) {
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn put_u64(&mut self, value: u64) {
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn put_bytes(&mut self, value: &[u8]) {
+        self.put_u32(value.len() as u32);
+        self.buf.extend_from_slice(value);
+    }
+
+    fn put_str(&mut self, value: &str) {
+        self.put_bytes(value.as_bytes());
+    }
+
+    fn put_message(&mut self, message: &Message) {
+        match message {
+            Message::Block(block) => {
+                self.put_u8(0);
+                self.put_block(block);
+            }
+            Message::Transaction(tx) => {
+                self.put_u8(1);
+                self.put_transaction(tx);
+            }
+            Message::Inv(items) => {
+                self.put_u8(2);
+                self.put_items(items);
+            }
+            Message::GetData(items) => {
+                self.put_u8(3);
+                self.put_items(items);
+            }
+            Message::GetHeaders { locator } => {
+                self.put_u8(4);
+                self.put_u32(locator.len() as u32);
+                for hash in locator {
+                    self.put_str(hash);
+                }
+            }
+            Message::Headers(headers) => {
+                self.put_u8(5);
+                self.put_u32(headers.len() as u32);
+                for header in headers {
+                    self.put_header(header);
+                }
+            }
+            Message::Evidence(evidence) => {
+                self.put_u8(6);
+                self.put_evidence(evidence);
+            }
+            Message::Proposal(proposal) => {
+                self.put_u8(7);
+                self.put_u64(proposal.height as u64);
+                self.put_u32(proposal.round);
+                self.put_str(&proposal.block_hash);
+                self.put_option_u32(proposal.valid_round);
+                self.put_str(&proposal.proposer);
+                self.put_bytes(&proposal.signature);
+            }
+            Message::Vote(vote) => {
+                self.put_u8(8);
+                self.put_u64(vote.height as u64);
+                self.put_u32(vote.round);
+                self.put_u8(match vote.kind {
+                    VoteKind::Prevote => 0,
+                    VoteKind::Precommit => 1,
+                });
+                match &vote.block_hash {
+                    Some(hash) => {
+                        self.put_u8(1);
+                        self.put_str(hash);
+                    }
+                    None => self.put_u8(0),
+                }
+                self.put_str(&vote.validator);
+                self.put_bytes(&vote.signature);
+            }
+        }
+    }
+
+    fn put_option_u32(&mut self, value: Option<u32>) {
+        match value {
+            Some(value) => {
+                self.put_u8(1);
+                self.put_u32(value);
+            }
+            None => self.put_u8(0),
+        }
+    }
+
+    fn put_items(&mut self, items: &[InvItem]) {
+        self.put_u32(items.len() as u32);
+        for item in items {
+            match item {
+                InvItem::Block(hash) => {
+                    self.put_u8(0);
+                    self.put_str(hash);
+                }
+                InvItem::Transaction(id) => {
+                    self.put_u8(1);
+                    self.put_str(id);
+                }
+            }
+        }
+    }
+
+    fn put_header(&mut self, header: &BlockHeader) {
+        self.put_u64(header.index as u64);
+        self.put_u64(header.timestamp);
+        self.put_str(&header.previous_hash);
+        self.put_str(&header.hash);
+        self.put_u64(header.nonce);
+        self.put_str(&header.merkle_root);
+        self.put_str(&header.proposer);
+        self.put_bytes(&header.signature);
+    }
+
+    fn put_evidence(&mut self, evidence: &EquivocationEvidence) {
+        self.put_header(&evidence.first);
+        self.put_header(&evidence.second);
+    }
+
+    fn put_block(&mut self, block: &Block) {
+        self.put_header(&block.header());
+        self.put_u32(block.transactions.len() as u32);
+        for tx in &block.transactions {
+            self.put_transaction(tx);
+        }
+        self.put_u32(block.evidence.len() as u32);
+        for evidence in &block.evidence {
+            self.put_evidence(evidence);
+        }
+    }
+
+    fn put_transaction(&mut self, tx: &Transaction) {
+        self.put_str(&tx.id);
+        self.put_u64(tx.timestamp);
+        self.put_u32(tx.inputs.len() as u32);
+        for input in &tx.inputs {
+            self.put_str(&input.utxo_id);
+            self.put_u64(input.utxo_output_index as u64);
+            self.put_bytes(&input.signature);
+            self.put_str(&input.sender_address);
+        }
+        self.put_u32(tx.outputs.len() as u32);
+        for output in &tx.outputs {
+            self.put_u64(output.amount);
+            self.put_str(&output.recipient_address);
+        }
+    }
+}
+
+struct Decoder<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl Decoder<'_> {
+    fn take(&mut self, length: usize) -> Result<&[u8], WireError> {
+        let end = self.pos.checked_add(length).filter(|&end| end <= self.data.len()).ok_or(WireError::UnexpectedEof)?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, WireError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, WireError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, WireError> {
+        let bytes = self.take(8)?;
+        Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    // Liste uzunluğu; kalan veriden büyük uzunluklar için boşuna bellek ayrılmaz
+    fn len(&mut self) -> Result<usize, WireError> {
+        let length = self.u32()? as usize;
+        if length > self.data.len() - self.pos {
+            return Err(WireError::UnexpectedEof);
+        }
+        Ok(length)
+    }
+
+    fn bytes(&mut self) -> Result<Vec<u8>, WireError> {
+        let length = self.len()?;
+        Ok(self.take(length)?.to_vec())
+    }
+
+    fn string(&mut self) -> Result<String, WireError> {
+        String::from_utf8(self.bytes()?).map_err(|_| WireError::InvalidUtf8)
+    }
+
+    fn message(&mut self) -> Result<Message, WireError> {
+        let message = match self.u8()? {
+            0 => Message::Block(self.block()?),
+            1 => Message::Transaction(self.transaction()?),
+            2 => Message::Inv(self.items()?),
+            3 => Message::GetData(self.items()?),
+            4 => {
+                let count = self.len()?;
+                let mut locator = Vec::with_capacity(count);
+                for _ in 0..count {
+                    locator.push(self.string()?);
+                }
+                Message::GetHeaders { locator }
+            }
+            5 => {
+                let count = self.len()?;
+                let mut headers = Vec::with_capacity(count);
+                for _ in 0..count {
+                    headers.push(self.header()?);
+                }
+                Message::Headers(headers)
+            }
+            6 => Message::Evidence(Box::new(self.evidence()?)),
+            7 => Message::Proposal(Box::new(Proposal {
+                height: self.u64()? as usize,
+                round: self.u32()?,
+                block_hash: self.string()?,
+                valid_round: match self.u8()? {
+                    0 => None,
+                    _ => Some(self.u32()?),
+                },
+                proposer: self.string()?,
+                signature: self.bytes()?,
+            })),
+            8 => Message::Vote(Box::new(Vote {
+                height: self.u64()? as usize,
+                round: self.u32()?,
+                kind: match self.u8()? {
+                    0 => VoteKind::Prevote,
+                    1 => VoteKind::Precommit,
+                    tag => return Err(WireError::UnknownTag(tag)),
+                },
+                block_hash: match self.u8()? {
+                    0 => None,
+                    _ => Some(self.string()?),
+                },
+                validator: self.string()?,
+                signature: self.bytes()?,
+            })),
+            tag => return Err(WireError::UnknownTag(tag)),
+        };
+        Ok(message)
+    }
+
+    fn items(&mut self) -> Result<Vec<InvItem>, WireError> {
+        let count = self.len()?;
+        let mut items = Vec::with_capacity(count);
+        for _ in 0..count {
+            let item = match self.u8()? {
+                0 => InvItem::Block(self.string()?),
+                1 => InvItem::Transaction(self.string()?),
+                tag => return Err(WireError::UnknownTag(tag)),
+            };
+            items.push(item);
+        }
+        Ok(items)
+    }
+
+    fn header(&mut self) -> Result<BlockHeader, WireError> {
+        Ok(BlockHeader {
+            index: self.u64()? as usize,
+            timestamp: self.u64()?,
+            previous_hash: self.string()?,
+            hash: self.string()?,
+            nonce: self.u64()?,
+            merkle_root: self.string()?,
+            proposer: self.string()?,
+            signature: self.bytes()?,
+        })
+    }
+
+    fn evidence(&mut self) -> Result<EquivocationEvidence, WireError> {
+        Ok(EquivocationEvidence {
+            first: self.header()?,
+            second: self.header()?,
+        })
+    }
+
+    fn block(&mut self) -> Result<Block, WireError> {
+        let header = self.header()?;
+        let count = self.len()?;
+        let mut transactions = Vec::with_capacity(count);
+        for _ in 0..count {
+            transactions.push(self.transaction()?);
+        }
+        let count = self.len()?;
+        let mut evidence = Vec::with_capacity(count);
+        for _ in 0..count {
+            evidence.push(self.evidence()?);
+        }
+
+        Ok(Block {
+            index: header.index,
+            timestamp: header.timestamp,
+            transactions,
+            previous_hash: header.previous_hash,
+            hash: header.hash,
+            nonce: header.nonce,
+            merkle_root: header.merkle_root,
+            proposer: header.proposer,
+            signature: header.signature,
+            evidence,
+        })
+    }
+
+    fn transaction(&mut self) -> Result<Transaction, WireError> {
+        let id = self.string()?;
+        let timestamp = self.u64()?;
+
+        let input_count = self.len()?;
+        let mut inputs = Vec::with_capacity(input_count);
+        for _ in 0..input_count {
+            inputs.push(TxInput {
+                utxo_id: self.string()?,
+                utxo_output_index: self.u64()? as usize,
+                signature: self.bytes()?,
+                sender_address: self.string()?,
+            });
+        }
+
+        let output_count = self.len()?;
+        let mut outputs = Vec::with_capacity(output_count);
+        for _ in 0..output_count {
+            outputs.push(TxOutput {
+                amount: self.u64()?,
+                recipient_address: self.string()?,
+            });
+        }
+
+        Ok(Transaction { id, inputs, outputs, timestamp })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wallet::Wallet;
+
+    // Block PartialEq türetmediği için mesajlar Debug çıktılarıyla karşılaştırılır
+    fn assert_round_trip(packet: Packet) {
+        let encoded = encode_packet(&packet);
+        let decoded = decode_packet(&encoded).expect("paket çözülemedi");
+        assert_eq!(format!("{:?}", decoded), format!("{:?}", packet));
+        assert_eq!(encode_packet(&decoded), encoded);
+    }
+
+    fn signed_block(wallet: &Wallet, index: usize, timestamp: u64) -> Block {
+        let coinbase = Transaction::new_coinbase_at(wallet.get_address().to_string(), 50, timestamp, index as u64);
+        let mut block = Block::new(index, timestamp, vec![coinbase], "0".repeat(64));
+        block.sign(wallet);
+        block
+    }
+
+    fn sample_transaction() -> Transaction {
+        let input = TxInput { utxo_id: "a".repeat(64), utxo_output_index: 1, signature: vec![1, 2, 3], sender_address: "gönderen".to_string() };
+        let outputs = vec![
+            TxOutput { amount: 30, recipient_address: "alıcı".to_string() },
+            TxOutput { amount: u64::MAX, recipient_address: String::new() },
+        ];
+        Transaction::new_at(vec![input], outputs, 1_700_000_000)
+    }
+
+    #[test]
+    fn handshake_packets_round_trip() {
+        assert_round_trip(Packet::Version { version: PROTOCOL_VERSION, node_id: 3, height: 42, listen_port: 8333, address: "127.0.0.1:8333".to_string() });
+        assert_round_trip(Packet::VerAck);
+    }
+
+    #[test]
+    fn every_message_round_trips() {
+        let wallet = Wallet::new();
+        let block = signed_block(&wallet, 7, 1_700_000_000);
+        let mut other = signed_block(&wallet, 7, 1_700_000_001);
+        other.transactions.push(sample_transaction());
+        let evidence = EquivocationEvidence::new(block.header(), other.header());
+        let mut with_evidence = Block::with_evidence(8, 1_700_000_002, vec![sample_transaction()], vec![evidence.clone()], block.hash.clone());
+        with_evidence.sign(&wallet);
+        let items = vec![InvItem::Block(block.hash.clone()), InvItem::Transaction("b".repeat(64))];
+
+        let messages = vec![
+            Message::Block(block.clone()),
+            Message::Block(with_evidence),
+            Message::Transaction(sample_transaction()),
+            Message::Inv(items.clone()),
+            Message::Inv(Vec::new()),
+            Message::GetData(items),
+            Message::GetHeaders { locator: vec![block.hash.clone(), other.hash.clone()] },
+            Message::Headers(vec![block.header(), other.header()]),
+            Message::Evidence(Box::new(evidence)),
+            Message::Proposal(Box::new(Proposal::new(7, 0, block.hash.clone(), None, &wallet))),
+            Message::Proposal(Box::new(Proposal::new(7, 2, block.hash.clone(), Some(1), &wallet))),
+            Message::Vote(Box::new(Vote::new(7, 0, VoteKind::Prevote, Some(block.hash.clone()), &wallet))),
+            Message::Vote(Box::new(Vote::new(7, 1, VoteKind::Precommit, None, &wallet))),
+        ];
+        for message in messages {
+            assert_round_trip(Packet::Message(message));
+        }
+    }
+
+    #[test]
+    fn truncated_packets_are_rejected() {
+        let wallet = Wallet::new();
+        let encoded = encode_packet(&Packet::Message(Message::Block(signed_block(&wallet, 1, 1_700_000_000))));
+        for length in 0..encoded.len() {
+            assert!(decode_packet(&encoded[..length]).is_err(), "{} byte kabul edildi", length);
+        }
+
+        let mut trailing = encode_packet(&Packet::VerAck);
+        trailing.push(0);
+        assert_eq!(decode_packet(&trailing).unwrap_err(), WireError::TrailingBytes(1));
+        assert_eq!(decode_packet(&[9]).unwrap_err(), WireError::UnknownTag(9));
+    }
+
+    #[test]
+    fn frames_round_trip() {
+        let mut buffer = Vec::new();
+        write_packet(&mut buffer, &Packet::VerAck).unwrap();
+        write_frame(&mut buffer, b"merhaba").unwrap();
+
+        let mut reader = buffer.as_slice();
+        assert!(matches!(read_packet(&mut reader).unwrap(), Packet::VerAck));
+        assert_eq!(read_frame(&mut reader).unwrap(), b"merhaba");
+        assert_eq!(read_frame(&mut reader).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn truncated_frames_are_rejected() {
+        let mut buffer = Vec::new();
+        write_frame(&mut buffer, b"merhaba").unwrap();
+        for length in 0..buffer.len() {
+            let error = read_frame(&mut &buffer[..length]).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+        }
+    }
+
+    #[test]
+    fn oversized_frames_are_rejected() {
+        let mut buffer = ((MAX_FRAME_SIZE + 1) as u32).to_be_bytes().to_vec();
+        buffer.extend_from_slice(&[0; 16]);
+        let error = read_frame(&mut buffer.as_slice()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(error.to_string(), WireError::FrameTooLarge(MAX_FRAME_SIZE + 1).to_string());
+
+        // Sınırdaki uzunluk reddedilmez; içerik eksik olduğu için okuma EOF ile biter
+        let buffer = (MAX_FRAME_SIZE as u32).to_be_bytes();
+        assert_eq!(read_frame(&mut buffer.as_slice()).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
@@ -0,0 +1,375 @@
+use rand::Rng;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::distribution;
+use crate::error::{NetworkError, TransactionError, WalletError};
+use crate::network::{BlockchainNetwork, SimEvent};
+use crate::node::BLOCK_TRANSACTION_LIMIT;
+use crate::sim::SimTime;
+
+// Sentetik işlem yükünün parametreleri
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Workload {
+    pub rate: f64,              // Saniyede ortalama işlem sayısı; işlemler Poisson süreciyle gelir
+    pub min_amount: u64,        // Pareto miktar dağılımının ölçeği, yani en küçük ödeme (satoshi)
+    pub amount_shape: f64,      // Pareto şekil parametresi; küçüldükçe büyük ödemeler sıklaşır
+    pub hotspots: usize,        // Ödemelerin yoğunlaştığı sıcak alıcı sayısı
+    pub hotspot_share: f64,     // Ödemelerin sıcak alıcılara giden payı
+    pub fan_out: usize,         // Toplu ödemelerdeki en fazla alıcı sayısı
+    pub batch_probability: f64, // Bir işlemin birden fazla alıcıya ödeyen toplu işlem olma olasılığı
+}
+
+impl Default for Workload {
+    fn default() -> Self {
+        Workload {
+            rate: 1.0,
+            min_amount: 1_000_000, // 0.01 coin
+            amount_shape: 1.5,
+            hotspots: 0,
+            hotspot_share: 0.0,
+            fan_out: 5,
+            batch_probability: 0.0,
+        }
+    }
+}
+
+impl Workload {
+    // Parametreleri kontrol et
+    pub fn validate(&self) -> Result<(), NetworkError> {
+        if !(self.rate.is_finite() && self.rate > 0.0) {
+            return Err(NetworkError::InvalidWorkload(format!("işlem oranı pozitif olmalı: {}", self.rate)));
+        }
+        if self.min_amount == 0 || !(self.amount_shape.is_finite() && self.amount_shape > 0.0) {
+            return Err(NetworkError::InvalidWorkload(format!(
+                "miktar dağılımının parametreleri pozitif olmalı: {} / {}",
+                self.min_amount, self.amount_shape
+            )));
+        }
+        if self.fan_out == 0 {
+            return Err(NetworkError::InvalidWorkload("toplu ödemelerde en az bir alıcı olmalı".to_string()));
+        }
+        if !(0.0..=1.0).contains(&self.hotspot_share) {
+            return Err(NetworkError::InvalidShare { name: "sıcak alıcı payı", value: self.hotspot_share.to_string() });
+        }
+        if !(0.0..=1.0).contains(&self.batch_probability) {
+            return Err(NetworkError::InvalidShare { name: "toplu ödeme olasılığı", value: self.batch_probability.to_string() });
+        }
+        Ok(())
+    }
+
+    // Bir ödeme miktarı örnekle
+    fn sample_amount<R: Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+        let amount = distribution::pareto(rng, self.min_amount as f64, self.amount_shape);
+        amount.round().min(u64::MAX as f64) as u64
+    }
+
+    // Bir sonraki işleme kadar geçecek süre (en az 1 ms)
+    // Çok yüksek oranlarda sıfır aralık, simülasyon saatini ilerletmeyen sonsuz bir döngü olurdu.
+    fn sample_interval<R: Rng + ?Sized>(&self, rng: &mut R) -> SimTime {
+        (distribution::exponential(rng, 1000.0 / self.rate).round() as SimTime).max(1)
+    }
+}
+
+impl fmt::Display for Workload {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "saniyede {} işlem; miktar Pareto, en az {} coin, şekil {}",
+            self.rate,
+            self.min_amount as f64 / 100_000_000.0,
+            self.amount_shape
+        )?;
+        if self.hotspots > 0 && self.hotspot_share > 0.0 {
+            write!(f, "; ödemelerin %{:.0}'i {} sıcak alıcıya", self.hotspot_share * 100.0, self.hotspots)?;
+        }
+        if self.batch_probability > 0.0 && self.fan_out > 1 {
+            write!(f, "; işlemlerin %{:.0}'i en fazla {} alıcılı toplu ödeme", self.batch_probability * 100.0, self.fan_out)?;
+        }
+        Ok(())
+    }
+}
+
+// Yük üretecinin gönderdiği bir işlem
+#[derive(Debug, Clone)]
+pub struct WorkloadTransaction {
+    pub id: String,
+    pub sender: usize,
+    pub recipients: Vec<usize>,
+    pub amount: u64, // Alıcılara ödenen toplam (para üstü hariç)
+    pub submitted_at: SimTime,
+}
+
+// Çalışan (veya durdurulmuş) yük üretecinin durumu
+#[derive(Debug, Clone)]
+pub struct WorkloadGenerator {
+    pub config: Workload,
+    pub senders: Vec<usize>,        // İşlem gönderebilen node'lar (boşsa tüm node'lar)
+    pub hot_recipients: Vec<usize>, // Başlarken rastgele seçilen sıcak alıcılar
+    pub started_at: SimTime,
+    pub stopped_at: Option<SimTime>,
+    pub transactions: Vec<WorkloadTransaction>,
+    pub insufficient_funds: usize,  // Göndericinin harcanabilir bakiyesi yetmediği için gönderilemeyen işlemler
+    pub failed: usize,              // Başka nedenlerle (çevrimdışı gönderici vb.) gönderilemeyen işlemler
+    pub peak_mempool: usize,        // Çevrimiçi node'ların mempool'larında görülen en büyük işlem sayısı
+}
+
+// Yük altında mempool'un, blok üretiminin ve onay süresinin durumu
+#[derive(Debug, Clone)]
+pub struct WorkloadReport {
+    pub config: Workload,
+    pub running: bool,
+    pub elapsed: SimTime,                          // Yükün uygulandığı süre (ms)
+    pub submitted: usize,                          // Gönderilen işlemler
+    pub payments: usize,                           // Gönderilen işlemlerdeki alıcı çıktısı sayısı
+    pub total_amount: u64,
+    pub insufficient_funds: usize,
+    pub failed: usize,
+    pub offered_tps: f64,                          // Saniyede gönderilen işlem
+    pub confirmed: usize,                          // En uzun zincire giren işlemler
+    pub confirmed_tps: f64,                        // Saniyede onaylanan işlem
+    pub average_confirmation_time: Option<f64>,    // Gönderimden bloğun üretilmesine kadar geçen süre ortalaması (ms)
+    pub max_confirmation_time: Option<SimTime>,
+    pub pending: usize,                            // Gönderilmiş ama henüz zincire girmemiş işlemler
+    pub peak_mempool: usize,
+    pub average_mempool: f64,                      // Çevrimiçi node'ların şu anki mempool büyüklüğü ortalaması
+    pub blocks: usize,                             // Yük başladıktan sonra en uzun zincire giren bloklar
+    pub full_blocks: usize,                        // İşlem sınırına ulaşmış bloklar
+    pub hot_recipients: Vec<usize>,
+    pub hot_payments: usize,                       // Sıcak alıcılara giden ödeme çıktıları
+}
+
+impl fmt::Display for WorkloadReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "İşlem yükü raporu ({}):", if self.running { "sürüyor" } else { "durduruldu" })?;
+        writeln!(f, "  Model: {}", self.config)?;
+        writeln!(f, "  Süre: {} ms", self.elapsed)?;
+        writeln!(
+            f,
+            "  Gönderilen: {} işlem, {} ödeme, toplam {} coin (saniyede {:.2} işlem)",
+            self.submitted,
+            self.payments,
+            self.total_amount as f64 / 100_000_000.0,
+            self.offered_tps
+        )?;
+        writeln!(f, "  Gönderilemeyen: {} yetersiz bakiye, {} diğer", self.insufficient_funds, self.failed)?;
+        writeln!(f, "  Onaylanan: {} işlem (saniyede {:.2}), bekleyen: {}", self.confirmed, self.confirmed_tps, self.pending)?;
+        match (self.average_confirmation_time, self.max_confirmation_time) {
+            (Some(average), Some(max)) => writeln!(f, "  Onay süresi: ortalama {:.0} ms, en fazla {} ms", average, max)?,
+            _ => writeln!(f, "  Onay süresi: -")?,
+        }
+        writeln!(f, "  Mempool: şu an ortalama {:.1} işlem, en fazla {} işlem", self.average_mempool, self.peak_mempool)?;
+        write!(f, "  Bloklar: {} ({} tanesi {} işlemlik sınırda dolu)", self.blocks, self.full_blocks, BLOCK_TRANSACTION_LIMIT)?;
+        if !self.hot_recipients.is_empty() {
+            let share = if self.payments > 0 { self.hot_payments as f64 / self.payments as f64 } else { 0.0 };
+            write!(f, "\n  Sıcak alıcılar {:?}: ödemelerin %{:.1}'i", self.hot_recipients, share * 100.0)?;
+        }
+        Ok(())
+    }
+}
+
+impl BlockchainNetwork {
+    // Node cüzdanları arasında sentetik işlem yükü başlat (senders boşsa tüm node'lar gönderir)
+    // Çalışan bir yük varsa yerini yenisi alır.
+    pub fn start_workload(&mut self, config: Workload, senders: &[usize]) -> Result<(), NetworkError> {
+        config.validate()?;
+        if self.nodes.len() < 2 {
+            return Err(NetworkError::InvalidWorkload("işlem yükü için en az iki node gerekli".to_string()));
+        }
+        if let Some(&missing) = senders.iter().find(|&&id| id >= self.nodes.len()) {
+            return Err(NetworkError::NodeNotFound(missing));
+        }
+
+        // Sıcak alıcılar tüm node'lar arasından rastgele seçilir
+        let mut candidates: Vec<usize> = (0..self.nodes.len()).collect();
+        let mut hot_recipients = Vec::new();
+        while hot_recipients.len() < config.hotspots.min(self.nodes.len()) {
+            let index = self.rng.random_range(0..candidates.len());
+            hot_recipients.push(candidates.swap_remove(index));
+        }
+        hot_recipients.sort_unstable();
+
+        self.cancel_workload_events();
+        self.workload = Some(WorkloadGenerator {
+            config,
+            senders: senders.to_vec(),
+            hot_recipients,
+            started_at: self.now(),
+            stopped_at: None,
+            transactions: Vec::new(),
+            insufficient_funds: 0,
+            failed: 0,
+            peak_mempool: 0,
+        });
+        let delay = config.sample_interval(&mut self.rng);
+        self.scheduler.schedule_in(delay, SimEvent::WorkloadArrival);
+        self.process_due_events();
+        Ok(())
+    }
+
+    // Yükü durdur; rapor sonradan da alınabilir
+    pub fn stop_workload(&mut self) -> Option<WorkloadReport> {
+        self.cancel_workload_events();
+        let now = self.now();
+        let generator = self.workload.as_mut()?;
+        generator.stopped_at.get_or_insert(now);
+        self.workload_report()
+    }
+
+    // Planlanmış işlem geldi: gönderici, alıcılar ve miktarlar örneklenir ve işlem ağa verilir
+    pub(crate) fn handle_workload_arrival(&mut self) {
+        let generator = match self.workload.as_ref() {
+            Some(generator) if generator.stopped_at.is_none() => generator,
+            _ => return,
+        };
+        let config = generator.config;
+        let hot_recipients = generator.hot_recipients.clone();
+        let senders: Vec<usize> = if generator.senders.is_empty() {
+            (0..self.nodes.len()).collect()
+        } else {
+            generator.senders.clone()
+        };
+        let online: Vec<usize> = senders.into_iter().filter(|&id| self.is_online(id)).collect();
+
+        // Çevrimiçi gönderici yoksa işlem gönderilemez
+        let result = if online.is_empty() {
+            None
+        } else {
+            let sender = online[self.rng.random_range(0..online.len())];
+            let count = if config.fan_out > 1 && self.rng.random_bool(config.batch_probability) {
+                self.rng.random_range(2..=config.fan_out)
+            } else {
+                1
+            };
+            let recipients: Vec<usize> = (0..count).map(|_| self.sample_recipient(sender, &config, &hot_recipients)).collect();
+            let payments: Vec<(String, u64)> = recipients
+                .iter()
+                .map(|&recipient| (self.get_node_address(recipient), config.sample_amount(&mut self.rng)))
+                .collect();
+            let amount = payments.iter().fold(0u64, |total, (_, amount)| total.saturating_add(*amount));
+            Some(self.submit_payment(sender, &payments).map(|tx| WorkloadTransaction {
+                id: tx.id,
+                sender,
+                recipients,
+                amount,
+                submitted_at: self.now(),
+            }))
+        };
+
+        let peak = self.nodes.iter().filter(|node| node.online).map(|node| node.mempool.len()).max().unwrap_or(0);
+        if let Some(generator) = self.workload.as_mut() {
+            match result {
+                Some(Ok(transaction)) => generator.transactions.push(transaction),
+                Some(Err(NetworkError::Transaction(TransactionError::Wallet(WalletError::InsufficientFunds { .. })))) => {
+                    generator.insufficient_funds += 1;
+                }
+                Some(Err(_)) | None => generator.failed += 1,
+            }
+            generator.peak_mempool = generator.peak_mempool.max(peak);
+        }
+
+        let delay = config.sample_interval(&mut self.rng);
+        self.scheduler.schedule_in(delay, SimEvent::WorkloadArrival);
+    }
+
+    // Son (veya çalışan) yükün raporu
+    pub fn workload_report(&self) -> Option<WorkloadReport> {
+        let generator = self.workload.as_ref()?;
+        let now = self.now();
+        let elapsed = generator.stopped_at.unwrap_or(now).saturating_sub(generator.started_at);
+        let seconds = elapsed as f64 / 1000.0;
+
+        // En uzun zincirdeki işlemler ve bloklarının üretildiği an
+        let chain = self.canonical_chain();
+        let mut mined_at: HashMap<&str, Option<SimTime>> = HashMap::new();
+        let mut blocks = 0;
+        let mut full_blocks = 0;
+        for block in chain {
+            let time = self.propagation.get(&block.hash).map(|record| record.mined_at);
+            if time.map(|time| time >= generator.started_at).unwrap_or(false) {
+                blocks += 1;
+                if block.transactions.len() >= BLOCK_TRANSACTION_LIMIT {
+                    full_blocks += 1;
+                }
+            }
+            for tx in &block.transactions {
+                mined_at.insert(tx.id.as_str(), time);
+            }
+        }
+
+        let mut confirmed = 0;
+        let mut confirmation_times = Vec::new();
+        for tx in &generator.transactions {
+            if let Some(time) = mined_at.get(tx.id.as_str()) {
+                confirmed += 1;
+                if let Some(time) = time {
+                    confirmation_times.push(time.saturating_sub(tx.submitted_at));
+                }
+            }
+        }
+
+        let hot: HashSet<usize> = generator.hot_recipients.iter().copied().collect();
+        let online: Vec<usize> = self.nodes.iter().filter(|node| node.online).map(|node| node.mempool.len()).collect();
+        let rate = |count: usize| if seconds > 0.0 { count as f64 / seconds } else { 0.0 };
+        Some(WorkloadReport {
+            config: generator.config,
+            running: generator.stopped_at.is_none(),
+            elapsed,
+            submitted: generator.transactions.len(),
+            payments: generator.transactions.iter().map(|tx| tx.recipients.len()).sum(),
+            total_amount: generator.transactions.iter().fold(0u64, |total, tx| total.saturating_add(tx.amount)),
+            insufficient_funds: generator.insufficient_funds,
+            failed: generator.failed,
+            offered_tps: rate(generator.transactions.len()),
+            confirmed,
+            confirmed_tps: rate(confirmed),
+            average_confirmation_time: if confirmation_times.is_empty() {
+                None
+            } else {
+                Some(confirmation_times.iter().sum::<SimTime>() as f64 / confirmation_times.len() as f64)
+            },
+            max_confirmation_time: confirmation_times.iter().copied().max(),
+            pending: generator.transactions.len() - confirmed,
+            peak_mempool: generator.peak_mempool,
+            average_mempool: if online.is_empty() { 0.0 } else { online.iter().sum::<usize>() as f64 / online.len() as f64 },
+            blocks,
+            full_blocks,
+            hot_recipients: generator.hot_recipients.clone(),
+            hot_payments: generator.transactions.iter().flat_map(|tx| tx.recipients.iter()).filter(|id| hot.contains(id)).count(),
+        })
+    }
+
+    // Göndericiden farklı bir alıcı seç; sıcak alıcılar verilen payla tercih edilir
+    fn sample_recipient(&mut self, sender: usize, config: &Workload, hot_recipients: &[usize]) -> usize {
+        let hot: Vec<usize> = hot_recipients.iter().copied().filter(|&id| id != sender).collect();
+        if !hot.is_empty() && self.rng.random_bool(config.hotspot_share) {
+            return hot[self.rng.random_range(0..hot.len())];
+        }
+        // Gönderici hariç düzgün dağılım
+        let recipient = self.rng.random_range(0..self.nodes.len() - 1);
+        if recipient >= sender {
+            recipient + 1
+        } else {
+            recipient
+        }
+    }
+
+    fn cancel_workload_events(&mut self) {
+        self.scheduler.retain(|event| !matches!(event, SimEvent::WorkloadArrival));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn arrival_interval_is_at_least_one_millisecond() {
+        let workload = Workload { rate: 1e9, ..Workload::default() };
+        let mut rng = StdRng::seed_from_u64(1);
+        assert!((0..1000).all(|_| workload.sample_interval(&mut rng) >= 1));
+    }
+}